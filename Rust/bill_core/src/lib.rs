@@ -0,0 +1,15 @@
+#![no_std]
+
+extern crate alloc;
+
+mod method;
+mod money;
+mod priority;
+mod schedule;
+mod split;
+
+pub use method::Method;
+pub use money::{Money, ParseMoneyError, Rounding};
+pub use priority::Priority;
+pub use schedule::{Period, Recurrence};
+pub use split::{is_valid_name, percent_to_basis_points, Share, Split};
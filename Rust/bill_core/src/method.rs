@@ -0,0 +1,44 @@
+use alloc::{format, string::String, vec::Vec};
+use core::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Method {
+    DirectDebit,
+    Card,
+    Transfer,
+    Cash,
+}
+impl Method {
+    pub const ALL: [Method; 4] = [
+        Method::DirectDebit,
+        Method::Card,
+        Method::Transfer,
+        Method::Cash,
+    ];
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Method::DirectDebit => write!(f, "direct debit"),
+            Method::Card => write!(f, "card"),
+            Method::Transfer => write!(f, "transfer"),
+            Method::Cash => write!(f, "cash"),
+        }
+    }
+}
+
+impl FromStr for Method {
+    type Err = String;
+
+    fn from_str(method: &str) -> Result<Self, Self::Err> {
+        let normalized = method.split_whitespace().collect::<Vec<_>>().join(" ");
+        match normalized.to_lowercase().as_str() {
+            "direct debit" | "debit" | "dd" => Ok(Method::DirectDebit),
+            "card" => Ok(Method::Card),
+            "transfer" => Ok(Method::Transfer),
+            "cash" => Ok(Method::Cash),
+            _ => Err(format!("invalid payment method {}", method)),
+        }
+    }
+}
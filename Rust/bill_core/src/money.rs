@@ -0,0 +1,199 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::{
+    fmt,
+    iter::Sum,
+    ops::{Add, AddAssign, Sub, SubAssign},
+    str::FromStr,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+#[error("invalid amount {0}")]
+pub struct ParseMoneyError(String);
+
+const DECIMALS: u32 = 2;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rounding {
+    #[default]
+    HalfUp,
+    HalfEven,
+    Down,
+    Up,
+}
+impl Rounding {
+    pub const ALL: [Rounding; 4] = [
+        Rounding::HalfUp,
+        Rounding::HalfEven,
+        Rounding::Down,
+        Rounding::Up,
+    ];
+}
+
+impl fmt::Display for Rounding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rounding::HalfUp => write!(f, "half-up"),
+            Rounding::HalfEven => write!(f, "half-even"),
+            Rounding::Down => write!(f, "down"),
+            Rounding::Up => write!(f, "up"),
+        }
+    }
+}
+
+impl FromStr for Rounding {
+    type Err = String;
+
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        Rounding::ALL
+            .into_iter()
+            .find(|rounding| rounding.to_string() == rule.trim().to_lowercase())
+            .ok_or_else(|| format!("invalid rounding {}", rule))
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money(i64);
+impl Money {
+    pub const ZERO: Money = Money(0);
+
+    pub fn cents(self) -> i64 {
+        self.0
+    }
+    pub fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+    pub fn abs(self) -> Money {
+        Money(self.0.abs())
+    }
+    pub fn scale(self, factor: f64) -> Money {
+        Money(libm::round(self.0 as f64 * factor) as i64)
+    }
+    pub fn as_f64(self) -> f64 {
+        self.0 as f64 / 10_u32.pow(DECIMALS) as f64
+    }
+    pub fn ratio(self, other: Money) -> f64 {
+        self.0 as f64 / other.0 as f64
+    }
+    pub fn round(self, decimals: u32, rounding: Rounding) -> Money {
+        let unit = 10_i64.pow(DECIMALS.saturating_sub(decimals));
+        let (whole, rest) = (self.0.abs() / unit, self.0.abs() % unit);
+        let up = match rounding {
+            Rounding::HalfUp => rest * 2 >= unit,
+            Rounding::HalfEven => rest * 2 > unit || (rest * 2 == unit && whole % 2 == 1),
+            Rounding::Down => false,
+            Rounding::Up => rest > 0,
+        };
+        let rounded = (whole + i64::from(up)) * unit;
+        Money(if self.0 < 0 { -rounded } else { rounded })
+    }
+    pub fn format(self, decimals: u32) -> String {
+        let decimals = decimals.min(DECIMALS);
+        let rounded = self.round(decimals, Rounding::HalfUp);
+        let sign = if rounded.0 < 0 { "-" } else { "" };
+        let cents = rounded.0.unsigned_abs();
+        let unit = 10_u64.pow(DECIMALS);
+        if decimals == 0 {
+            return format!("{}{}", sign, cents / unit);
+        }
+        let fraction = cents % unit / 10_u64.pow(DECIMALS - decimals);
+        format!(
+            "{}{}.{:0width$}",
+            sign,
+            cents / unit,
+            fraction,
+            width = decimals as usize
+        )
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.format(DECIMALS))
+    }
+}
+
+impl FromStr for Money {
+    type Err = ParseMoneyError;
+
+    fn from_str(amount: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseMoneyError(amount.to_string());
+        let (negative, digits) = match amount.trim().strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, amount.trim()),
+        };
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+        if (whole.is_empty() && fraction.is_empty()) || !is_digits(whole) || !is_digits(fraction) {
+            return Err(invalid());
+        }
+
+        let whole: i64 = match whole {
+            "" => 0,
+            whole => whole.parse().map_err(|_| invalid())?,
+        };
+        let mut fraction = fraction.bytes().map(|digit| i64::from(digit - b'0'));
+        let tenths = fraction.next().unwrap_or(0);
+        let hundredths = fraction.next().unwrap_or(0);
+        let round_up = fraction.next().is_some_and(|digit| digit >= 5);
+
+        let cents = whole
+            .checked_mul(100)
+            .and_then(|cents| cents.checked_add(tenths * 10 + hundredths + i64::from(round_up)))
+            .ok_or_else(invalid)?;
+        Ok(Money(if negative { -cents } else { cents }))
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        Money(self.0 + other.0)
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        Money(self.0 - other.0)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        self.0 += other.0;
+    }
+}
+
+impl SubAssign for Money {
+    fn sub_assign(&mut self, other: Money) {
+        self.0 -= other.0;
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::ZERO, Add::add)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scales_and_rounds_without_std() {
+        let amount: Money = "10.05".parse().unwrap();
+        assert_eq!(amount.scale(0.5).cents(), 503);
+        assert_eq!((Money::ZERO - amount).scale(0.5).cents(), -503);
+        assert_eq!(amount.as_f64(), 10.05);
+        assert_eq!(crate::percent_to_basis_points(33.335), 3334);
+    }
+}
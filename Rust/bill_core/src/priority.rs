@@ -0,0 +1,34 @@
+use alloc::{format, string::String};
+use core::{fmt, str::FromStr};
+
+/// Ordered so that sorting ascending lists the most important bills first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    High,
+    #[default]
+    Normal,
+    Low,
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Priority::High => write!(f, "high"),
+            Priority::Normal => write!(f, "normal"),
+            Priority::Low => write!(f, "low"),
+        }
+    }
+}
+
+impl FromStr for Priority {
+    type Err = String;
+
+    fn from_str(priority: &str) -> Result<Self, Self::Err> {
+        match priority.trim().to_lowercase().as_str() {
+            "high" | "h" => Ok(Priority::High),
+            "normal" | "n" => Ok(Priority::Normal),
+            "low" | "l" => Ok(Priority::Low),
+            _ => Err(format!("invalid priority {}", priority)),
+        }
+    }
+}
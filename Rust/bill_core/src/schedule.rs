@@ -0,0 +1,84 @@
+use alloc::{borrow::ToOwned, format, string::String, vec::Vec};
+use chrono::{Duration, Months, NaiveDate};
+use core::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Period {
+    Weekly,
+    Monthly,
+    Yearly,
+}
+impl Period {
+    fn unit(&self) -> &'static str {
+        match self {
+            Period::Weekly => "week",
+            Period::Monthly => "month",
+            Period::Yearly => "year",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Recurrence {
+    pub period: Period,
+    pub interval: u32,
+}
+impl Recurrence {
+    pub fn next_due(&self, due: NaiveDate) -> NaiveDate {
+        let next = match self.period {
+            Period::Weekly => due.checked_add_signed(Duration::weeks(self.interval.into())),
+            Period::Monthly => due.checked_add_months(Months::new(self.interval)),
+            Period::Yearly => due.checked_add_months(Months::new(self.interval * 12)),
+        };
+        next.unwrap_or(due)
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.interval == 1 {
+            match self.period {
+                Period::Weekly => write!(f, "weekly"),
+                Period::Monthly => write!(f, "monthly"),
+                Period::Yearly => write!(f, "yearly"),
+            }
+        } else {
+            write!(f, "every {} {}s", self.interval, self.period.unit())
+        }
+    }
+}
+
+impl FromStr for Recurrence {
+    type Err = String;
+
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        let rule = rule.trim().to_lowercase();
+        let (period, interval) = match rule.as_str() {
+            "weekly" => (Period::Weekly, 1),
+            "monthly" => (Period::Monthly, 1),
+            "yearly" => (Period::Yearly, 1),
+            _ => {
+                let words: Vec<&str> = rule.split_whitespace().collect();
+                let (interval, unit) = match words.as_slice() {
+                    ["every", interval, unit] => (*interval, *unit),
+                    _ => return Err(format!("unknown recurrence: {}", rule)),
+                };
+                let interval: u32 = interval
+                    .parse()
+                    .map_err(|_| format!("invalid interval: {}", interval))?;
+                let period = match unit.trim_end_matches('s') {
+                    "week" => Period::Weekly,
+                    "month" => Period::Monthly,
+                    "year" => Period::Yearly,
+                    _ => return Err(format!("unknown period: {}", unit)),
+                };
+                (period, interval)
+            }
+        };
+        if interval == 0 {
+            return Err("interval must be at least 1".to_owned());
+        }
+
+        Ok(Recurrence { period, interval })
+    }
+}
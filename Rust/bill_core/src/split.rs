@@ -0,0 +1,108 @@
+use crate::Money;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
+
+const WHOLE: u32 = 10_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Share {
+    pub person: String,
+    pub basis_points: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Split {
+    pub payer: String,
+    pub shares: Vec<Share>,
+}
+impl Split {
+    pub fn equal(payer: &str, people: &[String]) -> Split {
+        let count = people.len().max(1) as u32;
+        let shares = people
+            .iter()
+            .enumerate()
+            .map(|(num, person)| Share {
+                person: person.clone(),
+                basis_points: WHOLE / count + u32::from((num as u32) < WHOLE % count),
+            })
+            .collect();
+        Split {
+            payer: payer.to_string(),
+            shares,
+        }
+    }
+    fn total(&self) -> u32 {
+        self.shares.iter().map(|share| share.basis_points).sum()
+    }
+    pub fn total_percent(&self) -> f64 {
+        f64::from(self.total()) / 100.0
+    }
+    pub fn is_complete(&self) -> bool {
+        self.total() == WHOLE
+    }
+    pub fn owed(&self, amount: Money) -> Vec<(&str, Money)> {
+        self.shares
+            .iter()
+            .filter(|share| share.person != self.payer)
+            .map(|share| {
+                let owed = amount.scale(f64::from(share.basis_points) / f64::from(WHOLE));
+                (share.person.as_str(), owed)
+            })
+            .collect()
+    }
+}
+
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains([':', ',', '%'])
+}
+
+pub fn percent_to_basis_points(percent: f64) -> u32 {
+    libm::round(percent * 100.0) as u32
+}
+
+impl fmt::Display for Share {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let percent = f64::from(self.basis_points) / 100.0;
+        write!(f, "{} {}%", self.person, percent)
+    }
+}
+
+impl fmt::Display for Split {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let shares: Vec<String> = self.shares.iter().map(|share| share.to_string()).collect();
+        write!(f, "{}: {}", self.payer, shares.join(", "))
+    }
+}
+
+impl FromStr for Split {
+    type Err = String;
+
+    fn from_str(split: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid split {}", split);
+        let (payer, shares) = split.split_once(':').ok_or_else(invalid)?;
+        let shares = shares
+            .split(',')
+            .map(|share| {
+                let (person, percent) = share.trim().rsplit_once(' ')?;
+                let percent: f64 = percent.strip_suffix('%')?.parse().ok()?;
+                Some(Share {
+                    person: person.trim().to_string(),
+                    basis_points: percent_to_basis_points(percent),
+                })
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(invalid)?;
+        let payer = payer.trim();
+        if !is_valid_name(payer) || shares.iter().any(|share| !is_valid_name(&share.person)) {
+            return Err(invalid());
+        }
+        Ok(Split {
+            payer: payer.to_string(),
+            shares,
+        })
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{config, currency, Bill, Bills};
+use chrono::NaiveDateTime;
+use std::{collections::HashMap, io, path::Path};
+use vfs::Vfs;
+
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+const TIME_WIDTH: usize = 19;
+
+type Field = (&'static str, fn(&Bill) -> String);
+
+fn or_dash(value: Option<String>) -> String {
+    value.unwrap_or_else(|| "-".to_string())
+}
+
+const FIELDS: &[Field] = &[
+    ("name", |bill| bill.name.clone()),
+    ("amount", |bill| {
+        currency::format_amount(bill.amount, &bill.currency)
+    }),
+    ("due", |bill| or_dash(bill.due.map(config::format_date))),
+    ("repeats", |bill| {
+        or_dash(bill.recurrence.map(|r| r.to_string()))
+    }),
+    ("category", |bill| or_dash(bill.category.clone())),
+    ("notes", |bill| or_dash(bill.notes.clone())),
+    ("payee", |bill| {
+        or_dash(bill.payee.map(|id| format!("#{}", id)))
+    }),
+    ("split", |bill| {
+        or_dash(bill.split.as_ref().map(|s| s.to_string()))
+    }),
+    ("method", |bill| or_dash(bill.method.map(|m| m.to_string()))),
+    ("priority", |bill| bill.priority.to_string()),
+    ("receipts", |bill| bill.attachments.len().to_string()),
+    ("paid", |bill| or_dash(bill.paid.map(config::format_date))),
+];
+
+#[derive(Debug)]
+pub struct Entry {
+    pub time: NaiveDateTime,
+    pub id: i64,
+    pub text: String,
+}
+
+fn describe(before: Option<&Bill>, after: Option<&Bill>) -> Option<String> {
+    let amount = |bill: &Bill| currency::format_amount(bill.amount, &bill.currency);
+    match (before, after) {
+        (None, Some(bill)) => Some(format!("added {} {}", bill.name, amount(bill))),
+        (Some(bill), None) => Some(format!("removed {} {}", bill.name, amount(bill))),
+        (Some(old), Some(new)) if old != new => {
+            let payments: Vec<String> = new
+                .payments
+                .iter()
+                .skip(old.payments.len())
+                .map(|payment| {
+                    format!(
+                        "payment of {} on {}",
+                        currency::format_amount(payment.amount, &new.currency),
+                        config::format_date(payment.date)
+                    )
+                })
+                .collect();
+            let changes: Vec<String> = FIELDS
+                .iter()
+                .filter(|(_, value)| value(old) != value(new))
+                .map(|(field, value)| format!("{} {} -> {}", field, value(old), value(new)))
+                .collect();
+            let mut text = format!("updated {}", new.name);
+            if !payments.is_empty() {
+                text = format!("{}: {}", text, payments.join(", "));
+            }
+            if !changes.is_empty() {
+                let separator = if payments.is_empty() { ":" } else { "," };
+                text = format!("{}{} {}", text, separator, changes.join(", "));
+            }
+            Some(text)
+        }
+        _ => None,
+    }
+}
+
+pub fn changes(before: &HashMap<i64, Bill>, after: &Bills) -> Vec<(i64, String)> {
+    let mut ids: Vec<&i64> = before.keys().chain(after.list.keys()).collect();
+    ids.sort();
+    ids.dedup();
+    ids.into_iter()
+        .filter_map(|id| Some((*id, describe(before.get(id), after.list.get(id))?)))
+        .collect()
+}
+
+/// Adds the changes to the log. Like the journal the log is plaintext, so
+/// nothing is logged while the bills are encrypted.
+pub fn append(
+    vfs: &dyn Vfs,
+    file_name: &Path,
+    time: NaiveDateTime,
+    changes: &[(i64, String)],
+) -> io::Result<()> {
+    if changes.is_empty() || config::encrypted() {
+        return Ok(());
+    }
+    let lines: String = changes
+        .iter()
+        .map(|(id, text)| format!("{} #{} {}\n", time.format(TIME_FORMAT), id, text))
+        .collect();
+    vfs.append(file_name, lines.as_bytes())
+}
+
+fn parse_entry(line: &str) -> Option<Entry> {
+    let (time, rest) = line.split_at_checked(TIME_WIDTH)?;
+    let (id, text) = rest.trim_start().strip_prefix('#')?.split_once(' ')?;
+    Some(Entry {
+        time: NaiveDateTime::parse_from_str(time, TIME_FORMAT).ok()?,
+        id: id.parse().ok()?,
+        text: text.to_string(),
+    })
+}
+
+pub fn load(vfs: &dyn Vfs, file_name: &Path) -> io::Result<Vec<Entry>> {
+    match vfs.read_to_string(file_name) {
+        Ok(log) => Ok(log.lines().filter_map(parse_entry).collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::bill;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn describes_changes() {
+        let mut bills = Bills::new();
+        bills.add_bill(bill(1, "Rent", "500"));
+        bills.add_bill(bill(2, "Gym", "30"));
+        let before = bills.list.clone();
+        bills.record_payment(
+            1,
+            "200".parse().unwrap(),
+            NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+        );
+        bills.rename_bill(1, "Flat");
+        bills.remove_bill(2);
+        bills.add_bill(bill(3, "Water", "30"));
+
+        let changes = changes(&before, &bills);
+        assert_eq!(
+            changes,
+            [
+                (
+                    1,
+                    "updated Flat: payment of €200.00 on 2024-03-01, name Rent -> Flat".to_string()
+                ),
+                (2, "removed Gym €30.00".to_string()),
+                (3, "added Water €30.00".to_string()),
+            ]
+        );
+    }
+}
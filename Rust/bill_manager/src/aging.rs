@@ -0,0 +1,39 @@
+use crate::Bill;
+use chrono::NaiveDate;
+use std::fmt;
+
+const OVERDUE_LIMIT_DAYS: i64 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Bucket {
+    DueSoon,
+    Overdue,
+    LongOverdue,
+}
+impl Bucket {
+    pub const ALL: [Bucket; 3] = [Bucket::DueSoon, Bucket::Overdue, Bucket::LongOverdue];
+
+    pub fn of(bill: &Bill, today: NaiveDate) -> Bucket {
+        match bill.due {
+            Some(due) if bill.is_overdue(today) => {
+                if (today - due).num_days() > OVERDUE_LIMIT_DAYS {
+                    Bucket::LongOverdue
+                } else {
+                    Bucket::Overdue
+                }
+            }
+            _ => Bucket::DueSoon,
+        }
+    }
+}
+
+impl fmt::Display for Bucket {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = match self {
+            Bucket::DueSoon => i18n::t!("bills-aging-due-soon"),
+            Bucket::Overdue => i18n::t!("bills-aging-overdue", days = OVERDUE_LIMIT_DAYS),
+            Bucket::LongOverdue => i18n::t!("bills-aging-long-overdue", days = OVERDUE_LIMIT_DAYS),
+        };
+        f.write_str(&text)
+    }
+}
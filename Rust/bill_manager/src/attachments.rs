@@ -0,0 +1,54 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+pub fn is_valid_path(path: &Path) -> bool {
+    path.to_str()
+        .is_some_and(|path| !path.is_empty() && !path.contains(';'))
+}
+
+pub fn copy_into(dir: &Path, bill_id: i64, source: &Path) -> io::Result<PathBuf> {
+    let file_name = source
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "not a file"))?;
+    fs::create_dir_all(dir)?;
+
+    let mut target = dir.join(format!("{}-{}", bill_id, file_name));
+    let mut num = 1;
+    while target.exists() {
+        num += 1;
+        target = dir.join(format!("{}-{}-{}", bill_id, num, file_name));
+    }
+    fs::copy(source, &target)?;
+    Ok(target)
+}
+
+fn opener() -> Command {
+    if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else {
+        Command::new("xdg-open")
+    }
+}
+
+pub fn open(path: &Path) -> io::Result<()> {
+    if !path.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} does not exist", path.display()),
+        ));
+    }
+    opener()
+        .arg(path)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    Ok(())
+}
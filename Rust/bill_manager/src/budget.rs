@@ -0,0 +1,107 @@
+use crate::{currency::Rates, report, Bill};
+use bill_core::Money;
+use chrono::NaiveDate;
+use errors::Diagnostic;
+use flatfile::{Format, Writer};
+use std::{collections::BTreeMap, io, path::Path};
+use vfs::Vfs;
+
+pub struct BudgetStatus {
+    pub category: String,
+    pub limit: Money,
+    pub spent: Money,
+}
+impl BudgetStatus {
+    pub fn percent_used(&self) -> f64 {
+        if self.limit == Money::ZERO {
+            return 0.0;
+        }
+        self.spent.ratio(self.limit) * 100.0
+    }
+    pub fn is_over(&self) -> bool {
+        self.spent > self.limit
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Budgets {
+    list: BTreeMap<String, Money>,
+}
+impl Budgets {
+    pub fn set(&mut self, category: &str, limit: Money) {
+        self.list.insert(category.to_string(), limit);
+    }
+    pub fn remove(&mut self, category: &str) -> bool {
+        self.list.remove(category).is_some()
+    }
+    pub fn status<'a>(
+        &self,
+        bills: impl Iterator<Item = &'a Bill> + Clone,
+        month: NaiveDate,
+        rates: &Rates,
+        base: &str,
+    ) -> Vec<BudgetStatus> {
+        self.list
+            .iter()
+            .map(|(category, limit)| BudgetStatus {
+                category: category.clone(),
+                limit: *limit,
+                spent: spending(bills.clone(), category, month, rates, base),
+            })
+            .collect()
+    }
+}
+
+fn spending<'a>(
+    bills: impl Iterator<Item = &'a Bill>,
+    category: &str,
+    month: NaiveDate,
+    rates: &Rates,
+    base: &str,
+) -> Money {
+    bills
+        .filter(|bill| bill.category.as_deref() == Some(category))
+        .filter(|bill| bill.month(month) == report::month_of(month))
+        .filter_map(|bill| rates.convert(bill.amount, &bill.currency, base))
+        .sum()
+}
+
+fn parse_budget(fields: &[String]) -> Option<(String, Money)> {
+    // Older files wrote categories containing commas without quotes.
+    let (limit, category) = fields.split_last()?;
+    let category = category.join(",");
+    match limit.parse() {
+        Ok(limit) if !category.is_empty() => Some((category, limit)),
+        _ => None,
+    }
+}
+
+pub fn load_budgets(vfs: &dyn Vfs, input_file: &Path) -> io::Result<Budgets> {
+    let buffer = match vfs.read_to_string(input_file) {
+        Ok(buffer) => buffer,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Budgets::default()),
+        Err(e) => return Err(e),
+    };
+
+    let mut budgets = Budgets::default();
+    for record in flatfile::parse(&buffer, Format::default()).records {
+        let result = record.map_err(Diagnostic::from).and_then(|record| {
+            parse_budget(&record.fields)
+                .ok_or_else(|| Diagnostic::new("invalid budget", record.line))
+        });
+        match result {
+            Ok((category, limit)) => budgets.set(&category, limit),
+            Err(e) => tracing::warn!("{}", e.render(input_file.display(), &buffer)),
+        }
+    }
+    Ok(budgets)
+}
+
+pub fn save_budgets(vfs: &dyn Vfs, file_name: &Path, budgets: &Budgets) -> io::Result<()> {
+    let rows = budgets
+        .list
+        .iter()
+        .map(|(category, limit)| [category.clone(), limit.to_string()]);
+    let text = Writer::default().table(&["category", "limit"], rows);
+    vfs.write(file_name, text.as_bytes())
+}
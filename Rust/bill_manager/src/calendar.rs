@@ -0,0 +1,45 @@
+use crate::report::{self, Month};
+use chrono::{Datelike, NaiveDate};
+
+const WEEKDAYS: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+const CELL_WIDTH: usize = 4;
+
+pub fn first_day((year, month): Month) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, month, 1)
+}
+
+pub fn title(month: Month) -> String {
+    match first_day(month) {
+        Some(first) => first.format("%B %Y").to_string(),
+        None => format!("{}-{:02}", month.0, month.1),
+    }
+}
+
+pub fn grid(month: Month, marked: &[u32]) -> Vec<String> {
+    let (first, next) = match (first_day(month), first_day(report::next_month(month))) {
+        (Some(first), Some(next)) => (first, next),
+        _ => return vec![],
+    };
+    let days = (next - first).num_days() as u32;
+    let offset = first.weekday().num_days_from_monday() as usize;
+
+    let mut lines = vec![WEEKDAYS
+        .iter()
+        .map(|day| format!("{:<CELL_WIDTH$}", day))
+        .collect::<String>()];
+    let mut line = " ".repeat(offset * CELL_WIDTH);
+    for day in 1..=days {
+        let marker = if marked.contains(&day) { "*" } else { "" };
+        line.push_str(&format!("{:>2}{:<2}", day, marker));
+        if (offset + day as usize).is_multiple_of(7) {
+            lines.push(std::mem::take(&mut line));
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+        .into_iter()
+        .map(|line| line.trim_end().to_string())
+        .collect()
+}
@@ -0,0 +1,64 @@
+const MAX_SUGGESTIONS: usize = 3;
+
+pub fn normalize(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+pub fn by_normalized<'a>(input: &str, names: &[&'a str]) -> Vec<&'a str> {
+    let input = normalize(input);
+    let mut matches: Vec<&str> = names
+        .iter()
+        .filter(|name| normalize(name) == input)
+        .copied()
+        .collect();
+    matches.sort();
+    matches
+}
+
+pub fn by_prefix<'a>(input: &str, names: &[&'a str]) -> Vec<&'a str> {
+    let input = input.to_lowercase();
+    let mut matches: Vec<&str> = names
+        .iter()
+        .filter(|name| name.to_lowercase().starts_with(&input))
+        .copied()
+        .collect();
+    matches.sort();
+    matches
+}
+
+pub fn closest<'a>(input: &str, names: &[&'a str]) -> Vec<&'a str> {
+    let input = input.to_lowercase();
+    let max_distance = (input.chars().count() / 3).max(2);
+    let mut scored: Vec<(usize, &str)> = names
+        .iter()
+        .map(|name| (distance(&input, &name.to_lowercase()), *name))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+    scored.sort();
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, name)| name)
+        .collect()
+}
+
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let cost = usize::from(a_char != *b_char);
+            current.push(
+                (previous[j] + cost)
+                    .min(previous[j + 1] + 1)
+                    .min(current[j] + 1),
+            );
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
@@ -0,0 +1,147 @@
+use crate::{locale::Locale, Filter, Grouping, SortKey, DATE_FORMAT};
+use bill_core::Rounding;
+use chrono::{
+    format::{Item, StrftimeItems},
+    NaiveDate,
+};
+use render::Theme;
+use serde::{Deserialize, Serialize};
+use std::{path::PathBuf, sync::RwLock};
+use storage::Backend;
+
+static DISPLAY_FORMAT: RwLock<String> = RwLock::new(String::new());
+static STORAGE: RwLock<Backend> = RwLock::new(Backend::Csv);
+#[cfg(feature = "encryption")]
+static PASSPHRASE: RwLock<Option<encryption::Passphrase>> = RwLock::new(None);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewPrefs {
+    pub filter: Filter,
+    pub sort: SortKey,
+    pub descending: bool,
+    pub grouping: Grouping,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub currency: String,
+    pub default_category: Option<String>,
+    pub data_file: PathBuf,
+    pub date_format: Option<String>,
+    pub reminder_days: u32,
+    pub rounding: Rounding,
+    pub locale: Option<Locale>,
+    pub tax_categories: Vec<String>,
+    pub storage: Backend,
+    pub view: ViewPrefs,
+    pub theme: Theme,
+    /// Keep the bills and archive files encrypted with a passphrase.
+    pub encrypt: bool,
+}
+impl Config {
+    pub fn locale(&self) -> Locale {
+        self.locale.unwrap_or_else(Locale::from_env)
+    }
+    pub fn date_format(&self) -> &str {
+        match &self.date_format {
+            Some(format) => format,
+            None => self.locale().date_format(),
+        }
+    }
+}
+impl settings::Settings for Config {
+    const APP: &'static str = "bill_manager";
+}
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            currency: "EUR".to_string(),
+            default_category: None,
+            data_file: PathBuf::from("p1_data.csv"),
+            date_format: None,
+            reminder_days: 7,
+            rounding: Rounding::default(),
+            locale: None,
+            tax_categories: vec![],
+            storage: Backend::Csv,
+            view: ViewPrefs::default(),
+            theme: Theme::Default,
+            encrypt: false,
+        }
+    }
+}
+
+pub fn is_valid_date_format(format: &str) -> bool {
+    if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        return false;
+    }
+    NaiveDate::parse_from_str(&sample_date().format(format).to_string(), format)
+        == Ok(sample_date())
+}
+
+fn sample_date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, 3, 1).unwrap_or_default()
+}
+
+pub fn date_example() -> String {
+    format_date(sample_date())
+}
+
+pub fn set_date_format(format: &str) {
+    if let Ok(mut current) = DISPLAY_FORMAT.write() {
+        *current = format.to_string();
+    }
+}
+
+pub fn date_format() -> String {
+    match DISPLAY_FORMAT.read() {
+        Ok(format) if !format.is_empty() => format.clone(),
+        _ => DATE_FORMAT.to_string(),
+    }
+}
+
+pub fn format_date(date: NaiveDate) -> String {
+    date.format(&date_format()).to_string()
+}
+
+pub fn parse_date(input: &str) -> Option<NaiveDate> {
+    validate::date(input, &date_format()).ok()
+}
+
+pub fn set_storage(backend: Backend) {
+    if let Ok(mut current) = STORAGE.write() {
+        *current = backend;
+    }
+}
+
+pub fn storage() -> Backend {
+    STORAGE.read().map_or(Backend::Csv, |backend| *backend)
+}
+
+#[cfg(feature = "encryption")]
+pub fn set_passphrase(passphrase: Option<encryption::Passphrase>) {
+    if let Ok(mut current) = PASSPHRASE.write() {
+        *current = passphrase;
+    }
+}
+
+/// The passphrase bills files are encrypted with, if the `encrypt` setting is on.
+#[cfg(feature = "encryption")]
+pub fn passphrase() -> Option<encryption::Passphrase> {
+    PASSPHRASE
+        .read()
+        .ok()
+        .and_then(|passphrase| passphrase.clone())
+}
+
+#[cfg(feature = "encryption")]
+pub fn encrypted() -> bool {
+    passphrase().is_some()
+}
+
+#[cfg(not(feature = "encryption"))]
+pub fn encrypted() -> bool {
+    false
+}
@@ -0,0 +1,177 @@
+use crate::locale;
+use bill_core::{Money, Rounding};
+use errors::Diagnostic;
+use flatfile::{Format, Writer};
+use std::{collections::BTreeMap, io, path::Path, sync::RwLock};
+use vfs::Vfs;
+
+const DEFAULT_DECIMALS: u32 = 2;
+
+static ROUNDING: RwLock<Rounding> = RwLock::new(Rounding::HalfUp);
+
+struct CurrencyInfo {
+    code: &'static str,
+    symbol: &'static str,
+    decimals: u32,
+}
+
+const CURRENCIES: &[CurrencyInfo] = &[
+    CurrencyInfo {
+        code: "EUR",
+        symbol: "€",
+        decimals: 2,
+    },
+    CurrencyInfo {
+        code: "USD",
+        symbol: "$",
+        decimals: 2,
+    },
+    CurrencyInfo {
+        code: "GBP",
+        symbol: "£",
+        decimals: 2,
+    },
+    CurrencyInfo {
+        code: "CHF",
+        symbol: "CHF ",
+        decimals: 2,
+    },
+    CurrencyInfo {
+        code: "JPY",
+        symbol: "¥",
+        decimals: 0,
+    },
+];
+
+pub fn decimals(code: &str) -> u32 {
+    CURRENCIES
+        .iter()
+        .find(|info| info.code == code)
+        .map_or(DEFAULT_DECIMALS, |info| info.decimals)
+}
+
+pub fn set_rounding(rounding: Rounding) {
+    if let Ok(mut current) = ROUNDING.write() {
+        *current = rounding;
+    }
+}
+
+fn rounding() -> Rounding {
+    ROUNDING
+        .read()
+        .map_or(Rounding::HalfUp, |rounding| *rounding)
+}
+
+pub fn round(amount: Money, code: &str) -> Money {
+    amount.round(decimals(code), rounding())
+}
+
+pub fn format_amount(amount: Money, code: &str) -> String {
+    let locale = locale::current();
+    let number = locale.format_number(&round(amount, code).format(decimals(code)));
+    match CURRENCIES.iter().find(|info| info.code == code) {
+        Some(info) if locale.symbol_first() => format!("{}{}", info.symbol, number),
+        Some(info) => format!("{} {}", number, info.symbol.trim()),
+        None => format!("{} {}", number, code),
+    }
+}
+
+pub fn format_totals(totals: &BTreeMap<String, Money>) -> String {
+    if totals.is_empty() {
+        return "0".to_string();
+    }
+    totals
+        .iter()
+        .map(|(code, amount)| format_amount(*amount, code))
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+#[derive(Debug, Default)]
+pub struct Rates {
+    list: BTreeMap<(String, String), f64>,
+}
+impl Rates {
+    pub fn set(&mut self, from: &str, to: &str, rate: f64) {
+        self.list.remove(&(to.to_string(), from.to_string()));
+        self.list.insert((from.to_string(), to.to_string()), rate);
+    }
+    pub fn remove(&mut self, from: &str, to: &str) -> bool {
+        let removed = self.list.remove(&(from.to_string(), to.to_string()));
+        let inverse = self.list.remove(&(to.to_string(), from.to_string()));
+        removed.is_some() || inverse.is_some()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str, f64)> {
+        self.list
+            .iter()
+            .map(|((from, to), rate)| (from.as_str(), to.as_str(), *rate))
+    }
+    pub fn convert(&self, amount: Money, from: &str, to: &str) -> Option<Money> {
+        if from == to {
+            return Some(amount);
+        }
+        if let Some(rate) = self.list.get(&(from.to_string(), to.to_string())) {
+            return Some(round(amount.scale(*rate), to));
+        }
+        self.list
+            .get(&(to.to_string(), from.to_string()))
+            .map(|rate| round(amount.scale(1.0 / rate), to))
+    }
+    pub fn convert_totals<'a>(
+        &self,
+        totals: &'a BTreeMap<String, Money>,
+        to: &str,
+    ) -> (Money, Vec<&'a str>) {
+        let mut sum = Money::ZERO;
+        let mut missing = vec![];
+        for (code, amount) in totals {
+            match self.convert(*amount, code, to) {
+                Some(converted) => sum += converted,
+                None => missing.push(code.as_str()),
+            }
+        }
+        (sum, missing)
+    }
+}
+
+fn parse_rate(fields: &[String]) -> Option<(String, String, f64)> {
+    match fields {
+        [from, to, rate] => {
+            let rate: f64 = rate.parse().ok()?;
+            Some((
+                validate::currency(from).ok()?,
+                validate::currency(to).ok()?,
+                rate,
+            ))
+        }
+        _ => None,
+    }
+}
+
+pub fn load_rates(vfs: &dyn Vfs, input_file: &Path) -> io::Result<Rates> {
+    let buffer = match vfs.read_to_string(input_file) {
+        Ok(buffer) => buffer,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Rates::default()),
+        Err(e) => return Err(e),
+    };
+
+    let mut rates = Rates::default();
+    for record in flatfile::parse(&buffer, Format::default()).records {
+        let result = record.map_err(Diagnostic::from).and_then(|record| {
+            parse_rate(&record.fields).ok_or_else(|| Diagnostic::new("invalid rate", record.line))
+        });
+        match result {
+            Ok((from, to, rate)) => rates.set(&from, &to, rate),
+            Err(e) => tracing::warn!("{}", e.render(input_file.display(), &buffer)),
+        }
+    }
+    Ok(rates)
+}
+
+pub fn save_rates(vfs: &dyn Vfs, file_name: &Path, rates: &Rates) -> io::Result<()> {
+    let rows = rates
+        .iter()
+        .map(|(from, to, rate)| [from.to_string(), to.to_string(), rate.to_string()]);
+    let text = Writer::default().table(&["from", "to", "rate"], rows);
+    vfs.write(file_name, text.as_bytes())
+}
@@ -0,0 +1,77 @@
+use crate::locale::Locale;
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+
+const WEEKDAYS: [(&str, Weekday); 7] = [
+    ("monday", Weekday::Mon),
+    ("tuesday", Weekday::Tue),
+    ("wednesday", Weekday::Wed),
+    ("thursday", Weekday::Thu),
+    ("friday", Weekday::Fri),
+    ("saturday", Weekday::Sat),
+    ("sunday", Weekday::Sun),
+];
+
+fn weekday(name: &str) -> Option<Weekday> {
+    WEEKDAYS
+        .iter()
+        .find(|(full, _)| name.len() >= 3 && full.starts_with(name))
+        .map(|(_, weekday)| *weekday)
+}
+
+fn after(today: NaiveDate, count: u32, unit: &str) -> Option<NaiveDate> {
+    match unit.strip_suffix('s').unwrap_or(unit) {
+        "day" => today.checked_add_days(Days::new(count.into())),
+        "week" => today.checked_add_days(Days::new(u64::from(count) * 7)),
+        "month" => today.checked_add_months(Months::new(count)),
+        "year" => today.checked_add_months(Months::new(count.checked_mul(12)?)),
+        _ => None,
+    }
+}
+
+/// Day and month without a year mean their next occurrence.
+fn day_month(input: &str, separator: char, day_first: bool, today: NaiveDate) -> Option<NaiveDate> {
+    let parts: Vec<&str> = input.trim_end_matches(separator).split(separator).collect();
+    let (first, second, year) = match parts.as_slice() {
+        [first, second] => (first, second, None),
+        [first, second, year] => (first, second, Some(year.parse::<i32>().ok()?)),
+        _ => return None,
+    };
+    let (first, second): (u32, u32) = (first.parse().ok()?, second.parse().ok()?);
+    let (day, month) = if day_first {
+        (first, second)
+    } else {
+        (second, first)
+    };
+    match year {
+        Some(year) if year < 100 => NaiveDate::from_ymd_opt(2000 + year, month, day),
+        Some(year) => NaiveDate::from_ymd_opt(year, month, day),
+        None => NaiveDate::from_ymd_opt(today.year(), month, day)
+            .filter(|date| *date >= today)
+            .or_else(|| NaiveDate::from_ymd_opt(today.year() + 1, month, day)),
+    }
+}
+
+/// Parses relative dates like `tomorrow`, `next friday` or `in 2 weeks` and
+/// dates without a year. Dots separate day and month like `15.03.`, slashes
+/// follow the locale: `3/15` in English, `15/3` in German.
+pub fn parse(input: &str, today: NaiveDate, locale: Locale) -> Option<NaiveDate> {
+    let input = input.trim().to_lowercase();
+    let words: Vec<&str> = input.split_whitespace().collect();
+    match words.as_slice() {
+        ["today"] => Some(today),
+        ["tomorrow"] => today.succ_opt(),
+        ["yesterday"] => today.pred_opt(),
+        ["next", unit @ ("week" | "month" | "year")] => after(today, 1, unit),
+        ["in", "a" | "an" | "one", unit] => after(today, 1, unit),
+        ["in", count, unit] => after(today, count.parse().ok()?, unit),
+        ["next", name] | [name] if weekday(name).is_some() => {
+            let target = weekday(name)?;
+            let ahead =
+                (7 + target.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+            today.checked_add_days(Days::new(if ahead == 0 { 7 } else { ahead.into() }))
+        }
+        [date] if date.contains('.') => day_month(date, '.', true, today),
+        [date] if date.contains('/') => day_month(date, '/', locale == Locale::De, today),
+        _ => None,
+    }
+}
@@ -0,0 +1,79 @@
+use bill_core::Money;
+use std::{iter::Peekable, str::Chars};
+
+// expr = term (('+' | '-') term)*, term = factor (('*' | '/') factor)*,
+// factor = '-' factor | '(' expr ')' | number
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+impl Parser<'_> {
+    fn peek(&mut self) -> Option<char> {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+        self.chars.peek().copied()
+    }
+    fn expr(&mut self) -> Result<Money, String> {
+        let mut value = self.term()?;
+        while let Some(op @ ('+' | '-')) = self.peek() {
+            self.chars.next();
+            let rhs = self.term()?;
+            value = if op == '+' { value + rhs } else { value - rhs };
+        }
+        Ok(value)
+    }
+    fn term(&mut self) -> Result<Money, String> {
+        let mut value = self.factor()?;
+        while let Some(op @ ('*' | '/')) = self.peek() {
+            self.chars.next();
+            let rhs = self.factor()?;
+            value = if op == '*' {
+                value.scale(rhs.as_f64())
+            } else if rhs == Money::ZERO {
+                return Err("division by zero".to_string());
+            } else {
+                value.scale(1.0 / rhs.as_f64())
+            };
+        }
+        Ok(value)
+    }
+    fn factor(&mut self) -> Result<Money, String> {
+        match self.peek() {
+            Some('-') => {
+                self.chars.next();
+                Ok(Money::ZERO - self.factor()?)
+            }
+            Some('(') => {
+                self.chars.next();
+                let value = self.expr()?;
+                match self.peek() {
+                    Some(')') => {
+                        self.chars.next();
+                        Ok(value)
+                    }
+                    _ => Err("missing )".to_string()),
+                }
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => {
+                let mut number = String::new();
+                while let Some(c) = self.chars.next_if(|c| c.is_ascii_digit() || *c == '.') {
+                    number.push(c);
+                }
+                number.parse().map_err(|e| format!("{}", e))
+            }
+            Some(c) => Err(format!("unexpected {}", c)),
+            None => Err("missing number".to_string()),
+        }
+    }
+}
+
+/// Evaluates sums like `12.50+3.20*2`; multiplying or dividing two amounts
+/// treats the right one as a plain number.
+pub fn evaluate(input: &str) -> Result<Money, String> {
+    let mut parser = Parser {
+        chars: input.chars().peekable(),
+    };
+    let value = parser.expr()?;
+    match parser.peek() {
+        Some(c) => Err(format!("unexpected {}", c)),
+        None => Ok(value),
+    }
+}
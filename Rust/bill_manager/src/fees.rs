@@ -0,0 +1,22 @@
+use crate::Bill;
+use bill_core::Money;
+use chrono::NaiveDate;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LateFees {
+    pub fee: Money,
+    pub annual_rate: f64,
+}
+impl LateFees {
+    pub fn accrued(&self, bill: &Bill, today: NaiveDate) -> Money {
+        let due = match bill.due {
+            Some(due) if bill.is_overdue(today) => due,
+            _ => return Money::ZERO,
+        };
+        let days = (today - due).num_days() as f64;
+        self.fee
+            + bill
+                .remaining()
+                .scale(self.annual_rate / 100.0 * days / 365.0)
+    }
+}
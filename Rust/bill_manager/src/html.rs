@@ -0,0 +1,127 @@
+use crate::{
+    config, currency,
+    report::{Month, MonthReport},
+};
+use bill_core::Money;
+use chrono::NaiveDate;
+use std::{collections::BTreeMap, fmt::Write};
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; margin-bottom: 1.5em; }
+th, td { border: 1px solid #ccc; padding: 0.3em 0.8em; text-align: left; }
+td.amount { text-align: right; }
+.bar { background: #4a7ebb; height: 1em; }
+.chart td { border: none; }
+.chart td.track { width: 20em; }";
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn month_label((year, month): Month) -> String {
+    format!("{}-{:02}", year, month)
+}
+
+fn bar_chart(html: &mut String, rows: &[(String, Money)], base: &str) {
+    let max = rows.iter().map(|row| row.1).max().unwrap_or(Money::ZERO);
+    html.push_str("<table class=\"chart\">\n");
+    for (label, amount) in rows {
+        let percent = if max > Money::ZERO {
+            amount.ratio(max) * 100.0
+        } else {
+            0.0
+        };
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td class=\"track\"><div class=\"bar\" style=\"width: {:.1}%\"></div></td><td class=\"amount\">{}</td></tr>",
+            escape(label),
+            percent,
+            escape(&currency::format_amount(*amount, base))
+        );
+    }
+    html.push_str("</table>\n");
+}
+
+fn amount_row(html: &mut String, label: &str, amount: Money, base: &str) {
+    let _ = writeln!(
+        html,
+        "<tr><td>{}</td><td class=\"amount\">{}</td></tr>",
+        escape(label),
+        escape(&currency::format_amount(amount, base))
+    );
+}
+
+pub fn render_report(
+    reports: &BTreeMap<Month, MonthReport>,
+    base: &str,
+    generated: NaiveDate,
+) -> String {
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Bill report</title>\n<style>\n{}\n</style>\n</head>\n<body>\n",
+        STYLE
+    );
+    html.push_str("<h1>Bill report</h1>\n");
+    let _ = writeln!(
+        html,
+        "<p>Generated on {}, amounts in {}.</p>",
+        escape(&config::format_date(generated)),
+        escape(base)
+    );
+
+    let by_month: Vec<(String, Money)> = reports
+        .iter()
+        .map(|(month, report)| (month_label(*month), report.billed))
+        .collect();
+    let mut by_category: BTreeMap<String, Money> = BTreeMap::new();
+    for report in reports.values() {
+        for (category, amount) in &report.by_category {
+            *by_category.entry(category.clone()).or_default() += *amount;
+        }
+    }
+    let by_category: Vec<(String, Money)> = by_category.into_iter().collect();
+
+    html.push_str("<h2>Spending by month</h2>\n");
+    bar_chart(&mut html, &by_month, base);
+    html.push_str("<h2>Spending by category</h2>\n");
+    bar_chart(&mut html, &by_category, base);
+
+    for (month, report) in reports.iter().rev() {
+        let _ = writeln!(html, "<h2>{}</h2>", month_label(*month));
+        html.push_str("<table>\n");
+        amount_row(&mut html, "Billed", report.billed, base);
+        amount_row(&mut html, "Paid", report.paid, base);
+        html.push_str("</table>\n");
+
+        if !report.by_category.is_empty() {
+            html.push_str("<table>\n<tr><th>Category</th><th>Amount</th></tr>\n");
+            for (category, amount) in &report.by_category {
+                amount_row(&mut html, category, *amount, base);
+            }
+            html.push_str("</table>\n");
+        }
+        if report.unconverted > 0 {
+            let _ = writeln!(
+                html,
+                "<p>{} amounts without an exchange rate to {} left out.</p>",
+                report.unconverted,
+                escape(base)
+            );
+        }
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
@@ -0,0 +1,89 @@
+use crate::{complete, Bill};
+use bill_core::{Money, Period, Recurrence};
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+
+const MIN_OCCURRENCES: usize = 3;
+const TOLERANCE_DAYS: i64 = 3;
+const CANDIDATES: &[(Period, u32)] = &[
+    (Period::Weekly, 1),
+    (Period::Weekly, 2),
+    (Period::Monthly, 1),
+    (Period::Monthly, 2),
+    (Period::Monthly, 3),
+    (Period::Monthly, 6),
+    (Period::Yearly, 1),
+];
+
+type Key = (String, Money, String);
+
+#[derive(Debug)]
+pub struct Suggestion {
+    pub id: i64,
+    pub name: String,
+    pub amount: Money,
+    pub currency: String,
+    pub recurrence: Recurrence,
+    pub occurrences: usize,
+}
+
+fn fits(recurrence: Recurrence, dates: &[NaiveDate]) -> bool {
+    dates.windows(2).all(|pair| {
+        let expected = recurrence.next_due(pair[0]);
+        (pair[1] - expected).num_days().abs() <= TOLERANCE_DAYS
+    })
+}
+
+fn infer(dates: &[NaiveDate]) -> Option<Recurrence> {
+    CANDIDATES
+        .iter()
+        .map(|&(period, interval)| Recurrence { period, interval })
+        .find(|recurrence| fits(*recurrence, dates))
+}
+
+pub fn suggest<'a>(
+    current: impl Iterator<Item = &'a Bill>,
+    archived: impl Iterator<Item = &'a Bill>,
+) -> Vec<Suggestion> {
+    let mut groups: BTreeMap<Key, Vec<(NaiveDate, Option<&Bill>)>> = BTreeMap::new();
+    let bills = current
+        .map(|bill| (bill, true))
+        .chain(archived.map(|bill| (bill, false)));
+    for (bill, is_current) in bills {
+        if let Some(date) = bill.due.or(bill.paid) {
+            let key = (
+                complete::normalize(&bill.name),
+                bill.amount,
+                bill.currency.clone(),
+            );
+            groups
+                .entry(key)
+                .or_default()
+                .push((date, Some(bill).filter(|_| is_current)));
+        }
+    }
+
+    let mut suggestions = vec![];
+    for (_, mut occurrences) in groups {
+        if occurrences.len() < MIN_OCCURRENCES {
+            continue;
+        }
+        occurrences.sort_by_key(|(date, _)| *date);
+        let latest = match occurrences.last() {
+            Some((_, Some(bill))) if bill.recurrence.is_none() => *bill,
+            _ => continue,
+        };
+        let dates: Vec<NaiveDate> = occurrences.iter().map(|(date, _)| *date).collect();
+        if let Some(recurrence) = infer(&dates) {
+            suggestions.push(Suggestion {
+                id: latest.id,
+                name: latest.name.clone(),
+                amount: latest.amount,
+                currency: latest.currency.clone(),
+                recurrence,
+                occurrences: dates.len(),
+            });
+        }
+    }
+    suggestions
+}
@@ -0,0 +1,62 @@
+use crate::{bill_fields, parse_bill, save_bills, Bill, Bills};
+use std::{io, path::Path};
+use undo::{Changes, Command, Journal};
+
+/// Unsaved changes to the bills in `data_file`, so a crash loses none of them.
+pub fn journal(data_file: &Path) -> Journal {
+    Journal::beside(data_file, "journal")
+}
+
+impl undo::Fields for Bill {
+    type Context = String;
+    fn to_fields(&self) -> Vec<String> {
+        bill_fields(self)
+    }
+    fn from_fields(fields: &[String], default_currency: &String) -> Option<Self> {
+        parse_bill(fields, default_currency).ok()
+    }
+}
+
+pub fn append(journal: &Journal, changes: &Changes<i64, Bill>) -> io::Result<()> {
+    journal.append("commit", changes)
+}
+
+pub fn recover(data_file: &Path, bills: &mut Bills, default_currency: &str) -> io::Result<usize> {
+    let journal = journal(data_file);
+    let mut count = 0;
+    for (_, changes) in journal.read::<i64, Bill>(&default_currency.to_string())? {
+        changes.apply(&mut bills.list);
+        count += changes.len();
+    }
+    if count > 0 {
+        save_bills(data_file, bills)?;
+    }
+    journal.clear()?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::bill;
+
+    #[test]
+    fn replays_unsaved_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_file = dir.path().join("bills.csv");
+        let journal = journal(&data_file);
+        let mut bills = Bills::new();
+        bills.add_bill(bill(1, "Rent", "500"));
+        let before = bills.list.clone();
+        bills.add_bill(bill(2, "Water", "30"));
+        bills.remove_bill(1);
+        let changes = Changes::between(&before, &bills.list);
+        append(&journal, &changes).unwrap();
+
+        let mut recovered = Bills::new();
+        recovered.add_bill(bill(1, "Rent", "500"));
+        assert_eq!(recover(&data_file, &mut recovered, "EUR").unwrap(), 2);
+        assert_eq!(recovered.list, bills.list);
+        assert!(!journal.path().exists());
+    }
+}
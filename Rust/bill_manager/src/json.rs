@@ -0,0 +1,71 @@
+use crate::{fees::LateFees, Bill, Bills, DATE_FORMAT};
+use bill_core::Money;
+use chrono::NaiveDate;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+fn date(date: Option<NaiveDate>) -> Value {
+    match date {
+        Some(date) => json!(date.format(DATE_FORMAT).to_string()),
+        None => Value::Null,
+    }
+}
+
+fn totals(totals: &BTreeMap<String, Money>) -> Value {
+    let map: Map<String, Value> = totals
+        .iter()
+        .map(|(code, amount)| (code.clone(), json!(amount.to_string())))
+        .collect();
+    Value::Object(map)
+}
+
+fn bill_json(bill: &Bill, fees: LateFees, today: NaiveDate) -> Value {
+    let payments: Vec<Value> = bill
+        .payments
+        .iter()
+        .map(|payment| {
+            json!({
+                "amount": payment.amount.to_string(),
+                "date": date(Some(payment.date)),
+            })
+        })
+        .collect();
+    json!({
+        "id": bill.id,
+        "name": bill.name,
+        "amount": bill.amount.to_string(),
+        "currency": bill.currency,
+        "remaining": bill.remaining().to_string(),
+        "late": fees.accrued(bill, today).to_string(),
+        "due": date(bill.due),
+        "paid": date(bill.paid),
+        "overdue": bill.is_overdue(today),
+        "recurrence": bill.recurrence.map(|recurrence| recurrence.to_string()),
+        "category": bill.category,
+        "method": bill.method.map(|method| method.to_string()),
+        "priority": bill.priority.to_string(),
+        "payee": bill.payee,
+        "split": bill.split.as_ref().map(|split| split.to_string()),
+        "notes": bill.notes,
+        "payments": payments,
+    })
+}
+
+pub fn bills_json(bills: &Bills, fees: LateFees, today: NaiveDate) -> Value {
+    let list: Vec<Value> = bills
+        .view_by_due()
+        .into_iter()
+        .map(|bill| bill_json(bill, fees, today))
+        .collect();
+    let summary = bills.summary(today, fees);
+    json!({
+        "bills": list,
+        "summary": {
+            "count": summary.count,
+            "owed": totals(&summary.owed),
+            "late": totals(&summary.late),
+            "paid_this_month": totals(&summary.paid_this_month),
+            "largest": summary.largest.map(|bill| bill.id),
+        },
+    })
+}
@@ -0,0 +1,4635 @@
+use aging::Bucket;
+use bill_core::{Method, Money, Priority, Recurrence, Split};
+use budget::Budgets;
+use chrono::{Datelike, Days, Local, NaiveDate};
+use clap::{builder::Styles, ArgGroup, Parser, ValueHint};
+use config::{Config, ViewPrefs};
+use contact_manager::Records;
+use currency::Rates;
+use errors::{Diagnostic, FieldError};
+use fees::LateFees;
+use locale::Locale;
+use plugin::Plugin;
+use profile::Profile;
+use prompt::{confirm, Back, Terminal, UserInterface};
+use render::Style;
+use search::Query;
+use serde::{Deserialize, Serialize};
+use settings::{Layers, Settings};
+use split::Transfer;
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    ffi::OsString,
+    fmt, fs,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    process,
+};
+use storage::{Backend, Csv, Row, Rows, Storage};
+use thiserror::Error;
+use undo::{Changes, Command, History};
+use vfs::{Disk, Vfs};
+
+mod activity;
+mod aging;
+mod attachments;
+mod budget;
+mod calendar;
+mod complete;
+mod config;
+mod currency;
+mod dates;
+mod expr;
+mod fees;
+mod html;
+mod infer;
+mod journal;
+mod json;
+mod locale;
+mod lock;
+mod migrations;
+mod profile;
+mod reconcile;
+mod report;
+mod rotation;
+mod script;
+mod search;
+mod split;
+mod tax;
+#[cfg(feature = "tui")]
+mod tui;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Payment {
+    amount: Money,
+    date: NaiveDate,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Bill {
+    id: i64,
+    name: String,
+    amount: Money,
+    due: Option<NaiveDate>,
+    recurrence: Option<Recurrence>,
+    paid: Option<NaiveDate>,
+    category: Option<String>,
+    currency: String,
+    payments: Vec<Payment>,
+    notes: Option<String>,
+    payee: Option<i64>,
+    split: Option<Split>,
+    method: Option<Method>,
+    attachments: Vec<PathBuf>,
+    original_due: Option<NaiveDate>,
+    postponed: u32,
+    priority: Priority,
+}
+impl Bill {
+    fn is_overdue(&self, today: NaiveDate) -> bool {
+        self.paid.is_none() && self.due.is_some_and(|due| due < today)
+    }
+    fn is_due_within(&self, today: NaiveDate, days: u32) -> bool {
+        self.paid.is_none()
+            && self
+                .due
+                .is_some_and(|due| due >= today && (due - today).num_days() <= i64::from(days))
+    }
+    fn month(&self, today: NaiveDate) -> report::Month {
+        report::month_of(self.due.or(self.paid).unwrap_or(today))
+    }
+    fn paid_amount(&self) -> Money {
+        self.payments.iter().map(|payment| payment.amount).sum()
+    }
+    fn remaining(&self) -> Money {
+        if self.paid.is_some() {
+            return Money::ZERO;
+        }
+        (self.amount - self.paid_amount()).max(Money::ZERO)
+    }
+}
+impl fmt::Display for Bill {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let overdue = self.is_overdue(Local::now().date_naive());
+        let style = if overdue {
+            Some(Style::Error)
+        } else if self.paid.is_some() {
+            Some(Style::Success)
+        } else {
+            None
+        };
+        if let Some(style) = style {
+            write!(f, "{}", style.start())?;
+        }
+
+        write!(
+            f,
+            "#{} {}: {}",
+            self.id,
+            self.name,
+            currency::format_amount(self.amount, &self.currency)
+        )?;
+        if let Some(category) = &self.category {
+            write!(f, " <{}>", category)?;
+        }
+        if let Some(due) = self.due {
+            write!(
+                f,
+                " {}",
+                i18n::t!("bills-line-due", date = config::format_date(due))
+            )?;
+        }
+        if let Some(recurrence) = self.recurrence {
+            write!(f, " [{}]", recurrence)?;
+        }
+        if self.postponed > 0 {
+            write!(
+                f,
+                " {}",
+                i18n::t!("bills-line-postponed", count = self.postponed)
+            )?;
+        }
+        if self.priority != Priority::Normal {
+            write!(
+                f,
+                " {}",
+                i18n::t!("bills-line-priority", priority = self.priority)
+            )?;
+        }
+        if let Some(paid) = self.paid {
+            write!(
+                f,
+                " {}",
+                i18n::t!("bills-line-paid", date = config::format_date(paid))
+            )?;
+        } else if !self.payments.is_empty() {
+            write!(
+                f,
+                " {}",
+                i18n::t!(
+                    "bills-line-remaining",
+                    amount = currency::format_amount(self.remaining(), &self.currency)
+                )
+            )?;
+        }
+        if overdue {
+            write!(f, " {}", i18n::t!("bills-line-overdue"))?;
+        }
+        if let Some(style) = style {
+            write!(f, "{}", style.end())?;
+        }
+        Ok(())
+    }
+}
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const UNCATEGORIZED: &str = "Uncategorized";
+const BILL_COLUMNS: &[&str] = &[
+    "name",
+    "amount",
+    "due",
+    "recurrence",
+    "paid",
+    "category",
+    "currency",
+    "id",
+    "payments",
+    "notes",
+    "payee",
+    "split",
+    "method",
+    "attachments",
+    "original_due",
+    "postponed",
+    "priority",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SortKey {
+    Name,
+    Amount,
+    #[default]
+    Due,
+    Category,
+}
+impl SortKey {
+    fn compare(&self, a: &Bill, b: &Bill) -> Ordering {
+        let ordering = match self {
+            SortKey::Name => Ordering::Equal,
+            SortKey::Amount => a.amount.cmp(&b.amount),
+            SortKey::Due => match (a.due, b.due) {
+                (Some(a_due), Some(b_due)) => a_due.cmp(&b_due),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+            SortKey::Category => a.category.cmp(&b.category),
+        };
+        ordering.then(a.name.cmp(&b.name))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Sort {
+    key: SortKey,
+    descending: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Filter {
+    #[default]
+    All,
+    Unpaid,
+    Paid,
+}
+impl Filter {
+    fn matches(&self, bill: &Bill) -> bool {
+        match self {
+            Filter::All => true,
+            Filter::Unpaid => bill.paid.is_none(),
+            Filter::Paid => bill.paid.is_some(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Grouping {
+    #[default]
+    Flat,
+    Category,
+    Aging,
+}
+
+fn totals_by_currency<'a>(
+    amounts: impl Iterator<Item = (&'a str, Money)>,
+) -> BTreeMap<String, Money> {
+    let mut totals = BTreeMap::new();
+    for (currency, amount) in amounts {
+        *totals.entry(currency.to_string()).or_default() += amount;
+    }
+    totals
+}
+
+struct Summary<'a> {
+    owed: BTreeMap<String, Money>,
+    late: BTreeMap<String, Money>,
+    paid_this_month: BTreeMap<String, Money>,
+    count: usize,
+    largest: Option<&'a Bill>,
+}
+
+#[derive(Debug)]
+struct Bills {
+    list: HashMap<i64, Bill>,
+    /// The last id handed out, so ids of removed bills are not reused.
+    counter: ids::Counter,
+}
+impl Bills {
+    fn new() -> Self {
+        Self {
+            list: HashMap::new(),
+            counter: ids::Counter::default(),
+        }
+    }
+    fn add_bill(&mut self, bill: Bill) {
+        self.list.insert(bill.id, bill);
+    }
+    fn view_bill(&self) -> Vec<&Bill> {
+        self.list.values().collect()
+    }
+    fn next_id(&mut self) -> i64 {
+        let largest = self.list.keys().copied().max().unwrap_or(0);
+        self.counter.last = self.counter.last.max(largest);
+        let list = &self.list;
+        self.counter.next(|id| list.contains_key(&id))
+    }
+    fn view_sorted(&self, sort: Sort) -> Vec<&Bill> {
+        let mut bills = self.view_bill();
+        bills.sort_by(|a, b| {
+            let ordering = sort.key.compare(a, b);
+            if sort.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        bills
+    }
+    fn view_by_due(&self) -> Vec<&Bill> {
+        self.view_sorted(Sort {
+            key: SortKey::Due,
+            descending: false,
+        })
+    }
+    fn categories(&self) -> Vec<&str> {
+        let mut categories: Vec<&str> = self
+            .list
+            .values()
+            .filter_map(|bill| bill.category.as_deref())
+            .collect();
+        categories.sort();
+        categories.dedup();
+        categories
+    }
+    fn view_by_category(&self) -> BTreeMap<&str, Vec<&Bill>> {
+        let mut groups: BTreeMap<&str, Vec<&Bill>> = BTreeMap::new();
+        for bill in self.view_by_due() {
+            let category = bill.category.as_deref().unwrap_or(UNCATEGORIZED);
+            groups.entry(category).or_default().push(bill);
+        }
+        groups
+    }
+    fn summary(&self, today: NaiveDate, fees: LateFees) -> Summary<'_> {
+        let bills = self.view_bill();
+        let this_month =
+            |date: NaiveDate| date.year() == today.year() && date.month() == today.month();
+
+        Summary {
+            owed: totals_by_currency(
+                bills
+                    .iter()
+                    .filter(|bill| bill.paid.is_none())
+                    .map(|bill| (bill.currency.as_str(), bill.remaining())),
+            ),
+            late: totals_by_currency(
+                bills
+                    .iter()
+                    .map(|bill| (bill.currency.as_str(), fees.accrued(bill, today)))
+                    .filter(|(_, accrued)| *accrued > Money::ZERO),
+            ),
+            paid_this_month: totals_by_currency(bills.iter().flat_map(|bill| {
+                bill.payments
+                    .iter()
+                    .filter(|payment| this_month(payment.date))
+                    .map(|payment| (bill.currency.as_str(), payment.amount))
+            })),
+            count: bills.len(),
+            largest: bills.into_iter().max_by_key(|bill| bill.amount),
+        }
+    }
+    fn remove_bill(&mut self, id: i64) -> bool {
+        self.list.remove(&id).is_some()
+    }
+    fn update_bill(
+        &mut self,
+        id: i64,
+        amount: Money,
+        due: Option<NaiveDate>,
+        recurrence: Option<Recurrence>,
+        notes: Option<String>,
+        payee: Option<i64>,
+    ) -> bool {
+        match self.list.get_mut(&id) {
+            Some(bill) => {
+                bill.amount = amount;
+                if bill.due != due {
+                    // A date set by hand is no longer a postponement.
+                    bill.original_due = None;
+                    bill.postponed = 0;
+                }
+                bill.due = due;
+                bill.recurrence = recurrence;
+                bill.notes = notes;
+                bill.payee = payee;
+                true
+            }
+            None => false,
+        }
+    }
+    fn record_payment(&mut self, id: i64, amount: Money, date: NaiveDate) -> bool {
+        let bill = match self.list.get_mut(&id) {
+            Some(bill) => bill,
+            None => return false,
+        };
+        bill.payments.push(Payment { amount, date });
+
+        if bill.paid_amount() >= bill.amount {
+            let last_payment = bill.payments.iter().map(|payment| payment.date).max();
+            self.settle_bill(id, last_payment.unwrap_or(date));
+        }
+        true
+    }
+    fn pay_bill(&mut self, id: i64, today: NaiveDate) -> bool {
+        match self.list.get(&id) {
+            Some(bill) => self.record_payment(id, bill.remaining(), today),
+            None => false,
+        }
+    }
+    fn settle_bill(&mut self, id: i64, date: NaiveDate) {
+        let mut bill = match self.list.remove(&id) {
+            Some(bill) => bill,
+            None => return,
+        };
+        bill.paid = Some(date);
+        self.roll_over(bill, date);
+    }
+    fn roll_over(&mut self, mut bill: Bill, fallback_due: NaiveDate) {
+        if let Some(recurrence) = bill.recurrence.take() {
+            let due = bill.original_due.or(bill.due).unwrap_or(fallback_due);
+            self.add_bill(Bill {
+                due: Some(recurrence.next_due(due)),
+                recurrence: Some(recurrence),
+                paid: None,
+                payments: vec![],
+                attachments: vec![],
+                original_due: None,
+                postponed: 0,
+                ..bill.clone()
+            });
+            bill.id = self.next_id();
+        }
+        self.add_bill(bill);
+    }
+    fn generate_recurring(&mut self, today: NaiveDate) -> Vec<String> {
+        let mut generated = vec![];
+        let mut ids: Vec<i64> = self.list.keys().copied().collect();
+        ids.sort();
+        for id in ids {
+            while let Some(bill) = self.list.get(&id) {
+                let started = match (bill.due, bill.recurrence, bill.paid) {
+                    (Some(due), Some(recurrence), None) => recurrence.next_due(due) <= today,
+                    _ => false,
+                };
+                if !started {
+                    break;
+                }
+                if let Some(bill) = self.list.remove(&id) {
+                    self.roll_over(bill, today);
+                }
+                if let Some(next) = self.list.get(&id) {
+                    generated.push(next.to_string());
+                }
+            }
+        }
+        generated
+    }
+    fn take_paid(&mut self) -> Vec<Bill> {
+        let ids: Vec<i64> = self
+            .list
+            .values()
+            .filter(|bill| bill.paid.is_some())
+            .map(|bill| bill.id)
+            .collect();
+        ids.iter().filter_map(|id| self.list.remove(id)).collect()
+    }
+    fn rename_bill(&mut self, id: i64, new_name: &str) -> bool {
+        match self.list.get_mut(&id) {
+            Some(bill) => {
+                bill.name = new_name.to_string();
+                true
+            }
+            None => false,
+        }
+    }
+    fn with_name(&self, name: &str) -> Vec<&Bill> {
+        let mut bills: Vec<&Bill> = self
+            .list
+            .values()
+            .filter(|bill| bill.name == name)
+            .collect();
+        bills.sort_by_key(|bill| bill.id);
+        bills
+    }
+    fn postpone(&mut self, id: i64, days: u32) -> Option<NaiveDate> {
+        let bill = self.list.get_mut(&id)?;
+        let due = bill.due?.checked_add_days(Days::new(days.into()))?;
+        bill.original_due = bill.original_due.or(bill.due);
+        bill.due = Some(due);
+        bill.postponed += 1;
+        Some(due)
+    }
+    fn attach(&mut self, id: i64, path: PathBuf) -> bool {
+        match self.list.get_mut(&id) {
+            Some(bill) => {
+                bill.attachments.push(path);
+                true
+            }
+            None => false,
+        }
+    }
+    fn set_recurrence(&mut self, id: i64, recurrence: Recurrence) -> bool {
+        match self.list.get_mut(&id) {
+            Some(bill) => {
+                bill.recurrence = Some(recurrence);
+                true
+            }
+            None => false,
+        }
+    }
+    fn set_priority(&mut self, id: i64, priority: Priority) -> bool {
+        match self.list.get_mut(&id) {
+            Some(bill) => {
+                bill.priority = priority;
+                true
+            }
+            None => false,
+        }
+    }
+    fn set_method(&mut self, id: i64, method: Option<Method>) -> bool {
+        match self.list.get_mut(&id) {
+            Some(bill) => {
+                bill.method = method;
+                true
+            }
+            None => false,
+        }
+    }
+    fn set_split(&mut self, id: i64, split: Option<Split>) -> bool {
+        match self.list.get_mut(&id) {
+            Some(bill) => {
+                bill.split = split;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+enum ParseError {
+    #[error("{0}")]
+    InvalidAmount(#[from] bill_core::ParseMoneyError),
+    #[error("invalid id")]
+    InvalidId(#[from] std::num::ParseIntError),
+    #[error("missing fields {0}")]
+    MissingField(String),
+    #[error("invalid date")]
+    InvalidDate(#[from] chrono::ParseError),
+    #[error("{0}")]
+    InvalidRecurrence(String),
+    #[error(transparent)]
+    Invalid(#[from] validate::Invalid),
+    #[error("invalid payment {0}")]
+    InvalidPayment(String),
+    #[error("{0}")]
+    InvalidSplit(String),
+    #[error("{0}")]
+    InvalidMethod(String),
+    #[error("{0}")]
+    InvalidPriority(String),
+}
+
+fn parse_payment(payment: &str) -> Result<Payment, ParseError> {
+    let invalid = || ParseError::InvalidPayment(payment.to_string());
+    let (amount, date) = payment.split_once('@').ok_or_else(invalid)?;
+
+    Ok(Payment {
+        amount: amount.parse().map_err(|_| invalid())?,
+        date: NaiveDate::parse_from_str(date, DATE_FORMAT).map_err(|_| invalid())?,
+    })
+}
+
+/// The field at `index` read with `parse`, or `None` if it is empty.
+fn field<T>(
+    fields: &[String],
+    index: usize,
+    parse: impl FnOnce(&str) -> Result<T, ParseError>,
+) -> Result<Option<T>, FieldError<ParseError>> {
+    match fields.get(index).filter(|field| !field.is_empty()) {
+        Some(field) => parse(field)
+            .map(Some)
+            .map_err(|e| FieldError::new(index, e)),
+        None => Ok(None),
+    }
+}
+
+fn required<T>(
+    fields: &[String],
+    index: usize,
+    name: &str,
+    parse: impl FnOnce(&str) -> Result<T, ParseError>,
+) -> Result<T, FieldError<ParseError>> {
+    field(fields, index, parse)?
+        .ok_or_else(|| FieldError::new(index, ParseError::MissingField(name.to_owned())))
+}
+
+fn stored_date(date: &str) -> Result<NaiveDate, ParseError> {
+    Ok(NaiveDate::parse_from_str(date, DATE_FORMAT)?)
+}
+
+fn parse_bill(fields: &[String], default_currency: &str) -> Result<Bill, FieldError<ParseError>> {
+    let name = required(fields, 0, "name", |name| Ok(name.to_string()))?;
+    let amount = required(fields, 1, "amount", |amount| {
+        Ok(validate::non_negative(amount.parse()?)?)
+    })?;
+    let due = field(fields, 2, stored_date)?;
+    let recurrence = field(fields, 3, |rule| {
+        rule.parse().map_err(ParseError::InvalidRecurrence)
+    })?;
+    let paid = field(fields, 4, stored_date)?;
+    let category = field(fields, 5, |category| Ok(category.to_string()))?;
+    let currency = field(fields, 6, |code| Ok(validate::currency(code)?))?
+        .unwrap_or_else(|| default_currency.to_string());
+    let id = field(fields, 7, |id| Ok(id.parse()?))?.unwrap_or(0);
+    let payments = field(fields, 8, |payments| {
+        payments.split(';').map(parse_payment).collect()
+    })?
+    .unwrap_or_default();
+    let notes = field(fields, 9, |notes| Ok(notes.to_string()))?;
+    let payee = field(fields, 10, |payee| Ok(payee.parse()?))?;
+    let split = field(fields, 11, |split| {
+        split.parse().map_err(ParseError::InvalidSplit)
+    })?;
+    let method = field(fields, 12, |method| {
+        method.parse().map_err(ParseError::InvalidMethod)
+    })?;
+    let attachments = field(fields, 13, |paths| {
+        Ok(paths.split(';').map(PathBuf::from).collect())
+    })?
+    .unwrap_or_default();
+    let original_due = field(fields, 14, stored_date)?;
+    let postponed = field(fields, 15, |count| Ok(count.parse()?))?.unwrap_or(0);
+    let priority = field(fields, 16, |priority| {
+        priority.parse().map_err(ParseError::InvalidPriority)
+    })?
+    .unwrap_or(Priority::Normal);
+
+    Ok(Bill {
+        id,
+        name,
+        amount,
+        due,
+        recurrence,
+        paid,
+        category,
+        currency,
+        payments,
+        notes,
+        payee,
+        split,
+        method,
+        attachments,
+        original_due,
+        postponed,
+        priority,
+    })
+}
+
+impl Rows for Bills {
+    /// The currency of rows without one.
+    type Context = String;
+    const COLUMNS: &'static [&'static str] = BILL_COLUMNS;
+    const MIGRATIONS: &'static [migrate::Migration<migrate::Table>] = migrations::MIGRATIONS;
+    fn to_rows(&self) -> Vec<Vec<String>> {
+        let mut list = self.view_bill();
+        list.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+        list.into_iter().map(bill_fields).collect()
+    }
+    fn from_rows(rows: Vec<Row>, default_currency: &String) -> (Self, Vec<Diagnostic>) {
+        let mut list = Bills::new();
+        let mut unnumbered = vec![];
+        let mut skipped = vec![];
+        for row in rows {
+            match parse_bill(&row.fields, default_currency) {
+                Ok(bill) if bill.id == 0 || list.list.contains_key(&bill.id) => {
+                    unnumbered.push(bill)
+                }
+                Ok(bill) => list.add_bill(bill),
+                Err(e) => skipped.push(row.diagnostic(e.error, e.index)),
+            }
+        }
+        for mut bill in unnumbered {
+            bill.id = list.next_id();
+            list.add_bill(bill);
+        }
+        (list, skipped)
+    }
+}
+
+fn bill_fields(bill: &Bill) -> Vec<String> {
+    let date = |date: Option<NaiveDate>| match date {
+        Some(date) => date.format(DATE_FORMAT).to_string(),
+        None => "".to_string(),
+    };
+    let attachments = bill
+        .attachments
+        .iter()
+        .map(|path| path.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(";");
+    let payments = bill
+        .payments
+        .iter()
+        .map(|payment| format!("{}@{}", payment.amount, payment.date.format(DATE_FORMAT)))
+        .collect::<Vec<_>>()
+        .join(";");
+
+    vec![
+        bill.name.clone(),
+        bill.amount.to_string(),
+        date(bill.due),
+        bill.recurrence.map(|r| r.to_string()).unwrap_or_default(),
+        date(bill.paid),
+        bill.category.clone().unwrap_or_default(),
+        bill.currency.clone(),
+        bill.id.to_string(),
+        payments,
+        bill.notes.clone().unwrap_or_default(),
+        bill.payee
+            .map(|payee| payee.to_string())
+            .unwrap_or_default(),
+        bill.split
+            .as_ref()
+            .map(|s| s.to_string())
+            .unwrap_or_default(),
+        bill.method.map(|m| m.to_string()).unwrap_or_default(),
+        attachments,
+        date(bill.original_due),
+        bill.postponed.to_string(),
+        bill.priority.to_string(),
+    ]
+}
+
+/// Opens a bills file with the backend the `storage` setting picked,
+/// encrypted if the `encrypt` setting is on.
+fn open_bills(file_name: &Path, default_currency: &str) -> io::Result<Box<dyn Storage<Bills>>> {
+    #[cfg(feature = "encryption")]
+    if let Some(passphrase) = config::passphrase() {
+        return Ok(storage::open_encrypted(
+            config::storage(),
+            file_name,
+            default_currency.to_string(),
+            passphrase,
+        )?);
+    }
+    Ok(storage::open(
+        config::storage(),
+        file_name,
+        default_currency.to_string(),
+    )?)
+}
+
+/// Where the last bill id handed out is kept, next to the bills file.
+fn counter_file(file_name: &Path) -> PathBuf {
+    let mut counter_name = file_name.file_name().unwrap_or_default().to_os_string();
+    counter_name.push(".ids");
+    file_name.with_file_name(counter_name)
+}
+
+fn save_bills(file_name: &Path, bills: &Bills) -> io::Result<()> {
+    // Only loading falls back to a default currency.
+    let store = open_bills(file_name, "")?;
+    store.save(bills)?;
+    // Encrypted bills keep no plaintext side files, so they go without one.
+    let vfs = store.vfs();
+    match store.encrypted() {
+        true => match vfs.remove_file(&counter_file(file_name)) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            result => result?,
+        },
+        false => bills.counter.save(&*vfs, &counter_file(file_name))?,
+    }
+    tracing::debug!(
+        "saved {} bills to {}",
+        bills.list.len(),
+        file_name.display()
+    );
+    Ok(())
+}
+
+fn read_bills(input_file: &Path, default_currency: &str) -> io::Result<Bills> {
+    let store = open_bills(input_file, default_currency)?;
+    let mut bills = store.load()?;
+    if !store.encrypted() {
+        bills.counter = ids::Counter::load(&*store.vfs(), &counter_file(input_file))?;
+    }
+    tracing::debug!(
+        "loaded {} bills from {}",
+        bills.list.len(),
+        input_file.display()
+    );
+    Ok(bills)
+}
+
+fn load_bills(input_file: &Path, default_currency: &str) -> io::Result<Bills> {
+    match read_bills(input_file, default_currency) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Bills::new()),
+        result => result,
+    }
+}
+
+fn load_contacts(input_file: &Path) -> io::Result<Records> {
+    match contact_manager::load_records(input_file.to_path_buf()) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Records::new()),
+        result => result,
+    }
+}
+
+fn export_bills(file_name: &Path, bills: &Bills) -> io::Result<()> {
+    Ok(Csv::new(file_name, String::new()).save(bills)?)
+}
+
+fn import_bills(input_file: &Path, bills: &mut Bills, default_currency: &str) -> io::Result<usize> {
+    let imported: Bills = Csv::new(input_file, default_currency.to_string()).load()?;
+    let count = imported.list.len();
+    let mut imported: Vec<Bill> = imported.list.into_values().collect();
+    imported.sort_by_key(|bill| bill.id);
+    for mut bill in imported {
+        bill.id = match bills.with_name(&bill.name).as_slice() {
+            [existing] => existing.id,
+            _ => bills.next_id(),
+        };
+        bills.add_bill(bill);
+    }
+    Ok(count)
+}
+
+const MENU_KEYS: &str = "1234567890acdefghijklmnopqrstuvwxyzACDEFGHIJKLMNOPQRSTUVWXYZ";
+
+type MenuResult = Result<(), Back>;
+
+fn menu_key(num: usize) -> Option<char> {
+    MENU_KEYS.chars().nth(num)
+}
+
+fn menu_number(input: &str) -> String {
+    let mut chars = input.chars();
+    match (chars.next(), chars.next()) {
+        (Some(key), None) => match MENU_KEYS.find(key) {
+            Some(num) => (num + 1).to_string(),
+            None => input.to_string(),
+        },
+        _ => input.to_string(),
+    }
+}
+
+struct Menu;
+impl Menu {
+    fn show_menu(ui: &mut dyn UserInterface) {
+        writeln!(ui);
+        writeln!(ui, "== {} ==", i18n::t!("bills-title"));
+        for (num, item) in locale::MENU.iter().enumerate() {
+            let key = menu_key(num).map_or_else(|| (num + 1).to_string(), String::from);
+            writeln!(ui, "{}. {}", Style::Strong.paint(&key), i18n::t!(item));
+        }
+        writeln!(ui);
+    }
+    fn add_bill_menu(
+        ui: &mut dyn UserInterface,
+        bills: &mut Bills,
+        contacts: &Records,
+        default_currency: &str,
+        default_category: Option<&str>,
+        max_amount: Money,
+    ) -> MenuResult {
+        let name = match ui.prompt(&i18n::t!("bills-prompt-name"), &i18n::t!("bills-help-name"))? {
+            Some(input) => input,
+            None => return Ok(()),
+        };
+        let mut id = bills.next_id();
+        let existing = bills.with_name(&name);
+        if !existing.is_empty() {
+            writeln!(ui, "{}", i18n::t!("bills-name-taken"));
+            for bill in &existing {
+                writeln!(ui, "{}", bill);
+            }
+            match ui
+                .prompt(
+                    &i18n::t!("bills-prompt-name-taken"),
+                    &i18n::t!("bills-help-name-taken"),
+                )?
+                .as_deref()
+            {
+                Some("a") => {}
+                Some("r") if existing.len() == 1 => id = existing[0].id,
+                Some("r") => {
+                    writeln!(ui, "{}", i18n::t!("bills-name-ambiguous"));
+                    return Ok(());
+                }
+                _ => return Ok(()),
+            }
+        }
+
+        let amount = match get_bill_amount(ui, &i18n::t!("bills-prompt-amount"), max_amount)? {
+            Some(amount) => amount,
+            None => return Ok(()),
+        };
+        let due = get_due_date(ui, None)?;
+        let recurrence = get_recurrence(ui, None)?;
+        let category = get_category(ui, &bills.categories(), default_category)?;
+        let currency = get_currency(ui, default_currency)?;
+        let payee = get_payee(ui, contacts, None)?;
+        let method = get_method(ui, None)?;
+        let priority = get_priority(ui, Priority::Normal)?;
+
+        let bill = Bill {
+            id,
+            name,
+            amount,
+            due,
+            recurrence,
+            paid: None,
+            category,
+            currency,
+            payments: vec![],
+            notes: None,
+            payee,
+            split: None,
+            method,
+            attachments: vec![],
+            original_due: None,
+            postponed: 0,
+            priority,
+        };
+        bills.add_bill(bill);
+        writeln!(ui, "{}", i18n::t!("bills-added"));
+        Ok(())
+    }
+    fn view_bill_menu(
+        ui: &mut dyn UserInterface,
+        bills: &Bills,
+        fees: LateFees,
+        prefs: &mut ViewPrefs,
+        rates: &Rates,
+        base: &str,
+    ) -> MenuResult {
+        let shown = match (prefs.grouping, prefs.filter) {
+            (Grouping::Aging, _) => "o",
+            (_, Filter::All) => "a",
+            (_, Filter::Unpaid) => "u",
+            (_, Filter::Paid) => "p",
+        };
+        match ui
+            .prompt(
+                &i18n::t!("bills-prompt-filter", current = shown),
+                &i18n::t!("bills-help-filter"),
+            )?
+            .as_deref()
+        {
+            Some(choice @ ("a" | "u" | "p")) => {
+                prefs.filter = match choice {
+                    "u" => Filter::Unpaid,
+                    "p" => Filter::Paid,
+                    _ => Filter::All,
+                };
+                // A list of bills is never grouped by aging.
+                if prefs.grouping == Grouping::Aging {
+                    prefs.grouping = Grouping::Flat;
+                }
+            }
+            Some("o") => prefs.grouping = Grouping::Aging,
+            _ => {}
+        }
+        if prefs.grouping == Grouping::Aging {
+            return Menu::aging_menu(ui, bills, fees);
+        }
+
+        let shown = match prefs.sort {
+            SortKey::Name => "n",
+            SortKey::Amount => "a",
+            SortKey::Due => "d",
+            SortKey::Category => "c",
+        };
+        match ui
+            .prompt(
+                &i18n::t!("bills-prompt-sort", current = shown),
+                &i18n::t!("bills-help-sort"),
+            )?
+            .as_deref()
+        {
+            Some("n") => prefs.sort = SortKey::Name,
+            Some("a") => prefs.sort = SortKey::Amount,
+            Some("d") => prefs.sort = SortKey::Due,
+            Some("c") => prefs.sort = SortKey::Category,
+            _ => {}
+        }
+        let shown = if prefs.descending { "y" } else { "n" };
+        match ui
+            .prompt(
+                &i18n::t!("bills-prompt-descending", current = shown),
+                &i18n::t!("bills-help-descending"),
+            )?
+            .as_deref()
+        {
+            Some("y") => prefs.descending = true,
+            Some("n") => prefs.descending = false,
+            _ => {}
+        }
+        let shown = match prefs.grouping {
+            Grouping::Category => "c",
+            _ => "n",
+        };
+        match ui
+            .prompt(
+                &i18n::t!("bills-prompt-group", current = shown),
+                &i18n::t!("bills-help-group"),
+            )?
+            .as_deref()
+        {
+            Some("n") => prefs.grouping = Grouping::Flat,
+            Some("c") => prefs.grouping = Grouping::Category,
+            _ => {}
+        }
+
+        let today = Local::now().date_naive();
+        let sort = Sort {
+            key: prefs.sort,
+            descending: prefs.descending,
+        };
+        let mut list: Vec<&Bill> = bills
+            .view_sorted(sort)
+            .into_iter()
+            .filter(|bill| prefs.filter.matches(bill))
+            .collect();
+        if prefs.filter == Filter::Unpaid {
+            list.sort_by_key(|bill| bill.priority);
+        }
+        if prefs.grouping == Grouping::Flat {
+            for bill in list {
+                writeln!(ui, "{}", bill_line(bill, fees, today));
+            }
+            return Ok(());
+        }
+
+        let total = converted_total(&list, rates, base);
+        let mut groups: BTreeMap<&str, Vec<&Bill>> = BTreeMap::new();
+        for bill in list {
+            let category = bill.category.as_deref().unwrap_or(UNCATEGORIZED);
+            groups.entry(category).or_default().push(bill);
+        }
+        for (category, list) in groups {
+            writeln!(ui);
+            writeln!(
+                ui,
+                "{}",
+                category_heading(category, &list, total, rates, base)
+            );
+            for bill in list {
+                writeln!(ui, "{}", bill_line(bill, fees, today));
+            }
+        }
+        Ok(())
+    }
+    fn aging_menu(ui: &mut dyn UserInterface, bills: &Bills, fees: LateFees) -> MenuResult {
+        let today = Local::now().date_naive();
+        let unpaid: Vec<&Bill> = bills
+            .view_by_due()
+            .into_iter()
+            .filter(|bill| Filter::Unpaid.matches(bill))
+            .collect();
+        if unpaid.is_empty() {
+            writeln!(ui, "{}", i18n::t!("bills-no-unpaid"));
+            return Ok(());
+        }
+
+        for bucket in Bucket::ALL {
+            let list: Vec<&Bill> = unpaid
+                .iter()
+                .copied()
+                .filter(|bill| Bucket::of(bill, today) == bucket)
+                .collect();
+            if list.is_empty() {
+                continue;
+            }
+            let subtotal = totals_by_currency(
+                list.iter()
+                    .map(|bill| (bill.currency.as_str(), bill.remaining())),
+            );
+            writeln!(ui);
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!(
+                    "bills-bucket-heading",
+                    bucket = bucket,
+                    subtotal = currency::format_totals(&subtotal)
+                )
+            );
+            for bill in list {
+                writeln!(ui, "{}", bill_line(bill, fees, today));
+            }
+        }
+        Ok(())
+    }
+    fn remove_bill_menu(ui: &mut dyn UserInterface, bills: &mut Bills) -> MenuResult {
+        let id = match get_bill_id(ui, bills, &i18n::t!("bills-prompt-name"))? {
+            Some(input) => input,
+            None => return Ok(()),
+        };
+
+        match bills.list.get(&id) {
+            Some(bill) => writeln!(ui, "{}", bill),
+            None => {
+                writeln!(ui, "{}", i18n::t!("bills-not-found"));
+                return Ok(());
+            }
+        }
+        if !confirm(ui, &i18n::t!("bills-confirm-remove"))? {
+            return Ok(());
+        }
+
+        bills.remove_bill(id);
+        writeln!(ui, "{}", i18n::t!("bills-removed"));
+        Ok(())
+    }
+    fn update_bill_menu(
+        ui: &mut dyn UserInterface,
+        bills: &mut Bills,
+        contacts: &Records,
+        max_amount: Money,
+    ) -> MenuResult {
+        for bill in bills.view_by_due() {
+            writeln!(ui, "{}", bill);
+        }
+
+        let id = match get_bill_id(ui, bills, &i18n::t!("bills-prompt-update"))? {
+            Some(input) => input,
+            None => return Ok(()),
+        };
+
+        let amount = match get_bill_amount(ui, &i18n::t!("bills-prompt-amount"), max_amount)? {
+            Some(amount) => amount,
+            None => return Ok(()),
+        };
+        let due = get_due_date(ui, bills.list.get(&id).and_then(|bill| bill.due))?;
+        let recurrence = get_recurrence(ui, bills.list.get(&id).and_then(|bill| bill.recurrence))?;
+        let notes = get_notes(ui, bills.list.get(&id).and_then(|bill| bill.notes.clone()))?;
+        let payee = get_payee(
+            ui,
+            contacts,
+            bills.list.get(&id).and_then(|bill| bill.payee),
+        )?;
+
+        let method = get_method(ui, bills.list.get(&id).and_then(|bill| bill.method))?;
+        let priority = get_priority(
+            ui,
+            bills
+                .list
+                .get(&id)
+                .map_or_else(Priority::default, |bill| bill.priority),
+        )?;
+
+        if bills.update_bill(id, amount, due, recurrence, notes, payee) {
+            bills.set_method(id, method);
+            bills.set_priority(id, priority);
+            writeln!(ui, "{}", i18n::t!("bills-updated"));
+        } else {
+            writeln!(ui, "{}", i18n::t!("bills-not-found"));
+        }
+        Ok(())
+    }
+    fn summary_bill_menu(
+        ui: &mut dyn UserInterface,
+        bills: &Bills,
+        rates: &Rates,
+        base: &str,
+        fees: LateFees,
+    ) -> MenuResult {
+        let summary = bills.summary(Local::now().date_naive(), fees);
+        writeln!(
+            ui,
+            "{}",
+            i18n::t!(
+                "bills-total-owed",
+                amount = currency::format_totals(&summary.owed)
+            )
+        );
+        print_converted_total(ui, &summary.owed, rates, base);
+        if !summary.late.is_empty() {
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!(
+                    "bills-late-charges",
+                    amount = currency::format_totals(&summary.late)
+                )
+            );
+            print_converted_total(ui, &summary.late, rates, base);
+        }
+        writeln!(
+            ui,
+            "{}",
+            i18n::t!(
+                "bills-paid-this-month",
+                amount = currency::format_totals(&summary.paid_this_month)
+            )
+        );
+        print_converted_total(ui, &summary.paid_this_month, rates, base);
+        writeln!(ui, "{}", i18n::t!("bills-count", count = summary.count));
+        match summary.largest {
+            Some(bill) => writeln!(ui, "{}", i18n::t!("bills-largest", bill = bill)),
+            None => writeln!(ui, "{}", i18n::t!("bills-largest-none")),
+        }
+        Ok(())
+    }
+    fn search_bill_menu(ui: &mut dyn UserInterface, bills: &Bills, fees: LateFees) -> MenuResult {
+        let query = loop {
+            let input = match ui.prompt(
+                &i18n::t!("bills-prompt-search"),
+                &i18n::t!("bills-help-search"),
+            )? {
+                Some(input) => input,
+                None => return Ok(()),
+            };
+            match Query::parse(&input) {
+                Ok(query) => break query,
+                Err(e) => writeln!(ui, "{}", e.render_line(&input)),
+            }
+        };
+
+        let results: Vec<&Bill> = bills
+            .view_by_due()
+            .into_iter()
+            .filter(|bill| query.matches(bill))
+            .collect();
+        if results.is_empty() {
+            writeln!(ui, "{}", i18n::t!("bills-none-found"));
+        }
+        let today = Local::now().date_naive();
+        for bill in results {
+            writeln!(ui, "{}", bill_line(bill, fees, today));
+        }
+        Ok(())
+    }
+    fn rename_bill_menu(ui: &mut dyn UserInterface, bills: &mut Bills) -> MenuResult {
+        let id = match get_bill_id(ui, bills, &i18n::t!("bills-prompt-rename"))? {
+            Some(input) => input,
+            None => return Ok(()),
+        };
+        let new_name = match ui.prompt(
+            &i18n::t!("bills-prompt-new-name"),
+            &i18n::t!("bills-help-new-name"),
+        )? {
+            Some(input) => input,
+            None => return Ok(()),
+        };
+
+        if bills.rename_bill(id, &new_name) {
+            writeln!(ui, "{}", i18n::t!("bills-renamed"));
+        } else {
+            writeln!(ui, "{}", i18n::t!("bills-not-found"));
+        }
+        Ok(())
+    }
+    fn show_bill_menu(
+        ui: &mut dyn UserInterface,
+        bills: &Bills,
+        contacts: &Records,
+        fees: LateFees,
+    ) -> MenuResult {
+        let id = match get_bill_id(ui, bills, &i18n::t!("bills-prompt-name"))? {
+            Some(input) => input,
+            None => return Ok(()),
+        };
+        let bill = match bills.list.get(&id) {
+            Some(bill) => bill,
+            None => {
+                writeln!(ui, "{}", i18n::t!("bills-not-found"));
+                return Ok(());
+            }
+        };
+
+        let date = |date: Option<NaiveDate>| match date {
+            Some(date) => config::format_date(date),
+            None => "-".to_string(),
+        };
+        let mut fields = vec![
+            (i18n::t!("bills-field-name"), bill.name.clone()),
+            (
+                i18n::t!("bills-field-amount"),
+                currency::format_amount(bill.amount, &bill.currency),
+            ),
+            (
+                i18n::t!("bills-field-category"),
+                bill.category
+                    .as_deref()
+                    .unwrap_or(UNCATEGORIZED)
+                    .to_string(),
+            ),
+            (i18n::t!("bills-field-due"), date(bill.due)),
+        ];
+        if bill.postponed > 0 {
+            fields.push((
+                i18n::t!("bills-field-postponed"),
+                i18n::t!(
+                    "bills-postponed-times",
+                    count = bill.postponed,
+                    date = date(bill.original_due)
+                ),
+            ));
+        }
+        fields.push((
+            i18n::t!("bills-field-repeats"),
+            bill.recurrence.map_or_else(
+                || i18n::t!("bills-repeats-never"),
+                |recurrence| recurrence.to_string(),
+            ),
+        ));
+        fields.push((i18n::t!("bills-field-paid"), date(bill.paid)));
+        fields.push((
+            i18n::t!("bills-field-remaining"),
+            currency::format_amount(bill.remaining(), &bill.currency),
+        ));
+        let late = fees.accrued(bill, Local::now().date_naive());
+        if late > Money::ZERO {
+            fields.push((
+                i18n::t!("bills-field-late"),
+                currency::format_amount(late, &bill.currency),
+            ));
+        }
+        fields.push((
+            i18n::t!("bills-field-payments"),
+            bill.payments.len().to_string(),
+        ));
+        fields.push((
+            i18n::t!("bills-field-notes"),
+            bill.notes.as_deref().unwrap_or("-").to_string(),
+        ));
+        fields.push((
+            i18n::t!("bills-field-method"),
+            bill.method
+                .map_or_else(|| "-".to_string(), |method| method.to_string()),
+        ));
+        fields.push((i18n::t!("bills-field-priority"), bill.priority.to_string()));
+        fields.push((
+            i18n::t!("bills-field-split"),
+            bill.split
+                .as_ref()
+                .map_or_else(|| "-".to_string(), |split| split.to_string()),
+        ));
+        let payee = match bill.payee.map(|id| (id, contacts.get(id))) {
+            Some((_, Some(contact))) => format!(
+                "{} <{}>",
+                contact.name,
+                contact.email.as_deref().unwrap_or("-")
+            ),
+            Some((id, None)) => i18n::t!("bills-payee-missing", id = id),
+            None => "-".to_string(),
+        };
+        fields.push((i18n::t!("bills-field-payee"), payee));
+        let receipts = if bill.attachments.is_empty() { "-" } else { "" };
+        fields.push((i18n::t!("bills-field-receipts"), receipts.to_string()));
+        for line in render::card(&i18n::t!("bills-card-title", id = bill.id), &fields) {
+            writeln!(ui, "{}", line);
+        }
+        if bill.attachments.is_empty() {
+            return Ok(());
+        }
+        for (num, path) in bill.attachments.iter().enumerate() {
+            writeln!(ui, "    {}. {}", num + 1, path.display());
+        }
+
+        let input = match ui.prompt(
+            &i18n::t!("bills-prompt-open-receipt"),
+            &i18n::t!("bills-help-open-receipt"),
+        )? {
+            Some(input) => input,
+            None => return Ok(()),
+        };
+        let path = input
+            .parse::<usize>()
+            .ok()
+            .and_then(|num| bill.attachments.get(num.checked_sub(1)?));
+        match path {
+            Some(path) => {
+                if let Err(e) = attachments::open(path) {
+                    writeln!(ui, "{}", i18n::t!("bills-receipt-open-failed", error = e));
+                }
+            }
+            None => writeln!(ui, "{}", i18n::t!("bills-no-such-receipt")),
+        }
+        Ok(())
+    }
+    fn postpone_menu(ui: &mut dyn UserInterface, bills: &mut Bills) -> MenuResult {
+        for bill in bills.view_by_due() {
+            if Filter::Unpaid.matches(bill) && bill.due.is_some() {
+                writeln!(ui, "{}", bill);
+            }
+        }
+        let id = match get_bill_id(ui, bills, &i18n::t!("bills-prompt-postpone"))? {
+            Some(input) => input,
+            None => return Ok(()),
+        };
+        match bills.list.get(&id) {
+            Some(bill) if bill.paid.is_some() => {
+                writeln!(ui, "{}", i18n::t!("bills-already-paid"));
+                return Ok(());
+            }
+            Some(bill) if bill.due.is_none() => {
+                writeln!(ui, "{}", i18n::t!("bills-no-due-date"));
+                return Ok(());
+            }
+            Some(_) => {}
+            None => {
+                writeln!(ui, "{}", i18n::t!("bills-not-found"));
+                return Ok(());
+            }
+        }
+
+        let days = loop {
+            let input = match ui.prompt(
+                &i18n::t!("bills-prompt-postpone-days"),
+                &i18n::t!("bills-help-postpone-days"),
+            )? {
+                Some(input) => input,
+                None => break 7,
+            };
+            match input.parse::<u32>() {
+                Ok(days) if days > 0 => break days,
+                _ => writeln!(ui, "{}", i18n::t!("bills-enter-days")),
+            }
+        };
+        match bills.postpone(id, days) {
+            Some(due) => writeln!(
+                ui,
+                "{}",
+                i18n::t!("bills-now-due", date = config::format_date(due))
+            ),
+            None => writeln!(ui, "{}", i18n::t!("bills-postpone-failed")),
+        }
+        Ok(())
+    }
+    fn attach_menu(ui: &mut dyn UserInterface, bills: &mut Bills, dir: &Path) -> MenuResult {
+        let id = match get_bill_id(ui, bills, &i18n::t!("bills-prompt-attach"))? {
+            Some(input) => input,
+            None => return Ok(()),
+        };
+        if !bills.list.contains_key(&id) {
+            writeln!(ui, "{}", i18n::t!("bills-not-found"));
+            return Ok(());
+        }
+
+        let path = match ui.prompt(
+            &i18n::t!("bills-prompt-receipt-file"),
+            &i18n::t!("bills-help-receipt-file"),
+        )? {
+            Some(input) => PathBuf::from(input),
+            None => return Ok(()),
+        };
+        if !path.is_file() {
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!("bills-not-a-file", path = path.display())
+            );
+            return Ok(());
+        }
+        if !attachments::is_valid_path(&path) {
+            writeln!(ui, "{}", i18n::t!("bills-file-name-semicolon"));
+            return Ok(());
+        }
+
+        let path = if confirm(
+            ui,
+            &i18n::t!("bills-confirm-copy-receipt", dir = dir.display()),
+        )? {
+            match attachments::copy_into(dir, id, &path) {
+                Ok(copy) => copy,
+                Err(e) => {
+                    writeln!(ui, "{}", i18n::t!("bills-receipt-copy-failed", error = e));
+                    return Ok(());
+                }
+            }
+        } else {
+            fs::canonicalize(&path).unwrap_or(path)
+        };
+        bills.attach(id, path);
+        writeln!(ui, "{}", i18n::t!("bills-receipt-attached"));
+        Ok(())
+    }
+    fn archive_menu(
+        ui: &mut dyn UserInterface,
+        bills: &mut Bills,
+        archive: &mut Bills,
+    ) -> MenuResult {
+        let count = bills
+            .list
+            .values()
+            .filter(|bill| Filter::Paid.matches(bill))
+            .count();
+        if count == 0 {
+            writeln!(ui, "{}", i18n::t!("bills-nothing-to-archive"));
+            return Ok(());
+        }
+        if !confirm(ui, &i18n::t!("bills-confirm-archive", count = count))? {
+            return Ok(());
+        }
+
+        let paid = bills.take_paid();
+        let count = paid.len();
+        for bill in paid {
+            archive.add_bill(bill);
+        }
+        writeln!(ui, "{}", i18n::t!("bills-archived", count = count));
+        let this_year = Local::now().year();
+        if archive.list.len() > rotation::LARGE_ARCHIVE
+            && !rotation::closed_years(archive, this_year).is_empty()
+        {
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!("bills-archive-large", count = archive.list.len())
+            );
+        }
+        Ok(())
+    }
+    fn view_archive_menu(
+        ui: &mut dyn UserInterface,
+        vfs: &dyn Vfs,
+        archive: &Bills,
+        archive_file: &Path,
+    ) -> MenuResult {
+        if archive.list.is_empty() {
+            writeln!(ui, "{}", i18n::t!("bills-archive-empty"));
+        }
+        for bill in archive.view_by_due() {
+            writeln!(ui, "{}", bill);
+        }
+        let years = rotation::rotated_years(vfs, archive_file).unwrap_or_default();
+        if !years.is_empty() {
+            let years: Vec<String> = years.iter().map(|year| year.to_string()).collect();
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!("bills-rotated-years", years = years.join(", "))
+            );
+        }
+        Ok(())
+    }
+    fn history_menu(ui: &mut dyn UserInterface, vfs: &dyn Vfs, log_file: &Path) -> MenuResult {
+        let entries = match activity::load(vfs, log_file) {
+            Ok(entries) => entries,
+            Err(e) => {
+                writeln!(ui, "{}", i18n::t!("bills-history-read-failed", error = e));
+                return Ok(());
+            }
+        };
+        let input = ui
+            .prompt(
+                &i18n::t!("bills-prompt-history"),
+                &i18n::t!("bills-help-history"),
+            )?
+            .unwrap_or_default();
+        let id: Option<i64> = input.strip_prefix('#').unwrap_or(&input).parse().ok();
+        let name = input.to_lowercase();
+
+        let mut shown = 0;
+        for entry in entries {
+            let matches = match id {
+                Some(id) => entry.id == id,
+                None => entry.text.to_lowercase().contains(&name),
+            };
+            if matches {
+                writeln!(
+                    ui,
+                    "{} #{} {}",
+                    entry.time.format("%Y-%m-%d %H:%M"),
+                    entry.id,
+                    entry.text
+                );
+                shown += 1;
+            }
+        }
+        if shown == 0 {
+            writeln!(ui, "{}", i18n::t!("bills-no-matching-history"));
+        }
+        Ok(())
+    }
+    fn rotate_menu(
+        ui: &mut dyn UserInterface,
+        vfs: &dyn Vfs,
+        archive: &mut Bills,
+        archive_file: &Path,
+        default_currency: &str,
+    ) -> MenuResult {
+        let years = rotation::closed_years(archive, Local::now().year());
+        if years.is_empty() {
+            writeln!(ui, "{}", i18n::t!("bills-no-past-years"));
+            return Ok(());
+        }
+        for (year, ids) in &years {
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!("bills-year-count", year = year, count = ids.len())
+            );
+        }
+        if !confirm(ui, &i18n::t!("bills-confirm-rotate"))? {
+            return Ok(());
+        }
+
+        for (year, ids) in years {
+            let list: Vec<&Bill> = ids.iter().filter_map(|id| archive.list.get(id)).collect();
+            match rotation::save_year(vfs, archive_file, year, &list, default_currency) {
+                Ok(file_name) => {
+                    for id in &ids {
+                        archive.list.remove(id);
+                    }
+                    writeln!(
+                        ui,
+                        "{}",
+                        i18n::t!(
+                            "bills-rotated",
+                            count = ids.len(),
+                            file = file_name.display()
+                        )
+                    );
+                }
+                Err(e) => writeln!(
+                    ui,
+                    "{}",
+                    i18n::t!("bills-rotate-failed", year = year, error = e)
+                ),
+            }
+        }
+        Ok(())
+    }
+    fn unarchive_menu(
+        ui: &mut dyn UserInterface,
+        bills: &mut Bills,
+        archive: &mut Bills,
+    ) -> MenuResult {
+        let id = match get_bill_id(ui, archive, &i18n::t!("bills-prompt-unarchive"))? {
+            Some(input) => input,
+            None => return Ok(()),
+        };
+
+        match archive.list.remove(&id) {
+            Some(mut bill) => {
+                if bills.list.contains_key(&bill.id) {
+                    bill.id = bills.next_id();
+                }
+                bills.add_bill(bill);
+                writeln!(ui, "{}", i18n::t!("bills-unarchived"));
+            }
+            None => writeln!(ui, "{}", i18n::t!("bills-not-in-archive")),
+        }
+        Ok(())
+    }
+    fn undo_menu(
+        ui: &mut dyn UserInterface,
+        bills: &mut Bills,
+        history: &mut History<Changes<i64, Bill>>,
+    ) -> MenuResult {
+        match history.undo(&mut bills.list) {
+            Some(redo) => writeln!(
+                ui,
+                "{}",
+                i18n::t!("bills-undone", names = changed_names(redo, bills))
+            ),
+            None => writeln!(ui, "{}", i18n::t!("bills-nothing-to-undo")),
+        }
+        Ok(())
+    }
+    fn redo_menu(
+        ui: &mut dyn UserInterface,
+        bills: &mut Bills,
+        history: &mut History<Changes<i64, Bill>>,
+    ) -> MenuResult {
+        match history.redo(&mut bills.list) {
+            Some(undo) => writeln!(
+                ui,
+                "{}",
+                i18n::t!("bills-redone", names = changed_names(undo, bills))
+            ),
+            None => writeln!(ui, "{}", i18n::t!("bills-nothing-to-redo")),
+        }
+        Ok(())
+    }
+    fn rates_menu(ui: &mut dyn UserInterface, rates: &mut Rates, base: &str) -> MenuResult {
+        for (from, to, rate) in rates.iter() {
+            writeln!(ui, "1 {} = {} {}", from, rate, to);
+        }
+
+        loop {
+            let code = match ui.prompt(
+                &i18n::t!("bills-prompt-rate-currency", base = base),
+                &i18n::t!("bills-help-rate-currency", base = base),
+            )? {
+                Some(input) => input.to_uppercase(),
+                None => return Ok(()),
+            };
+            if validate::currency(&code).is_err() || code == base {
+                writeln!(
+                    ui,
+                    "{}",
+                    i18n::t!("bills-enter-other-currency", base = base)
+                );
+                continue;
+            }
+
+            match get_rate(ui, &code, base)? {
+                Some(rate) => {
+                    rates.set(&code, base, rate);
+                    writeln!(ui, "{}", i18n::t!("bills-rate-saved"));
+                }
+                None => {
+                    if rates.remove(&code, base) {
+                        writeln!(ui, "{}", i18n::t!("bills-rate-removed"));
+                    }
+                }
+            }
+        }
+    }
+    fn profile_menu(
+        ui: &mut dyn UserInterface,
+        opt: &Opt,
+        data_file: &Path,
+        current: &str,
+    ) -> Result<Option<String>, Back> {
+        let profiles = match profile::list_profiles(data_file) {
+            Ok(profiles) => profiles,
+            Err(e) => {
+                writeln!(ui, "{}", i18n::t!("bills-profiles-list-failed", error = e));
+                return Ok(None);
+            }
+        };
+        for name in &profiles {
+            let marker = if name == current { "*" } else { " " };
+            writeln!(ui, "{} {}", marker, name);
+        }
+
+        let input = match ui.prompt(
+            &i18n::t!("bills-prompt-profile"),
+            &i18n::t!("bills-help-profile"),
+        )? {
+            Some(input) => input,
+            None => return Ok(None),
+        };
+        if let Some(name) = input.strip_prefix('-') {
+            if name == current || name == profile::DEFAULT_PROFILE {
+                writeln!(ui, "{}", i18n::t!("bills-profile-in-use"));
+            } else if !profiles.iter().any(|profile| profile == name) {
+                writeln!(ui, "{}", i18n::t!("bills-profile-not-found"));
+            } else if confirm(ui, &i18n::t!("bills-confirm-delete-profile", name = name))? {
+                match Profile::new(opt, data_file, name).remove_files() {
+                    Ok(()) => writeln!(ui, "{}", i18n::t!("bills-profile-deleted")),
+                    Err(e) => {
+                        writeln!(ui, "{}", i18n::t!("bills-profile-delete-failed", error = e))
+                    }
+                }
+            }
+            return Ok(None);
+        }
+
+        if !profile::is_valid_name(&input) {
+            writeln!(ui, "{}", i18n::t!("bills-profile-name-chars"));
+            return Ok(None);
+        }
+        if input == current {
+            return Ok(None);
+        }
+        if !profiles.contains(&input) {
+            writeln!(ui, "{}", i18n::t!("bills-profile-created"));
+        }
+        Ok(Some(input))
+    }
+    fn settings_menu(ui: &mut dyn UserInterface, config: &mut Config) -> MenuResult {
+        loop {
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!("bills-setting-currency", value = config.currency)
+            );
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!(
+                    "bills-setting-default-category",
+                    value = config.default_category.as_deref().unwrap_or("-")
+                )
+            );
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!(
+                    "bills-setting-data-file",
+                    value = config.data_file.display()
+                )
+            );
+            match &config.date_format {
+                Some(format) => writeln!(
+                    ui,
+                    "{}",
+                    i18n::t!("bills-setting-date-format", value = format)
+                ),
+                None => writeln!(
+                    ui,
+                    "{}",
+                    i18n::t!(
+                        "bills-setting-date-format-default",
+                        value = config.date_format()
+                    )
+                ),
+            }
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!("bills-setting-reminder-days", value = config.reminder_days)
+            );
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!("bills-setting-rounding", value = config.rounding)
+            );
+            match config.locale {
+                Some(locale) => {
+                    writeln!(ui, "{}", i18n::t!("bills-setting-language", value = locale))
+                }
+                None => writeln!(
+                    ui,
+                    "{}",
+                    i18n::t!("bills-setting-language-default", value = config.locale())
+                ),
+            }
+            match config.tax_categories.as_slice() {
+                [] => writeln!(ui, "{}", i18n::t!("bills-setting-tax-categories-none")),
+                categories => writeln!(
+                    ui,
+                    "{}",
+                    i18n::t!(
+                        "bills-setting-tax-categories",
+                        value = categories.join(", ")
+                    )
+                ),
+            }
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!("bills-setting-theme", value = config.theme)
+            );
+
+            let input = match ui.prompt(
+                &i18n::t!("bills-prompt-setting"),
+                &i18n::t!("bills-help-setting"),
+            )? {
+                Some(input) => input,
+                None => return Ok(()),
+            };
+            match input.as_str() {
+                "1" => config.currency = get_currency(ui, &config.currency)?,
+                "2" => {
+                    match ui.prompt(
+                        &i18n::t!("bills-prompt-default-category"),
+                        &i18n::t!("bills-help-default-category"),
+                    )? {
+                        Some(input) if input == "-" => config.default_category = None,
+                        Some(input) => config.default_category = Some(input),
+                        None => {}
+                    }
+                }
+                "3" => {
+                    if let Some(input) = ui.prompt(
+                        &i18n::t!("bills-prompt-data-file"),
+                        &i18n::t!("bills-help-data-file"),
+                    )? {
+                        config.data_file = PathBuf::from(input);
+                    }
+                }
+                "4" => {
+                    while let Some(input) = ui.prompt(
+                        &i18n::t!("bills-prompt-date-format"),
+                        &i18n::t!("bills-help-date-format"),
+                    )? {
+                        if input == "-" {
+                            config.date_format = None;
+                            break;
+                        }
+                        if config::is_valid_date_format(&input) {
+                            config.date_format = Some(input);
+                            break;
+                        }
+                        writeln!(ui, "{}", i18n::t!("bills-enter-date-format"));
+                    }
+                }
+                "5" => {
+                    while let Some(input) = ui.prompt(
+                        &i18n::t!("bills-prompt-reminder-days"),
+                        &i18n::t!("bills-help-reminder-days"),
+                    )? {
+                        match input.parse() {
+                            Ok(days) => {
+                                config.reminder_days = days;
+                                break;
+                            }
+                            Err(_) => writeln!(ui, "{}", i18n::t!("bills-enter-days")),
+                        }
+                    }
+                }
+                "6" => {
+                    while let Some(input) = ui.prompt(
+                        &i18n::t!("bills-prompt-rounding"),
+                        &i18n::t!("bills-help-rounding"),
+                    )? {
+                        match input.parse() {
+                            Ok(rounding) => {
+                                config.rounding = rounding;
+                                break;
+                            }
+                            Err(e) => writeln!(ui, "{}", e),
+                        }
+                    }
+                }
+                "7" => {
+                    while let Some(input) = ui.prompt(
+                        &i18n::t!("bills-prompt-language"),
+                        &i18n::t!("bills-help-language"),
+                    )? {
+                        if input == "-" {
+                            config.locale = None;
+                            break;
+                        }
+                        match input.parse() {
+                            Ok(locale) => {
+                                config.locale = Some(locale);
+                                break;
+                            }
+                            Err(e) => writeln!(ui, "{}", e),
+                        }
+                    }
+                }
+                "8" => {
+                    if let Some(input) = ui.prompt(
+                        &i18n::t!("bills-prompt-tax-categories"),
+                        &i18n::t!("bills-help-tax-categories"),
+                    )? {
+                        config.tax_categories = input
+                            .split(',')
+                            .map(|category| category.trim().to_string())
+                            .filter(|category| !category.is_empty() && category != "-")
+                            .collect();
+                    }
+                }
+                "9" => {
+                    while let Some(input) = ui.prompt(
+                        &i18n::t!("bills-prompt-theme"),
+                        &i18n::t!("bills-help-theme"),
+                    )? {
+                        match input.parse() {
+                            Ok(theme) => {
+                                config.theme = theme;
+                                break;
+                            }
+                            Err(e) => writeln!(ui, "{}", e),
+                        }
+                    }
+                }
+                _ => writeln!(ui, "{}", i18n::t!("bills-enter-setting")),
+            }
+        }
+    }
+    fn split_bill_menu(ui: &mut dyn UserInterface, bills: &mut Bills) -> MenuResult {
+        let id = match get_bill_id(ui, bills, &i18n::t!("bills-prompt-split"))? {
+            Some(input) => input,
+            None => return Ok(()),
+        };
+        if !bills.list.contains_key(&id) {
+            writeln!(ui, "{}", i18n::t!("bills-not-found"));
+            return Ok(());
+        }
+
+        let payer = match ui.prompt(
+            &i18n::t!("bills-prompt-payer"),
+            &i18n::t!("bills-help-payer"),
+        )? {
+            Some(input) if bill_core::is_valid_name(&input) => input,
+            Some(_) => {
+                writeln!(ui, "{}", i18n::t!("bills-split-name-chars"));
+                return Ok(());
+            }
+            None => {
+                if bills.list.get(&id).is_some_and(|bill| bill.split.is_some()) {
+                    bills.set_split(id, None);
+                    writeln!(ui, "{}", i18n::t!("bills-split-removed"));
+                }
+                return Ok(());
+            }
+        };
+        let people: Vec<String> = match ui.prompt(
+            &i18n::t!("bills-prompt-people"),
+            &i18n::t!("bills-help-people"),
+        )? {
+            Some(input) => input
+                .split(',')
+                .map(|person| person.trim().to_string())
+                .filter(|person| !person.is_empty())
+                .collect(),
+            None => return Ok(()),
+        };
+        if people.is_empty() || !people.iter().all(|person| bill_core::is_valid_name(person)) {
+            writeln!(ui, "{}", i18n::t!("bills-enter-split-names"));
+            return Ok(());
+        }
+
+        let mut split = Split::equal(&payer, &people);
+        let custom = ui
+            .prompt(
+                &i18n::t!("bills-prompt-shares"),
+                &i18n::t!("bills-help-shares"),
+            )?
+            .as_deref()
+            == Some("c");
+        if custom {
+            for share in &mut split.shares {
+                loop {
+                    let input = match ui.prompt(
+                        &i18n::t!("bills-prompt-percent", name = share.person),
+                        &i18n::t!("bills-help-percent"),
+                    )? {
+                        Some(input) => input,
+                        None => return Ok(()),
+                    };
+                    match input.trim_end_matches('%').parse::<f64>() {
+                        Ok(percent) if (0.0..=100.0).contains(&percent) => {
+                            share.basis_points = bill_core::percent_to_basis_points(percent);
+                            break;
+                        }
+                        _ => writeln!(ui, "{}", i18n::t!("bills-enter-percent")),
+                    }
+                }
+            }
+            if !split.is_complete() {
+                writeln!(
+                    ui,
+                    "{}",
+                    i18n::t!("bills-shares-total", percent = split.total_percent())
+                );
+                return Ok(());
+            }
+        }
+
+        bills.set_split(id, Some(split));
+        writeln!(ui, "{}", i18n::t!("bills-split"));
+        Ok(())
+    }
+    fn settle_menu(
+        ui: &mut dyn UserInterface,
+        bills: &Bills,
+        archive: &Bills,
+        transfers: &mut Vec<Transfer>,
+    ) -> MenuResult {
+        let all = || bills.list.values().chain(archive.list.values());
+        for (code, balance) in split::balances(all(), transfers) {
+            writeln!(ui, "{}", i18n::t!("bills-balances", currency = code));
+            for (person, amount) in balance {
+                let sign = if amount > Money::ZERO {
+                    "+"
+                } else if amount.is_negative() {
+                    "-"
+                } else {
+                    ""
+                };
+                writeln!(
+                    ui,
+                    "  {}: {}{}",
+                    person,
+                    sign,
+                    currency::format_amount(amount.abs(), &code)
+                );
+            }
+        }
+
+        let settlement = split::settle(all(), transfers);
+        if settlement.values().all(|debts| debts.is_empty()) {
+            writeln!(ui, "{}", i18n::t!("bills-settled"));
+            return Ok(());
+        }
+        for (code, debts) in &settlement {
+            for debt in debts {
+                writeln!(
+                    ui,
+                    "{}",
+                    i18n::t!(
+                        "bills-owes",
+                        from = debt.from,
+                        to = debt.to,
+                        amount = currency::format_amount(debt.amount, code)
+                    )
+                );
+            }
+        }
+        if !confirm(ui, &i18n::t!("bills-confirm-transfers"))? {
+            return Ok(());
+        }
+
+        let today = Local::now().date_naive();
+        for (code, debts) in settlement {
+            for debt in debts {
+                transfers.push(Transfer {
+                    date: today,
+                    from: debt.from,
+                    to: debt.to,
+                    amount: debt.amount,
+                    currency: code.clone(),
+                });
+            }
+        }
+        writeln!(ui, "{}", i18n::t!("bills-transfers-recorded"));
+        Ok(())
+    }
+    fn category_bill_menu(
+        ui: &mut dyn UserInterface,
+        bills: &Bills,
+        rates: &Rates,
+        base: &str,
+    ) -> MenuResult {
+        let total = converted_total(&bills.view_bill(), rates, base);
+        for (category, list) in bills.view_by_category() {
+            writeln!(ui);
+            writeln!(
+                ui,
+                "{}",
+                category_heading(category, &list, total, rates, base)
+            );
+            for bill in list {
+                writeln!(ui, "{}", bill);
+            }
+        }
+        Ok(())
+    }
+    fn method_report_menu(
+        ui: &mut dyn UserInterface,
+        bills: &Bills,
+        rates: &Rates,
+        base: &str,
+    ) -> MenuResult {
+        let mut methods: Vec<Option<Method>> = Method::ALL.into_iter().map(Some).collect();
+        methods.push(None);
+        for method in methods {
+            let list: Vec<&Bill> = bills
+                .view_bill()
+                .into_iter()
+                .filter(|bill| bill.method == method)
+                .collect();
+            if list.is_empty() {
+                continue;
+            }
+            let totals = totals_by_currency(
+                list.iter()
+                    .map(|bill| (bill.currency.as_str(), bill.amount)),
+            );
+            let label = match method {
+                Some(method) => method.to_string(),
+                None => i18n::t!("bills-method-not-set"),
+            };
+            writeln!(
+                ui,
+                "{} ({}): {}",
+                label,
+                list.len(),
+                currency::format_totals(&totals)
+            );
+            print_converted_total(ui, &totals, rates, base);
+        }
+        Ok(())
+    }
+    fn pay_bill_menu(ui: &mut dyn UserInterface, bills: &mut Bills) -> MenuResult {
+        for bill in bills.view_by_due() {
+            if Filter::Unpaid.matches(bill) {
+                writeln!(ui, "{}", bill);
+            }
+        }
+
+        let id = match get_bill_id(ui, bills, &i18n::t!("bills-prompt-pay"))? {
+            Some(input) => input,
+            None => return Ok(()),
+        };
+
+        if bills.list.get(&id).is_some_and(|bill| bill.paid.is_some()) {
+            writeln!(ui, "{}", i18n::t!("bills-already-paid"));
+            return Ok(());
+        }
+        if !bills.pay_bill(id, Local::now().date_naive()) {
+            writeln!(ui, "{}", i18n::t!("bills-not-found"));
+            return Ok(());
+        }
+        match bills.list.get(&id) {
+            Some(next) if next.paid.is_none() => {
+                writeln!(ui, "{}", i18n::t!("bills-paid-next", next = next))
+            }
+            _ => writeln!(ui, "{}", i18n::t!("bills-paid")),
+        }
+        Ok(())
+    }
+    fn payment_menu(
+        ui: &mut dyn UserInterface,
+        bills: &mut Bills,
+        max_amount: Money,
+    ) -> MenuResult {
+        for bill in bills.view_by_due() {
+            if Filter::Unpaid.matches(bill) {
+                writeln!(ui, "{}", bill);
+            }
+        }
+
+        let id = match get_bill_id(ui, bills, &i18n::t!("bills-prompt-payment"))? {
+            Some(input) => input,
+            None => return Ok(()),
+        };
+        match bills.list.get(&id) {
+            Some(bill) if bill.paid.is_some() => {
+                writeln!(ui, "{}", i18n::t!("bills-already-paid"));
+                return Ok(());
+            }
+            Some(bill) => writeln!(
+                ui,
+                "{}",
+                i18n::t!(
+                    "bills-remaining",
+                    amount = currency::format_amount(bill.remaining(), &bill.currency)
+                )
+            ),
+            None => {
+                writeln!(ui, "{}", i18n::t!("bills-not-found"));
+                return Ok(());
+            }
+        }
+
+        let amount = match get_bill_amount(ui, &i18n::t!("bills-prompt-amount"), max_amount)? {
+            Some(amount) => amount,
+            None => return Ok(()),
+        };
+        let date = get_date(
+            ui,
+            &i18n::t!(
+                "bills-prompt-payment-date",
+                example = config::date_example()
+            ),
+            &i18n::t!("bills-help-payment-date"),
+        )?
+        .unwrap_or_else(|| Local::now().date_naive());
+
+        // A recurring bill that gets fully paid rolls over to its next
+        // instance under the same id.
+        let settles = bills
+            .list
+            .get(&id)
+            .is_some_and(|bill| amount >= bill.remaining());
+        bills.record_payment(id, amount, date);
+        match bills.list.get(&id) {
+            Some(next) if settles && next.paid.is_none() => writeln!(
+                ui,
+                "{}",
+                i18n::t!("bills-payment-settled-next", next = next)
+            ),
+            Some(bill) if bill.paid.is_none() => writeln!(
+                ui,
+                "{}",
+                i18n::t!(
+                    "bills-payment-remaining",
+                    amount = currency::format_amount(bill.remaining(), &bill.currency)
+                )
+            ),
+            _ => writeln!(ui, "{}", i18n::t!("bills-payment-settled")),
+        }
+        Ok(())
+    }
+    fn payment_history_menu(ui: &mut dyn UserInterface, bills: &Bills) -> MenuResult {
+        let id = match get_bill_id(ui, bills, &i18n::t!("bills-prompt-name"))? {
+            Some(input) => input,
+            None => return Ok(()),
+        };
+        let bill = match bills.list.get(&id) {
+            Some(bill) => bill,
+            None => {
+                writeln!(ui, "{}", i18n::t!("bills-not-found"));
+                return Ok(());
+            }
+        };
+
+        writeln!(ui, "{}", bill);
+        if bill.payments.is_empty() {
+            writeln!(ui, "{}", i18n::t!("bills-no-payments"));
+        }
+        for payment in &bill.payments {
+            writeln!(
+                ui,
+                "  {}  {}",
+                config::format_date(payment.date),
+                currency::format_amount(payment.amount, &bill.currency)
+            );
+        }
+        writeln!(
+            ui,
+            "{}",
+            i18n::t!(
+                "bills-paid-of",
+                paid = currency::format_amount(bill.paid_amount(), &bill.currency),
+                amount = currency::format_amount(bill.amount, &bill.currency),
+                remaining = currency::format_amount(bill.remaining(), &bill.currency)
+            )
+        );
+        Ok(())
+    }
+    fn set_budget_menu(
+        ui: &mut dyn UserInterface,
+        bills: &Bills,
+        budgets: &mut Budgets,
+        base: &str,
+        max_amount: Money,
+    ) -> MenuResult {
+        let category = match get_category(ui, &bills.categories(), None)? {
+            Some(category) => category,
+            None => return Ok(()),
+        };
+
+        let text = i18n::t!("bills-prompt-budget", currency = base);
+        match get_bill_amount(ui, &text, max_amount)? {
+            Some(limit) => {
+                budgets.set(&category, limit);
+                writeln!(ui, "{}", i18n::t!("bills-budget-saved"));
+            }
+            None => {
+                if budgets.remove(&category) {
+                    writeln!(ui, "{}", i18n::t!("bills-budget-removed"));
+                }
+            }
+        }
+        Ok(())
+    }
+    fn budget_status_menu(
+        ui: &mut dyn UserInterface,
+        bills: &Bills,
+        budgets: &Budgets,
+        rates: &Rates,
+        base: &str,
+    ) -> MenuResult {
+        let today = Local::now().date_naive();
+        let status = budgets.status(bills.list.values(), today, rates, base);
+        if status.is_empty() {
+            writeln!(ui, "{}", i18n::t!("bills-no-budgets"));
+        }
+
+        writeln!(
+            ui,
+            "{}",
+            i18n::t!("bills-budgets-for", month = today.format("%Y-%m"))
+        );
+        for budget in status {
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!(
+                    "bills-budget-line",
+                    category = budget.category,
+                    spent = currency::format_amount(budget.spent, base),
+                    limit = currency::format_amount(budget.limit, base),
+                    percent = format!("{:.0}", budget.percent_used()),
+                    over = if budget.is_over() {
+                        Style::Warning.paint(&format!(" {}", i18n::t!("bills-over-budget-marker")))
+                    } else {
+                        String::new()
+                    }
+                )
+            );
+        }
+        Ok(())
+    }
+    fn report_menu(
+        ui: &mut dyn UserInterface,
+        bills: &Bills,
+        rates: &Rates,
+        base: &str,
+    ) -> MenuResult {
+        let reports =
+            report::monthly_reports(bills.list.values(), Local::now().date_naive(), rates, base);
+        if reports.is_empty() {
+            writeln!(ui, "{}", i18n::t!("bills-nothing-to-report"));
+            return Ok(());
+        }
+
+        let months: Vec<String> = reports
+            .keys()
+            .map(|(year, month)| format!("{}-{:02}", year, month))
+            .collect();
+        writeln!(
+            ui,
+            "{}",
+            i18n::t!("bills-report-months", months = months.join(", "))
+        );
+        let selected = ui.prompt(
+            &i18n::t!("bills-prompt-month"),
+            &i18n::t!("bills-help-month"),
+        )?;
+
+        for ((year, month), report) in &reports {
+            let label = format!("{}-{:02}", year, month);
+            if selected.as_ref().is_some_and(|selected| *selected != label) {
+                continue;
+            }
+
+            writeln!(ui);
+            writeln!(ui, "== {} ==", label);
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!(
+                    "bills-report-billed",
+                    amount = currency::format_amount(report.billed, base)
+                )
+            );
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!(
+                    "bills-report-paid",
+                    amount = currency::format_amount(report.paid, base)
+                )
+            );
+            match reports.get(&report::previous_month((*year, *month))) {
+                Some(previous) => {
+                    let change = report.billed - previous.billed;
+                    if previous.billed > Money::ZERO {
+                        writeln!(
+                            ui,
+                            "{}",
+                            i18n::t!(
+                                "bills-report-change",
+                                sign = if change.is_negative() { "-" } else { "+" },
+                                amount = currency::format_amount(change.abs(), base),
+                                percent = format!("{:+.1}", change.ratio(previous.billed) * 100.0)
+                            )
+                        );
+                    } else {
+                        writeln!(
+                            ui,
+                            "{}",
+                            i18n::t!(
+                                "bills-report-change-new",
+                                amount = currency::format_amount(change, base)
+                            )
+                        );
+                    }
+                }
+                None => writeln!(ui, "{}", i18n::t!("bills-report-change-none")),
+            }
+            for (category, amount) in &report.by_category {
+                writeln!(
+                    ui,
+                    "  {}: {}",
+                    category,
+                    currency::format_amount(*amount, base)
+                );
+            }
+            if report.unconverted > 0 {
+                writeln!(
+                    ui,
+                    "  {}",
+                    i18n::t!(
+                        "bills-report-unconverted",
+                        count = report.unconverted,
+                        base = base
+                    )
+                );
+            }
+        }
+        Ok(())
+    }
+    fn html_report_menu(
+        ui: &mut dyn UserInterface,
+        vfs: &dyn Vfs,
+        bills: &Bills,
+        rates: &Rates,
+        base: &str,
+    ) -> MenuResult {
+        let today = Local::now().date_naive();
+        let reports = report::monthly_reports(bills.list.values(), today, rates, base);
+        if reports.is_empty() {
+            writeln!(ui, "{}", i18n::t!("bills-nothing-to-report"));
+            return Ok(());
+        }
+
+        let file_name = match ui.prompt(
+            &i18n::t!("bills-prompt-report-file"),
+            &i18n::t!("bills-help-report-file"),
+        )? {
+            Some(input) => PathBuf::from(input),
+            None => return Ok(()),
+        };
+        let html = html::render_report(&reports, base, today);
+        match vfs.write(&file_name, html.as_bytes()) {
+            Ok(()) => writeln!(ui, "{}", i18n::t!("bills-report-exported")),
+            Err(e) => writeln!(ui, "{}", i18n::t!("bills-report-export-failed", error = e)),
+        }
+        Ok(())
+    }
+    fn reconcile_menu(ui: &mut dyn UserInterface, vfs: &dyn Vfs, bills: &mut Bills) -> MenuResult {
+        let file_name = match ui.prompt(
+            &i18n::t!("bills-prompt-statement"),
+            &i18n::t!("bills-help-statement"),
+        )? {
+            Some(input) => PathBuf::from(input),
+            None => return Ok(()),
+        };
+        let statement = match vfs
+            .read_to_string(&file_name)
+            .and_then(|statement| reconcile::parse_statement(&statement))
+        {
+            Ok(statement) => statement,
+            Err(e) => {
+                writeln!(ui, "{}", i18n::t!("bills-statement-read-failed", error = e));
+                return Ok(());
+            }
+        };
+        if !statement.skipped.is_empty() {
+            let lines: Vec<String> = statement
+                .skipped
+                .iter()
+                .map(|num| num.to_string())
+                .collect();
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!("bills-statement-skipped", lines = lines.join(", "))
+            );
+        }
+
+        let (matched, unmatched) =
+            reconcile::match_transactions(&bills.view_by_due(), statement.transactions);
+        for (id, transaction) in &matched {
+            let name = bills.list.get(id).map_or("", |bill| bill.name.as_str());
+            writeln!(
+                ui,
+                "#{} {} <- {} {} {}",
+                id,
+                name,
+                config::format_date(transaction.date),
+                transaction.description,
+                transaction.amount
+            );
+        }
+        if !unmatched.is_empty() {
+            writeln!(ui, "{}", i18n::t!("bills-unmatched"));
+            for transaction in &unmatched {
+                writeln!(
+                    ui,
+                    "  {} {} {}",
+                    config::format_date(transaction.date),
+                    transaction.description,
+                    transaction.amount
+                );
+            }
+        }
+        if matched.is_empty() {
+            writeln!(ui, "{}", i18n::t!("bills-no-matches"));
+            return Ok(());
+        }
+        if !confirm(
+            ui,
+            &i18n::t!("bills-confirm-reconcile", count = matched.len()),
+        )? {
+            return Ok(());
+        }
+
+        for (id, transaction) in matched {
+            bills.record_payment(id, transaction.amount, transaction.date);
+        }
+        writeln!(ui, "{}", i18n::t!("bills-reconciled"));
+        Ok(())
+    }
+    fn calendar_menu(ui: &mut dyn UserInterface, bills: &Bills) -> MenuResult {
+        let mut month = report::month_of(Local::now().date_naive());
+        loop {
+            let mut by_day: BTreeMap<u32, Vec<&Bill>> = BTreeMap::new();
+            for bill in bills.view_by_due() {
+                if let Some(due) = bill.due.filter(|due| report::month_of(*due) == month) {
+                    by_day.entry(due.day()).or_default().push(bill);
+                }
+            }
+            let days: Vec<u32> = by_day.keys().copied().collect();
+
+            writeln!(ui);
+            writeln!(ui, "== {} ==", calendar::title(month));
+            for line in calendar::grid(month, &days) {
+                writeln!(ui, "{}", line);
+            }
+            writeln!(ui);
+            if by_day.is_empty() {
+                writeln!(ui, "{}", i18n::t!("bills-none-due-this-month"));
+            }
+            for (day, list) in by_day {
+                let totals = totals_by_currency(
+                    list.iter()
+                        .map(|bill| (bill.currency.as_str(), bill.amount)),
+                );
+                let names: Vec<&str> = list.iter().map(|bill| bill.name.as_str()).collect();
+                writeln!(
+                    ui,
+                    "{:>2}: {} ({})",
+                    day,
+                    currency::format_totals(&totals),
+                    names.join(", ")
+                );
+            }
+
+            match ui
+                .prompt(
+                    &i18n::t!("bills-prompt-calendar"),
+                    &i18n::t!("bills-help-calendar"),
+                )?
+                .as_deref()
+            {
+                Some("n") => month = report::next_month(month),
+                Some("p") => month = report::previous_month(month),
+                Some(_) => writeln!(ui, "{}", i18n::t!("bills-enter-next-or-previous")),
+                None => return Ok(()),
+            }
+        }
+    }
+    fn chart_menu(
+        ui: &mut dyn UserInterface,
+        bills: &Bills,
+        rates: &Rates,
+        base: &str,
+    ) -> MenuResult {
+        let by_month = ui
+            .prompt(
+                &i18n::t!("bills-prompt-chart"),
+                &i18n::t!("bills-help-chart"),
+            )?
+            .as_deref()
+            == Some("m");
+
+        let reports =
+            report::monthly_reports(bills.list.values(), Local::now().date_naive(), rates, base);
+        let mut totals: BTreeMap<String, Money> = BTreeMap::new();
+        for ((year, month), report) in &reports {
+            if by_month {
+                totals.insert(format!("{}-{:02}", year, month), report.billed);
+            } else {
+                for (category, amount) in &report.by_category {
+                    *totals.entry(category.clone()).or_default() += *amount;
+                }
+            }
+        }
+        if totals.is_empty() {
+            writeln!(ui, "{}", i18n::t!("bills-nothing-to-chart"));
+            return Ok(());
+        }
+
+        let rows: Vec<(String, f64, String)> = totals
+            .into_iter()
+            .map(|(label, amount)| {
+                let shown = currency::format_amount(amount, base);
+                (label, amount.cents() as f64, shown)
+            })
+            .collect();
+        for line in render::bar_chart(&rows, render::terminal_width()) {
+            writeln!(ui, "{}", line);
+        }
+        Ok(())
+    }
+    fn tax_menu(
+        ui: &mut dyn UserInterface,
+        vfs: &dyn Vfs,
+        bills: &Bills,
+        archive: &Bills,
+        archive_file: &Path,
+        rates: &Rates,
+        config: &Config,
+    ) -> MenuResult {
+        let base = config.currency.as_str();
+        let tax_categories = config.tax_categories.as_slice();
+        let this_year = Local::now().year();
+        let year = loop {
+            let input = match ui.prompt(
+                &i18n::t!("bills-prompt-year", default = this_year),
+                &i18n::t!("bills-help-tax-year"),
+            )? {
+                Some(input) => input,
+                None => break this_year,
+            };
+            match input.parse() {
+                Ok(year) => break year,
+                Err(_) => writeln!(ui, "{}", i18n::t!("bills-enter-year", example = this_year)),
+            }
+        };
+
+        // Bills are rotated by the year they were paid, earlier installments may fall in the year before.
+        let mut rotated = Bills::new();
+        for file_year in [year, year + 1] {
+            let file_name = rotation::year_file(archive_file, file_year);
+            match rotation::load_year(vfs, &file_name, base) {
+                Ok(loaded) => {
+                    for bill in loaded.list.into_values() {
+                        let id = rotated.next_id();
+                        rotated.add_bill(Bill { id, ..bill });
+                    }
+                }
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => writeln!(
+                    ui,
+                    "{}",
+                    i18n::t!("bills-read-failed", file = file_name.display(), error = e)
+                ),
+            }
+        }
+        let all = || {
+            bills
+                .list
+                .values()
+                .chain(archive.list.values())
+                .chain(rotated.list.values())
+        };
+        let summary = tax::year_summary(all(), year, rates, base);
+        writeln!(ui, "== {} ==", year);
+        if summary.by_category.is_empty() {
+            writeln!(ui, "{}", i18n::t!("bills-no-payments-in", year = year));
+        }
+        let mut deductible = Money::ZERO;
+        for (category, amount) in &summary.by_category {
+            if tax::is_tax_category(category, tax_categories) {
+                deductible += *amount;
+                writeln!(
+                    ui,
+                    "{}",
+                    i18n::t!(
+                        "bills-tax-category",
+                        category = category,
+                        amount = currency::format_amount(*amount, base)
+                    )
+                );
+            } else {
+                writeln!(
+                    ui,
+                    "{}: {}",
+                    category,
+                    currency::format_amount(*amount, base)
+                );
+            }
+        }
+        writeln!(
+            ui,
+            "{}",
+            i18n::t!(
+                "bills-total-paid",
+                amount = currency::format_amount(summary.total, base)
+            )
+        );
+        writeln!(
+            ui,
+            "{}",
+            i18n::t!(
+                "bills-tax-relevant",
+                amount = currency::format_amount(deductible, base)
+            )
+        );
+        if summary.unconverted > 0 {
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!(
+                    "bills-tax-unconverted",
+                    count = summary.unconverted,
+                    base = base
+                )
+            );
+        }
+
+        if tax_categories.is_empty() {
+            writeln!(ui, "{}", i18n::t!("bills-no-tax-categories"));
+            return Ok(());
+        }
+        let rows = tax::tax_rows(all(), year, tax_categories);
+        if rows.is_empty() {
+            return Ok(());
+        }
+        let file_name = match ui.prompt(
+            &i18n::t!("bills-prompt-tax-file"),
+            &i18n::t!("bills-help-csv-file"),
+        )? {
+            Some(input) => PathBuf::from(input),
+            None => return Ok(()),
+        };
+        match tax::save_tax_rows(vfs, &file_name, &rows) {
+            Ok(()) => writeln!(
+                ui,
+                "{}",
+                i18n::t!("bills-payments-exported", count = rows.len())
+            ),
+            Err(e) => writeln!(
+                ui,
+                "{}",
+                i18n::t!("bills-payments-export-failed", error = e)
+            ),
+        }
+        Ok(())
+    }
+    fn suggest_recurring_menu(
+        ui: &mut dyn UserInterface,
+        bills: &mut Bills,
+        archive: &Bills,
+    ) -> MenuResult {
+        let suggestions = infer::suggest(bills.list.values(), archive.list.values());
+        if suggestions.is_empty() {
+            writeln!(ui, "{}", i18n::t!("bills-no-repeating"));
+            return Ok(());
+        }
+
+        for suggestion in suggestions {
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!(
+                    "bills-suggestion",
+                    id = suggestion.id,
+                    name = suggestion.name,
+                    amount = currency::format_amount(suggestion.amount, &suggestion.currency),
+                    count = suggestion.occurrences,
+                    recurrence = suggestion.recurrence
+                )
+            );
+            match ui
+                .prompt(
+                    &i18n::t!("bills-prompt-mark-recurring"),
+                    &i18n::t!("bills-help-mark-recurring"),
+                )?
+                .as_deref()
+            {
+                Some("y") => {
+                    bills.set_recurrence(suggestion.id, suggestion.recurrence);
+                    writeln!(
+                        ui,
+                        "{}",
+                        i18n::t!(
+                            "bills-repeats",
+                            name = suggestion.name,
+                            recurrence = suggestion.recurrence
+                        )
+                    );
+                }
+                Some("q") => break,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+    fn export_bill_menu(ui: &mut dyn UserInterface, bills: &Bills) -> MenuResult {
+        let file_name = match ui.prompt(
+            &i18n::t!("bills-prompt-export-file"),
+            &i18n::t!("bills-help-csv-file"),
+        )? {
+            Some(input) => PathBuf::from(input),
+            None => return Ok(()),
+        };
+
+        match export_bills(&file_name, bills) {
+            Ok(()) => writeln!(ui, "{}", i18n::t!("bills-exported-to-file")),
+            Err(e) => writeln!(ui, "{}", i18n::t!("bills-export-failed", error = e)),
+        }
+        Ok(())
+    }
+    fn import_bill_menu(
+        ui: &mut dyn UserInterface,
+        bills: &mut Bills,
+        default_currency: &str,
+    ) -> MenuResult {
+        let file_name = match ui.prompt(
+            &i18n::t!("bills-prompt-import-file"),
+            &i18n::t!("bills-help-import-file"),
+        )? {
+            Some(input) => PathBuf::from(input),
+            None => return Ok(()),
+        };
+
+        match import_bills(&file_name, bills, default_currency) {
+            Ok(count) => writeln!(ui, "{}", i18n::t!("bills-imported", count = count)),
+            Err(e) => writeln!(ui, "{}", i18n::t!("bills-import-failed", error = e)),
+        }
+        Ok(())
+    }
+}
+
+fn converted_total(list: &[&Bill], rates: &Rates, base: &str) -> Money {
+    list.iter()
+        .filter_map(|bill| rates.convert(bill.amount, &bill.currency, base))
+        .sum()
+}
+fn category_heading(
+    category: &str,
+    list: &[&Bill],
+    total: Money,
+    rates: &Rates,
+    base: &str,
+) -> String {
+    let subtotal = totals_by_currency(
+        list.iter()
+            .map(|bill| (bill.currency.as_str(), bill.amount)),
+    );
+    let heading = i18n::t!(
+        "bills-bucket-heading",
+        bucket = category,
+        subtotal = currency::format_totals(&subtotal)
+    );
+    if total > Money::ZERO {
+        let share = converted_total(list, rates, base).ratio(total);
+        format!("{} {}", heading, render::share_bar(share))
+    } else {
+        heading
+    }
+}
+fn bill_line(bill: &Bill, fees: LateFees, today: NaiveDate) -> String {
+    let late = fees.accrued(bill, today);
+    if late > Money::ZERO {
+        i18n::t!(
+            "bills-line-late",
+            bill = bill,
+            late = currency::format_amount(late, &bill.currency)
+        )
+    } else {
+        bill.to_string()
+    }
+}
+fn get_bill_id(ui: &mut dyn UserInterface, bills: &Bills, text: &str) -> Result<Option<i64>, Back> {
+    let mut names: Vec<&str> = bills.list.values().map(|bill| bill.name.as_str()).collect();
+    names.sort();
+    names.dedup();
+    let help = &i18n::t!("bills-help-bill");
+    loop {
+        let input = match ui.prompt_completing(text, help, &names)? {
+            Some(input) => input,
+            None => return Ok(None),
+        };
+        let id = input.strip_prefix('#').unwrap_or(&input).parse().ok();
+        if let Some(id) = id.filter(|id| bills.list.contains_key(id)) {
+            return Ok(Some(id));
+        }
+
+        let name = if names.contains(&input.as_str()) {
+            input
+        } else {
+            let matches = match complete::by_normalized(&input, &names).as_slice() {
+                [] => complete::by_prefix(&input, &names),
+                matches => matches.to_vec(),
+            };
+            match matches.as_slice() {
+                [name] => {
+                    writeln!(ui, "{}", i18n::t!("bills-using", name = name));
+                    name.to_string()
+                }
+                [] => {
+                    let closest = complete::closest(&input, &names);
+                    if closest.is_empty() {
+                        writeln!(ui, "{}", i18n::t!("bills-not-found"));
+                        return Ok(None);
+                    }
+                    let mut accepted = None;
+                    for suggestion in closest {
+                        if confirm(ui, &i18n::t!("bills-did-you-mean", name = suggestion))? {
+                            accepted = Some(suggestion);
+                            break;
+                        }
+                    }
+                    match accepted {
+                        Some(name) => name.to_string(),
+                        None => continue,
+                    }
+                }
+                matches => {
+                    writeln!(
+                        ui,
+                        "{}",
+                        i18n::t!("bills-matching", names = matches.join(", "))
+                    );
+                    continue;
+                }
+            }
+        };
+
+        match bills.with_name(&name).as_slice() {
+            [bill] => return Ok(Some(bill.id)),
+            matches => {
+                writeln!(ui, "{}", i18n::t!("bills-enter-id", name = name));
+                for bill in matches {
+                    writeln!(ui, "  {}", bill);
+                }
+            }
+        }
+    }
+}
+fn get_bill_amount(
+    ui: &mut dyn UserInterface,
+    text: &str,
+    max: Money,
+) -> Result<Option<Money>, Back> {
+    let help = i18n::t!("bills-help-amount", max = max);
+    loop {
+        let input = match ui.prompt(text, &help)? {
+            Some(input) => input,
+            None => return Ok(None),
+        };
+
+        match expr::evaluate(&locale::current().parse_number(&input)) {
+            Ok(amount) if validate::non_negative(amount).is_err() => {
+                writeln!(ui, "{}", i18n::t!("bills-amount-negative"))
+            }
+            Ok(amount) if amount > max => {
+                writeln!(ui, "{}", i18n::t!("bills-amount-too-large", max = max))
+            }
+            Ok(amount) => {
+                if input.trim().parse::<Money>().is_err() {
+                    writeln!(ui, "= {}", amount);
+                }
+                return Ok(Some(amount));
+            }
+            Err(e) => writeln!(ui, "{}", i18n::t!("bills-enter-number", error = e)),
+        }
+    }
+}
+fn get_due_date(
+    ui: &mut dyn UserInterface,
+    current: Option<NaiveDate>,
+) -> Result<Option<NaiveDate>, Back> {
+    let text = match current {
+        Some(due) => i18n::t!("bills-prompt-due-current", due = config::format_date(due)),
+        None => i18n::t!("bills-prompt-due", example = config::date_example()),
+    };
+    loop {
+        let input = match ui.prompt(&text, &i18n::t!("bills-help-due"))? {
+            Some(input) if input == "-" => return Ok(None),
+            Some(input) => input,
+            None => return Ok(current),
+        };
+        if let Some(date) = parse_date(ui, &input) {
+            return Ok(Some(date));
+        }
+    }
+}
+fn get_date(ui: &mut dyn UserInterface, text: &str, help: &str) -> Result<Option<NaiveDate>, Back> {
+    loop {
+        let input = match ui.prompt(text, help)? {
+            Some(input) => input,
+            None => return Ok(None),
+        };
+        if let Some(date) = parse_date(ui, &input) {
+            return Ok(Some(date));
+        }
+    }
+}
+fn parse_date(ui: &mut dyn UserInterface, input: &str) -> Option<NaiveDate> {
+    if let Some(date) = config::parse_date(input) {
+        return Some(date);
+    }
+    let today = Local::now().date_naive();
+    match dates::parse(input, today, locale::current()) {
+        Some(date) => {
+            writeln!(ui, "= {}", config::format_date(date));
+            Some(date)
+        }
+        None => {
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!("bills-enter-date", example = config::date_example())
+            );
+            None
+        }
+    }
+}
+fn get_recurrence(
+    ui: &mut dyn UserInterface,
+    current: Option<Recurrence>,
+) -> Result<Option<Recurrence>, Back> {
+    let text = match current {
+        Some(recurrence) => i18n::t!("bills-prompt-recurrence-current", recurrence = recurrence),
+        None => i18n::t!("bills-prompt-recurrence"),
+    };
+    loop {
+        let input = match ui.prompt(&text, &i18n::t!("bills-help-recurrence"))? {
+            Some(input) if input == "-" => return Ok(None),
+            Some(input) => input,
+            None => return Ok(current),
+        };
+        match input.parse() {
+            Ok(recurrence) => return Ok(Some(recurrence)),
+            Err(e) => writeln!(ui, "{}", e),
+        }
+    }
+}
+fn get_category(
+    ui: &mut dyn UserInterface,
+    categories: &[&str],
+    default: Option<&str>,
+) -> Result<Option<String>, Back> {
+    for (num, category) in categories.iter().enumerate() {
+        writeln!(ui, "{}. {}", num + 1, category);
+    }
+
+    let text = match default {
+        Some(default) => i18n::t!("bills-prompt-category-default", default = default),
+        None => i18n::t!("bills-prompt-category"),
+    };
+    let input = match ui.prompt_completing(&text, &i18n::t!("bills-help-category"), categories)? {
+        Some(input) if input == "-" && default.is_some() => return Ok(None),
+        Some(input) => input,
+        None => return Ok(default.map(str::to_string)),
+    };
+    match input.parse::<usize>() {
+        Ok(num) if num >= 1 && num <= categories.len() => Ok(Some(categories[num - 1].to_string())),
+        _ => Ok(Some(input)),
+    }
+}
+fn get_rate(ui: &mut dyn UserInterface, code: &str, base: &str) -> Result<Option<f64>, Back> {
+    prompt::parsed(
+        ui,
+        &i18n::t!("bills-prompt-rate", code = code, base = base),
+        &i18n::t!("bills-help-rate", base = base, code = code),
+        |input| match input.parse::<f64>() {
+            Ok(rate) if rate > 0.0 => Ok(rate),
+            _ => Err(i18n::t!("bills-enter-positive")),
+        },
+    )
+}
+fn print_converted_total(
+    ui: &mut dyn UserInterface,
+    totals: &BTreeMap<String, Money>,
+    rates: &Rates,
+    base: &str,
+) {
+    if totals.keys().all(|code| code == base) {
+        return;
+    }
+    let (sum, missing) = rates.convert_totals(totals, base);
+    let converted = format!("  = {}", currency::format_amount(sum, base));
+    if missing.is_empty() {
+        writeln!(ui, "{}", converted);
+    } else {
+        writeln!(
+            ui,
+            "{}",
+            i18n::t!(
+                "bills-no-rate-for",
+                amount = converted,
+                currencies = missing.join(", ")
+            )
+        );
+    }
+}
+fn get_currency(ui: &mut dyn UserInterface, default_currency: &str) -> Result<String, Back> {
+    let code = prompt::parsed(
+        ui,
+        &i18n::t!("bills-prompt-currency", default = default_currency),
+        &i18n::t!("bills-help-currency"),
+        |input| validate::currency(input).map_err(|_| i18n::t!("bills-enter-currency")),
+    )?;
+    Ok(code.unwrap_or_else(|| default_currency.to_string()))
+}
+fn get_notes(ui: &mut dyn UserInterface, current: Option<String>) -> Result<Option<String>, Back> {
+    let text = match &current {
+        Some(notes) => i18n::t!("bills-prompt-notes-current", notes = notes),
+        None => i18n::t!("bills-prompt-notes"),
+    };
+    match ui.prompt(&text, &i18n::t!("bills-help-notes"))? {
+        Some(input) if input == "-" => Ok(None),
+        Some(input) => Ok(Some(input)),
+        None => Ok(current),
+    }
+}
+fn get_method(ui: &mut dyn UserInterface, current: Option<Method>) -> Result<Option<Method>, Back> {
+    let text = match current {
+        Some(method) => i18n::t!("bills-prompt-method-current", method = method),
+        None => i18n::t!("bills-prompt-method"),
+    };
+    loop {
+        let input = match ui.prompt(&text, &i18n::t!("bills-help-method"))? {
+            Some(input) if input == "-" => return Ok(None),
+            Some(input) => input,
+            None => return Ok(current),
+        };
+        match input.parse() {
+            Ok(method) => return Ok(Some(method)),
+            Err(e) => writeln!(ui, "{}", i18n::t!("bills-enter-method", error = e)),
+        }
+    }
+}
+fn get_priority(ui: &mut dyn UserInterface, current: Priority) -> Result<Priority, Back> {
+    let text = i18n::t!("bills-prompt-priority", current = current);
+    loop {
+        let input = match ui.prompt(&text, &i18n::t!("bills-help-priority"))? {
+            Some(input) => input,
+            None => return Ok(current),
+        };
+        match input.parse() {
+            Ok(priority) => return Ok(priority),
+            Err(e) => writeln!(ui, "{}", i18n::t!("bills-enter-priority", error = e)),
+        }
+    }
+}
+fn get_payee(
+    ui: &mut dyn UserInterface,
+    contacts: &Records,
+    current: Option<i64>,
+) -> Result<Option<i64>, Back> {
+    let text = match current {
+        Some(id) => i18n::t!("bills-prompt-payee-current", id = id),
+        None => i18n::t!("bills-prompt-payee"),
+    };
+    loop {
+        let input = match ui.prompt(&text, &i18n::t!("bills-help-payee"))? {
+            Some(input) if input == "-" => return Ok(None),
+            Some(input) => input,
+            None => return Ok(current),
+        };
+        if let Some(contact) = input.parse().ok().and_then(|id| contacts.get(id)) {
+            return Ok(Some(contact.id));
+        }
+
+        match contacts.search(&input).as_slice() {
+            [contact] => {
+                writeln!(ui, "{}", i18n::t!("bills-using", name = contact.name));
+                return Ok(Some(contact.id));
+            }
+            [] => writeln!(ui, "{}", i18n::t!("bills-contact-not-found")),
+            matches => {
+                let names: Vec<String> = matches
+                    .iter()
+                    .map(|contact| format!("#{} {}", contact.id, contact.name))
+                    .collect();
+                writeln!(
+                    ui,
+                    "{}",
+                    i18n::t!("bills-matching-contacts", names = names.join(", "))
+                );
+            }
+        }
+    }
+}
+fn warn_new_overruns(
+    ui: &mut dyn UserInterface,
+    before: &HashMap<i64, Bill>,
+    bills: &Bills,
+    budgets: &Budgets,
+    rates: &Rates,
+    base: &str,
+) {
+    let today = Local::now().date_naive();
+    let was_over: Vec<String> = budgets
+        .status(before.values(), today, rates, base)
+        .into_iter()
+        .filter(|budget| budget.is_over())
+        .map(|budget| budget.category)
+        .collect();
+
+    for budget in budgets.status(bills.list.values(), today, rates, base) {
+        if budget.is_over() && !was_over.contains(&budget.category) {
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!(
+                    "bills-over-budget",
+                    warning = Style::Warning.paint(&i18n::t!("bills-warning")),
+                    category = budget.category,
+                    spent = currency::format_amount(budget.spent, base),
+                    limit = currency::format_amount(budget.limit, base)
+                )
+            );
+        }
+    }
+}
+fn show_reminders(ui: &mut dyn UserInterface, bills: &Bills, days: u32) {
+    let today = Local::now().date_naive();
+    let mut overdue: Vec<&Bill> = bills
+        .view_by_due()
+        .into_iter()
+        .filter(|bill| bill.is_overdue(today))
+        .collect();
+    let mut due_soon: Vec<&Bill> = bills
+        .view_by_due()
+        .into_iter()
+        .filter(|bill| bill.is_due_within(today, days))
+        .collect();
+    overdue.sort_by_key(|bill| bill.priority);
+    due_soon.sort_by_key(|bill| bill.priority);
+    let reminder = |bill: &Bill| match bill.priority {
+        Priority::High => Style::Strong.paint(&format!("! {}", bill)),
+        _ => bill.to_string(),
+    };
+    if overdue.is_empty() && due_soon.is_empty() {
+        return;
+    }
+
+    writeln!(ui, "{}", Style::Strong.paint(&i18n::t!("bills-reminders")));
+    if !overdue.is_empty() {
+        writeln!(ui, "{}", i18n::t!("bills-overdue"));
+        for bill in overdue {
+            writeln!(ui, "  {}", reminder(bill));
+        }
+    }
+    if !due_soon.is_empty() {
+        writeln!(ui, "{}", i18n::t!("bills-due-within", days = days));
+        for bill in due_soon {
+            writeln!(ui, "  {}", reminder(bill));
+        }
+    }
+}
+fn save_archive_or_warn(
+    ui: &mut dyn UserInterface,
+    profile: &Profile,
+    bills: &Bills,
+    archive: &Bills,
+) {
+    save_or_warn(ui, &profile.data_file, bills);
+    if let Err(e) = save_bills(&profile.archive_file, archive) {
+        writeln!(ui, "{}", i18n::t!("bills-archive-save-failed", error = e));
+    }
+}
+fn log_changes(
+    ui: &mut dyn UserInterface,
+    vfs: &dyn Vfs,
+    log_file: &Path,
+    before: &HashMap<i64, Bill>,
+    bills: &Bills,
+) {
+    let changes = activity::changes(before, bills);
+    if let Err(e) = activity::append(vfs, log_file, Local::now().naive_local(), &changes) {
+        writeln!(ui, "{}", i18n::t!("bills-history-write-failed", error = e));
+    }
+}
+fn save_or_warn(ui: &mut dyn UserInterface, data_file: &Path, bills: &Bills) {
+    match save_bills(data_file, bills) {
+        Ok(()) => {
+            if let Err(e) = journal::journal(data_file).clear() {
+                writeln!(ui, "{}", i18n::t!("bills-journal-clear-failed", error = e));
+            }
+        }
+        Err(e) => writeln!(ui, "{}", i18n::t!("bills-save-failed", error = e)),
+    }
+}
+const HISTORY_LIMIT: usize = 20;
+
+/// The names of the bills `changes` touches, looking up removed ones in `bills`.
+fn changed_names(changes: &Changes<i64, Bill>, bills: &Bills) -> String {
+    changes
+        .iter()
+        .filter_map(|(id, bill)| bill.or_else(|| bills.list.get(id)))
+        .map(|bill| bill.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+const READ_ONLY_ACTIONS: &[&str] = &[
+    "2", "5", "6", "9", "10", "11", "15", "17", "18", "20", "22", "23", "24", "25", "28", "30",
+    "32", "34", "36", "38",
+];
+
+fn lock_profile(
+    ui: &mut dyn UserInterface,
+    profile: &Profile,
+    bills: &mut Bills,
+    default_currency: &str,
+) -> io::Result<Option<lock::Lock>> {
+    let lock = match lock::acquire(&profile.data_file)? {
+        Ok(lock) => lock,
+        Err(pid) => {
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!(
+                    "bills-opened-read-only",
+                    file = profile.data_file.display(),
+                    pid = pid
+                )
+            );
+            return Ok(None);
+        }
+    };
+    let count = journal::recover(&profile.data_file, bills, default_currency)?;
+    if count > 0 {
+        writeln!(ui, "{}", i18n::t!("bills-recovered", count = count));
+    }
+    Ok(Some(lock))
+}
+fn main_menu(
+    ui: &mut dyn UserInterface,
+    opt: &Opt,
+    layers: &Layers<Config>,
+    mut stored: Config,
+    mut profile: Profile,
+    mut bills: Bills,
+    mut lock: Option<lock::Lock>,
+) -> io::Result<()> {
+    let mut config = layers.resolve(&stored)?;
+    show_reminders(ui, &bills, config.reminder_days);
+    let vfs = Disk;
+    let mut rates = currency::load_rates(&vfs, &opt.rates_file)?;
+    let contacts = load_contacts(&opt.contacts_file)?;
+    let mut budgets = budget::load_budgets(&vfs, &profile.budgets_file)?;
+    let mut archive = load_bills(&profile.archive_file, &config.currency)?;
+    let mut transfers = split::load_transfers(&vfs, &profile.transfers_file)?;
+    let mut history = History::new(HISTORY_LIMIT);
+    let fees = LateFees {
+        fee: opt.late_fee,
+        annual_rate: opt.interest_rate,
+    };
+    loop {
+        Menu::show_menu(ui);
+
+        let input = match ui.prompt_key(
+            &i18n::t!("bills-selection"),
+            &i18n::t!("bills-selection-help"),
+        ) {
+            Ok(Some(input)) => menu_number(&input),
+            _ => break,
+        };
+        let is_action = input
+            .parse::<usize>()
+            .is_ok_and(|num| (1..=locale::MENU.len()).contains(&num));
+        if lock.is_none() && is_action && !READ_ONLY_ACTIONS.contains(&input.as_str()) {
+            writeln!(
+                ui,
+                "{}",
+                i18n::t!("bills-read-only-action", file = profile.data_file.display())
+            );
+            continue;
+        }
+
+        let before = bills.list.clone();
+        let outcome = match input.as_str() {
+            "1" => Menu::add_bill_menu(
+                ui,
+                &mut bills,
+                &contacts,
+                &config.currency,
+                config.default_category.as_deref(),
+                opt.max_amount,
+            ),
+            "2" => {
+                let outcome = Menu::view_bill_menu(
+                    ui,
+                    &bills,
+                    fees,
+                    &mut stored.view,
+                    &rates,
+                    &config.currency,
+                );
+                if stored.view != config.view {
+                    config.view = stored.view;
+                    if layers.path().is_some() {
+                        if let Err(e) = layers.save(&stored) {
+                            writeln!(ui, "{}", i18n::t!("bills-view-save-failed", error = e));
+                        }
+                    }
+                }
+                outcome
+            }
+            "3" => Menu::remove_bill_menu(ui, &mut bills),
+            "4" => Menu::update_bill_menu(ui, &mut bills, &contacts, opt.max_amount),
+            "5" => Menu::summary_bill_menu(ui, &bills, &rates, &config.currency, fees),
+            "6" => Menu::export_bill_menu(ui, &bills),
+            "7" => Menu::import_bill_menu(ui, &mut bills, &config.currency),
+            "8" => Menu::pay_bill_menu(ui, &mut bills),
+            "9" => Menu::category_bill_menu(ui, &bills, &rates, &config.currency),
+            "10" => {
+                let outcome = Menu::rates_menu(ui, &mut rates, &config.currency);
+                if let Err(e) = currency::save_rates(&vfs, &opt.rates_file, &rates) {
+                    writeln!(ui, "{}", i18n::t!("bills-rates-save-failed", error = e));
+                }
+                outcome
+            }
+            "11" => Menu::search_bill_menu(ui, &bills, fees),
+            "12" => {
+                if Menu::undo_menu(ui, &mut bills, &mut history).is_err() {
+                    writeln!(ui, "{}", i18n::t!("bills-back-to-menu"));
+                }
+                log_changes(ui, &vfs, &profile.log_file, &before, &bills);
+                save_or_warn(ui, &profile.data_file, &bills);
+                continue;
+            }
+            "39" => {
+                if Menu::redo_menu(ui, &mut bills, &mut history).is_err() {
+                    writeln!(ui, "{}", i18n::t!("bills-back-to-menu"));
+                }
+                log_changes(ui, &vfs, &profile.log_file, &before, &bills);
+                save_or_warn(ui, &profile.data_file, &bills);
+                continue;
+            }
+            "13" => Menu::rename_bill_menu(ui, &mut bills),
+            "14" => Menu::payment_menu(ui, &mut bills, opt.max_amount),
+            "15" => Menu::payment_history_menu(ui, &bills),
+            "16" => {
+                let outcome = Menu::set_budget_menu(
+                    ui,
+                    &bills,
+                    &mut budgets,
+                    &config.currency,
+                    opt.max_amount,
+                );
+                if let Err(e) = budget::save_budgets(&vfs, &profile.budgets_file, &budgets) {
+                    writeln!(ui, "{}", i18n::t!("bills-budgets-save-failed", error = e));
+                }
+                outcome
+            }
+            "17" => Menu::budget_status_menu(ui, &bills, &budgets, &rates, &config.currency),
+            "18" => Menu::report_menu(ui, &bills, &rates, &config.currency),
+            "19" => {
+                if Menu::archive_menu(ui, &mut bills, &mut archive).is_err() {
+                    writeln!(ui, "{}", i18n::t!("bills-back-to-menu"));
+                }
+                save_archive_or_warn(ui, &profile, &bills, &archive);
+                history.clear();
+                continue;
+            }
+            "20" => Menu::view_archive_menu(ui, &vfs, &archive, &profile.archive_file),
+            "22" => Menu::show_bill_menu(ui, &bills, &contacts, fees),
+            "23" => Menu::chart_menu(ui, &bills, &rates, &config.currency),
+            "21" => {
+                if Menu::unarchive_menu(ui, &mut bills, &mut archive).is_err() {
+                    writeln!(ui, "{}", i18n::t!("bills-back-to-menu"));
+                }
+                save_archive_or_warn(ui, &profile, &bills, &archive);
+                history.clear();
+                continue;
+            }
+            "24" => {
+                let name = match Menu::profile_menu(ui, opt, &config.data_file, &profile.name) {
+                    Ok(Some(name)) => name,
+                    Ok(None) => continue,
+                    Err(Back) => {
+                        writeln!(ui, "{}", i18n::t!("bills-back-to-menu"));
+                        continue;
+                    }
+                };
+                if lock.take().is_some() {
+                    save_archive_or_warn(ui, &profile, &bills, &archive);
+                }
+                profile = Profile::new(opt, &config.data_file, &name);
+                bills = load_bills(&profile.data_file, &config.currency)?;
+                lock = lock_profile(ui, &profile, &mut bills, &config.currency)?;
+                budgets = budget::load_budgets(&vfs, &profile.budgets_file)?;
+                archive = load_bills(&profile.archive_file, &config.currency)?;
+                transfers = split::load_transfers(&vfs, &profile.transfers_file)?;
+                history.clear();
+                writeln!(
+                    ui,
+                    "{}",
+                    i18n::t!("bills-switched-profile", name = profile.name)
+                );
+                continue;
+            }
+            "26" => Menu::split_bill_menu(ui, &mut bills),
+            "27" => {
+                let count = transfers.len();
+                let outcome = Menu::settle_menu(ui, &bills, &archive, &mut transfers);
+                if transfers.len() != count {
+                    if let Err(e) = split::save_transfers(&vfs, &profile.transfers_file, &transfers)
+                    {
+                        writeln!(ui, "{}", i18n::t!("bills-transfers-save-failed", error = e));
+                    }
+                }
+                outcome
+            }
+            "28" => Menu::html_report_menu(ui, &vfs, &bills, &rates, &config.currency),
+            "29" => Menu::reconcile_menu(ui, &vfs, &mut bills),
+            "30" => Menu::method_report_menu(ui, &bills, &rates, &config.currency),
+            "31" => Menu::attach_menu(ui, &mut bills, &profile.attachments_dir),
+            "32" => Menu::calendar_menu(ui, &bills),
+            "33" => Menu::postpone_menu(ui, &mut bills),
+            "34" => Menu::tax_menu(
+                ui,
+                &vfs,
+                &bills,
+                &archive,
+                &profile.archive_file,
+                &rates,
+                &config,
+            ),
+            "35" => Menu::suggest_recurring_menu(ui, &mut bills, &archive),
+            "38" => Menu::history_menu(ui, &vfs, &profile.log_file),
+            "37" => {
+                let outcome = Menu::rotate_menu(
+                    ui,
+                    &vfs,
+                    &mut archive,
+                    &profile.archive_file,
+                    &config.currency,
+                );
+                if let Err(e) = save_bills(&profile.archive_file, &archive) {
+                    writeln!(ui, "{}", i18n::t!("bills-archive-save-failed", error = e));
+                }
+                outcome
+            }
+            "36" => {
+                let json = json::bills_json(&bills, fees, Local::now().date_naive());
+                writeln!(ui, "{:#}", json);
+                Ok(())
+            }
+            "25" => {
+                let outcome = Menu::settings_menu(ui, &mut stored);
+                match layers.path() {
+                    Some(_) => {
+                        if let Err(e) = layers.save(&stored) {
+                            writeln!(ui, "{}", i18n::t!("bills-settings-save-failed", error = e));
+                        }
+                    }
+                    None => writeln!(ui, "{}", i18n::t!("bills-no-config-dir")),
+                }
+                let data_file = config.data_file.clone();
+                config = layers.resolve(&stored)?;
+                apply_settings(&config);
+                if config.data_file != data_file {
+                    if lock.take().is_some() {
+                        save_or_warn(ui, &profile.data_file, &bills);
+                    }
+                    profile = Profile::new(opt, &config.data_file, &profile.name);
+                    bills = load_bills(&profile.data_file, &config.currency)?;
+                    lock = lock_profile(ui, &profile, &mut bills, &config.currency)?;
+                    history.clear();
+                    writeln!(
+                        ui,
+                        "{}",
+                        i18n::t!("bills-using-data-file", file = profile.data_file.display())
+                    );
+                }
+                if outcome.is_err() {
+                    writeln!(ui, "{}", i18n::t!("bills-back-to-menu"));
+                }
+                continue;
+            }
+            _ => break,
+        };
+        if outcome.is_err() {
+            writeln!(ui, "{}", i18n::t!("bills-back-to-menu"));
+        }
+
+        warn_new_overruns(ui, &before, &bills, &budgets, &rates, &config.currency);
+        log_changes(ui, &vfs, &profile.log_file, &before, &bills);
+        let changes = Changes::between(&before, &bills.list);
+        if !changes.is_empty() {
+            // The journal is plaintext, so encrypted bills go without crash recovery.
+            if !config::encrypted() {
+                if let Err(e) = journal::append(&journal::journal(&profile.data_file), &changes) {
+                    writeln!(ui, "{}", i18n::t!("bills-journal-write-failed", error = e));
+                }
+            }
+            history.record(changes.invert(&before));
+            save_or_warn(ui, &profile.data_file, &bills);
+        }
+    }
+
+    if lock.is_none() {
+        return Ok(());
+    }
+    save_bills(&profile.data_file, &bills)?;
+    journal::journal(&profile.data_file).clear()
+}
+
+#[derive(Parser, Debug)]
+#[command(about = "Bill Manager", version, styles = Styles::styled())]
+#[command(group(
+    ArgGroup::new("screen")
+        .args(["tui", "interactive"])
+        .conflicts_with_all(["import", "export", "json"])
+))]
+struct Opt {
+    #[arg(
+        short,
+        long,
+        value_hint = ValueHint::FilePath,
+        help = "bills file, defaults to the data_file setting"
+    )]
+    data_file: Option<PathBuf>,
+    #[arg(
+        long,
+        value_hint = ValueHint::FilePath,
+        help = "config file, defaults to config.toml in the bill_manager config directory"
+    )]
+    config: Option<PathBuf>,
+    #[arg(
+        short,
+        long,
+        help = "log debug details; BILL_MANAGER_LOG overrides the level"
+    )]
+    verbose: bool,
+    #[arg(
+        long,
+        value_hint = ValueHint::FilePath,
+        help = "also append log output to this file"
+    )]
+    log_file: Option<PathBuf>,
+    #[arg(
+        short,
+        long,
+        requires = "json",
+        value_hint = ValueHint::FilePath,
+        help = "write the --json output to this file instead of stdout"
+    )]
+    output: Option<PathBuf>,
+    #[arg(
+        long,
+        default_value = profile::DEFAULT_PROFILE,
+        help = "profile whose bills, budgets and archive are used"
+    )]
+    profile: String,
+    #[arg(
+        long,
+        value_hint = ValueHint::FilePath,
+        help = "import bills from a CSV file and exit"
+    )]
+    import: Option<PathBuf>,
+    #[arg(
+        long,
+        value_hint = ValueHint::FilePath,
+        help = "export bills to a CSV file and exit"
+    )]
+    export: Option<PathBuf>,
+    #[arg(
+        long,
+        help = "currency code for new bills and rows without one, defaults to the currency setting"
+    )]
+    currency: Option<String>,
+    #[arg(
+        long,
+        help = "language for messages, dates and numbers (en or de), defaults to the locale setting"
+    )]
+    lang: Option<Locale>,
+    #[arg(long, value_hint = ValueHint::FilePath, default_value = "p1_rates.csv")]
+    rates_file: PathBuf,
+    #[arg(long, value_hint = ValueHint::FilePath, default_value = "p1_budgets.csv")]
+    budgets_file: PathBuf,
+    #[arg(long, value_hint = ValueHint::FilePath, default_value = "p1_archive.csv")]
+    archive_file: PathBuf,
+    #[arg(long, value_hint = ValueHint::FilePath, default_value = "p1_transfers.csv")]
+    transfers_file: PathBuf,
+    #[arg(
+        long,
+        value_hint = ValueHint::FilePath,
+        default_value = "p2_data.csv",
+        help = "contact manager data file used for payees"
+    )]
+    contacts_file: PathBuf,
+    #[arg(long, default_value = "0", help = "flat fee added to overdue bills")]
+    late_fee: Money,
+    #[arg(
+        long,
+        default_value = "0",
+        help = "yearly interest in percent on overdue balances"
+    )]
+    interest_rate: f64,
+    #[arg(
+        long,
+        default_value = "1000000",
+        help = "largest amount accepted when entering bills"
+    )]
+    max_amount: Money,
+    #[arg(long, help = "open the full-screen dashboard instead of the menu")]
+    tui: bool,
+    #[arg(
+        long,
+        help = "show the menu even when stdin is not a terminal instead of reading commands"
+    )]
+    interactive: bool,
+    #[arg(
+        long,
+        help = "print bills, payments and a summary as JSON and exit; amounts are decimal strings"
+    )]
+    json: bool,
+    #[arg(
+        long,
+        conflicts_with_all = ["screen", "import", "export", "json"],
+        help = "download and install the latest release, then exit"
+    )]
+    self_update: bool,
+    #[arg(
+        long,
+        requires = "self_update",
+        help = "with --self-update, only report whether a newer release exists"
+    )]
+    check: bool,
+    #[arg(
+        long,
+        value_name = "VERSION",
+        num_args = 0..=1,
+        conflicts_with_all = ["screen", "import", "export", "json", "self_update"],
+        help = "rewrite the bills and archive files in format VERSION, the latest if left out, then exit"
+    )]
+    migrate: Option<Option<u32>>,
+    #[arg(
+        long,
+        requires = "migrate",
+        help = "with --migrate, only list the migrations it would run"
+    )]
+    dry_run: bool,
+    #[arg(
+        trailing_var_arg = true,
+        allow_hyphen_values = true,
+        value_name = "PLUGIN",
+        conflicts_with_all = ["screen", "import", "export", "json", "self_update", "migrate"],
+        help = "run the bill_manager-PLUGIN program from PATH with the arguments after it"
+    )]
+    plugin: Vec<String>,
+}
+
+fn apply_settings(config: &Config) {
+    config::set_date_format(config.date_format());
+    config::set_storage(config.storage);
+    currency::set_rounding(config.rounding);
+    render::set_theme(config.theme);
+    locale::set_locale(config.locale());
+    i18n::set_language(config.locale.map(|locale| locale.to_string()).as_deref());
+}
+
+fn layers(opt: &Opt) -> Layers<Config> {
+    Layers::new()
+        .file(opt.config.clone())
+        .flag("currency", opt.currency.as_ref())
+        .flag("data_file", opt.data_file.as_ref())
+        .flag("locale", opt.lang)
+}
+
+#[cfg(feature = "self-update")]
+fn self_update(check: bool) -> io::Result<()> {
+    let status = update::self_update("bill_manager", env!("CARGO_PKG_VERSION"), check)?;
+    println!("{}", status);
+    Ok(())
+}
+
+#[cfg(not(feature = "self-update"))]
+fn self_update(_check: bool) -> io::Result<()> {
+    Err(errors::not_built_in("self-update"))
+}
+
+/// Asks for the passphrase of `data_file`, twice when it isn't encrypted yet.
+#[cfg(feature = "encryption")]
+fn ask_passphrase(data_file: &Path) -> io::Result<()> {
+    let confirm = !encryption::is_encrypted_file(data_file);
+    config::set_passphrase(Some(encryption::passphrase(Config::APP, confirm)?));
+    Ok(())
+}
+
+#[cfg(not(feature = "encryption"))]
+fn ask_passphrase(_data_file: &Path) -> io::Result<()> {
+    Err(errors::not_built_in("encryption"))
+}
+
+/// Rewrites the bills and archive files in version `to` of their format.
+fn migrate_files(profile: &Profile, to: Option<u32>, dry_run: bool) -> io::Result<()> {
+    let _lock = match lock::acquire(&profile.data_file)? {
+        Ok(lock) => Some(lock),
+        Err(_) if dry_run => None,
+        Err(pid) => {
+            return Err(io::Error::new(
+                io::ErrorKind::WouldBlock,
+                i18n::t!(
+                    "bills-in-use",
+                    file = profile.data_file.display(),
+                    pid = pid
+                ),
+            ))
+        }
+    };
+    for file_name in [&profile.data_file, &profile.archive_file] {
+        let migrated = match open_bills(file_name, "")?.migrate(to, dry_run) {
+            Err(storage::Error::Io(e)) if e.kind() == io::ErrorKind::NotFound => continue,
+            result => result?,
+        };
+        let file = file_name.display();
+        if migrated.steps.is_empty() {
+            println!(
+                "{}",
+                i18n::t!(
+                    "bills-migration-current",
+                    file = file,
+                    version = migrated.from
+                )
+            );
+            continue;
+        }
+        println!(
+            "{}",
+            i18n::t!(
+                "bills-migration",
+                file = file,
+                from = migrated.from,
+                to = migrated.to
+            )
+        );
+        for step in &migrated.steps {
+            println!("  {}", step);
+        }
+    }
+    if dry_run {
+        println!("{}", i18n::t!("bills-migration-dry-run"));
+    }
+    Ok(())
+}
+
+fn run_plugin(
+    name: &str,
+    args: &[String],
+    data_file: &Path,
+    storage: Backend,
+    verbose: bool,
+) -> io::Result<()> {
+    let plugin = Plugin::find(Config::APP, name).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            i18n::t!("bills-no-such-plugin", name = name),
+        )
+    })?;
+    let context = plugin::Context {
+        data_file,
+        storage,
+        verbose,
+    };
+    let code = plugin.run(Config::APP, env!("CARGO_PKG_VERSION"), context, args)?;
+    if code != 0 {
+        process::exit(code);
+    }
+    Ok(())
+}
+
+fn run(opt: Opt) -> io::Result<()> {
+    logging::init("bill_manager", opt.verbose, opt.log_file.as_deref())?;
+    settings::first_run(Config::APP);
+    if opt.self_update {
+        return self_update(opt.check);
+    }
+    #[cfg(not(feature = "tui"))]
+    if opt.tui {
+        return Err(errors::not_built_in("tui"));
+    }
+    if !profile::is_valid_name(&opt.profile) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            i18n::t!("bills-invalid-profile", name = opt.profile),
+        ));
+    }
+    let layers = layers(&opt);
+    let stored = layers.stored()?;
+    let config = layers.resolve(&stored)?;
+    if !config::is_valid_date_format(config.date_format()) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            i18n::t!("bills-invalid-date-format", format = config.date_format()),
+        ));
+    }
+    apply_settings(&config);
+
+    let profile = Profile::new(&opt, &config.data_file, &opt.profile);
+    if let Some((name, args)) = opt.plugin.split_first() {
+        return run_plugin(name, args, &profile.data_file, config.storage, opt.verbose);
+    }
+    if config.encrypt {
+        ask_passphrase(&profile.data_file)?;
+    }
+    if let Some(to) = opt.migrate {
+        return migrate_files(&profile, to, opt.dry_run);
+    }
+    let mut bills = load_bills(&profile.data_file, &config.currency)?;
+    let scripted = !opt.interactive
+        && !io::stdin().is_terminal()
+        && !opt.tui
+        && !opt.json
+        && opt.import.is_none()
+        && opt.export.is_none();
+    let quiet = opt.json || scripted;
+    // --json and --export only read the bills: they neither lock nor save,
+    // and recurring bills come due are generated in memory only.
+    let query = (opt.json || opt.export.is_some()) && opt.import.is_none();
+    let lock = match query {
+        true => None,
+        false => match lock::acquire(&profile.data_file)? {
+            Ok(lock) => {
+                tracing::debug!("locked {}", profile.data_file.display());
+                Some(lock)
+            }
+            Err(pid) => {
+                let in_use = i18n::t!(
+                    "bills-in-use",
+                    file = profile.data_file.display(),
+                    pid = pid
+                );
+                if opt.tui || opt.import.is_some() || scripted {
+                    return Err(io::Error::new(io::ErrorKind::WouldBlock, in_use));
+                }
+                println!("{}", in_use);
+                if !confirm(&mut Terminal::new(), &i18n::t!("bills-open-read-only"))
+                    .unwrap_or(false)
+                {
+                    return Ok(());
+                }
+                None
+            }
+        },
+    };
+    if lock.is_some() {
+        let recovered = journal::recover(&profile.data_file, &mut bills, &config.currency)?;
+        tracing::debug!("recovered {} changes from the journal", recovered);
+        if recovered > 0 && !quiet {
+            println!("{}", i18n::t!("bills-recovered", count = recovered));
+        }
+    }
+    let generated = bills.generate_recurring(Local::now().date_naive());
+    if !generated.is_empty() {
+        if !quiet {
+            println!("{}", i18n::t!("bills-new-recurring"));
+            for bill in &generated {
+                println!("  {}", bill);
+            }
+        }
+        if lock.is_some() {
+            save_bills(&profile.data_file, &bills)?;
+        }
+    }
+
+    #[cfg(feature = "tui")]
+    if opt.tui {
+        let fees = LateFees {
+            fee: opt.late_fee,
+            annual_rate: opt.interest_rate,
+        };
+        return tui::run(
+            &mut bills,
+            &profile.data_file,
+            &config,
+            opt.max_amount,
+            fees,
+        );
+    }
+    if scripted {
+        script::run(
+            io::stdin().lock(),
+            &mut io::stdout(),
+            &mut bills,
+            &Disk,
+            &profile.log_file,
+            &config.currency,
+            opt.max_amount,
+        )?;
+        return save_bills(&profile.data_file, &bills);
+    }
+    if opt.import.is_none() && opt.export.is_none() && !opt.json {
+        return main_menu(
+            &mut Terminal::new(),
+            &opt,
+            &layers,
+            stored,
+            profile,
+            bills,
+            lock,
+        );
+    }
+    if let Some(input_file) = &opt.import {
+        let count = import_bills(input_file, &mut bills, &config.currency)?;
+        save_bills(&profile.data_file, &bills)?;
+        if !opt.json {
+            println!("{}", i18n::t!("bills-imported", count = count));
+        }
+    }
+    if let Some(output_file) = &opt.export {
+        export_bills(output_file, &bills)?;
+        if !opt.json {
+            println!("{}", i18n::t!("bills-exported"));
+        }
+    }
+    if opt.json {
+        let fees = LateFees {
+            fee: opt.late_fee,
+            annual_rate: opt.interest_rate,
+        };
+        let json = format!(
+            "{:#}\n",
+            json::bills_json(&bills, fees, Local::now().date_naive())
+        );
+        match &opt.output {
+            Some(output_file) => fs::write(output_file, json)?,
+            None => print!("{}", json),
+        }
+    }
+    Ok(())
+}
+
+/// Runs the bill manager on `args`, the first being the program name, and
+/// exits when it fails.
+pub fn main<I, T>(args: I)
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    let opt = Opt::parse_from(args);
+    if let Err(e) = run(opt) {
+        errors::report(&e);
+        process::exit(1);
+    }
+}
+
+/// Loads `data` as a bills file kept with `backend`, then saves and loads it
+/// again, returning how many bills it held. This is the loader the fuzz
+/// targets feed, without touching the disk.
+pub fn load_bills_from(backend: Backend, data: &[u8]) -> io::Result<usize> {
+    let vfs = std::sync::Arc::new(vfs::Memory::new());
+    let path = Path::new("bills");
+    vfs.write(path, data)?;
+    let store = storage::open_in::<Bills>(vfs, backend, path, "EUR".to_string())?;
+    let mut bills = store.load()?;
+    bills.next_id();
+    store.save(&bills)?;
+    Ok(store.load()?.list.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bill_core::Rounding;
+    use prompt::Script;
+    use std::sync::Arc;
+    use vfs::Vfs;
+
+    pub fn bill(id: i64, name: &str, amount: &str) -> Bill {
+        Bill {
+            id,
+            name: name.to_string(),
+            amount: amount.parse().unwrap(),
+            due: None,
+            recurrence: None,
+            paid: None,
+            category: None,
+            currency: "EUR".to_string(),
+            payments: vec![],
+            notes: None,
+            payee: None,
+            split: None,
+            method: None,
+            attachments: vec![],
+            original_due: None,
+            postponed: 0,
+            priority: Priority::Normal,
+        }
+    }
+
+    #[test]
+    fn unnumbered_bill_after_the_largest_id() {
+        let file = format!(
+            "{}\nRent,500,,,,,,9223372036854775807\nGym,30\n",
+            BILL_COLUMNS.join(",")
+        );
+        let bills =
+            storage::parse_csv::<Bills>(&file, Path::new("bills.csv"), &"EUR".to_string()).unwrap();
+        assert_eq!(bills.list[&1].name, "Gym");
+        assert_eq!(bills.list[&i64::MAX].name, "Rent");
+    }
+
+    #[test]
+    fn loads_bills_from_bytes() {
+        let csv = format!("{}\nRent,500\nGym,30\n", BILL_COLUMNS.join(","));
+        assert_eq!(load_bills_from(Backend::Csv, csv.as_bytes()).unwrap(), 2);
+        assert!(load_bills_from(Backend::Json, b"[{").is_err());
+    }
+
+    #[test]
+    fn never_reuses_ids_of_removed_bills() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("bills.csv");
+        let mut bills = Bills::new();
+        for name in ["Rent", "Gym"] {
+            let id = bills.next_id();
+            bills.add_bill(bill(id, name, "10"));
+        }
+        bills.list.remove(&2);
+        save_bills(&file, &bills).unwrap();
+
+        let mut bills = read_bills(&file, "EUR").unwrap();
+        assert_eq!(bills.next_id(), 3);
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn bill_text_round_trips(
+            name in "[a-zA-Z ,;\"'é漢🙂]{1,20}|\\PC{1,20}",
+            category in "\\PC*",
+            notes in "\\PC*",
+        ) {
+            let mut original = bill(1, &name, "12.50");
+            original.category = Some(category).filter(|category| !category.is_empty());
+            original.notes = Some(notes).filter(|notes| !notes.is_empty());
+            let row = flatfile::write_record(&bill_fields(&original), ',');
+            let file = format!("{}\n{}", BILL_COLUMNS.join(","), row);
+            let loaded =
+                storage::parse_csv::<Bills>(&file, Path::new("bills.csv"), &"EUR".to_string())
+                    .unwrap();
+            proptest::prop_assert_eq!(loaded.list.get(&1), Some(&original));
+        }
+    }
+
+    #[test]
+    fn command_line_flags() {
+        use clap::CommandFactory;
+        Opt::command().debug_assert();
+
+        let opt = Opt::try_parse_from([
+            "bills",
+            "--data-file",
+            "b.csv",
+            "-v",
+            "--json",
+            "-o",
+            "b.json",
+        ]);
+        assert!(opt.is_ok_and(|opt| opt.verbose && opt.output.is_some()));
+        assert!(Opt::try_parse_from(["bills", "--tui", "--export", "b.csv"]).is_err());
+        assert!(Opt::try_parse_from(["bills", "-o", "b.json"]).is_err());
+    }
+
+    #[test]
+    fn add_bill_menu_adds_bill() {
+        let mut ui = Script::new(&["Rent", "500", "2024-03-01", "monthly", "Home", "", ""]);
+        let mut bills = Bills::new();
+        let max = "1000".parse().unwrap();
+        assert!(
+            Menu::add_bill_menu(&mut ui, &mut bills, &Records::new(), "EUR", None, max).is_ok()
+        );
+
+        let rent = &bills.with_name("Rent")[0];
+        assert_eq!(rent.amount, "500".parse().unwrap());
+        assert_eq!(rent.category.as_deref(), Some("Home"));
+        assert_eq!(rent.due, NaiveDate::from_ymd_opt(2024, 3, 1));
+        assert!(ui.printed("Bill added"));
+    }
+
+    #[test]
+    fn add_bill_menu_goes_back() {
+        let mut ui = Script::new(&["Rent", "b"]);
+        let mut bills = Bills::new();
+        let max = "1000".parse().unwrap();
+        assert!(
+            Menu::add_bill_menu(&mut ui, &mut bills, &Records::new(), "EUR", None, max).is_err()
+        );
+        assert!(bills.list.is_empty());
+    }
+
+    #[test]
+    fn update_bill_menu_keeps_unanswered_fields() {
+        let mut bills = Bills::new();
+        bills.add_bill(Bill {
+            due: NaiveDate::from_ymd_opt(2024, 3, 1),
+            recurrence: Some("monthly".parse().unwrap()),
+            ..bill(1, "Rent", "500")
+        });
+        let mut ui = Script::new(&["#1", "550"]);
+        let max = "1000".parse().unwrap();
+        assert!(Menu::update_bill_menu(&mut ui, &mut bills, &Records::new(), max).is_ok());
+
+        let rent = &bills.list[&1];
+        assert_eq!(rent.amount, "550".parse().unwrap());
+        assert_eq!(rent.due, NaiveDate::from_ymd_opt(2024, 3, 1));
+        assert_eq!(rent.recurrence, Some("monthly".parse().unwrap()));
+        assert!(ui.printed("Repeats [monthly] (empty to keep, - to clear):"));
+
+        let mut ui = Script::new(&["#1", "550", "-", "-"]);
+        assert!(Menu::update_bill_menu(&mut ui, &mut bills, &Records::new(), max).is_ok());
+        assert_eq!(bills.list[&1].due, None);
+        assert_eq!(bills.list[&1].recurrence, None);
+    }
+
+    #[test]
+    fn get_bill_amount_reprompts_until_valid() {
+        let mut ui = Script::new(&["-5", "abc", "2000", "12.5"]);
+        let amount = get_bill_amount(&mut ui, "Amount:", "1000".parse().unwrap());
+        assert_eq!(amount.ok().flatten(), Some("12.50".parse().unwrap()));
+        assert!(ui.printed("Amount cannot be negative"));
+        assert!(ui.printed("Please enter a number"));
+        assert!(ui.printed("Amount cannot be more than 1000.00"));
+
+        let mut ui = Script::new(&["12.50+3.20*2", "(10-2.5)/3"]);
+        let amount = get_bill_amount(&mut ui, "Amount:", "1000".parse().unwrap());
+        assert_eq!(amount.ok().flatten(), Some("18.90".parse().unwrap()));
+        assert!(ui.printed("= 18.90"));
+        assert_eq!(expr::evaluate("(10-2.5)/3").ok(), "2.50".parse().ok());
+        assert!(expr::evaluate("2*").is_err() && expr::evaluate("1/0").is_err());
+    }
+
+    #[test]
+    fn remove_bill_menu_asks_first() {
+        let mut bills = Bills::new();
+        bills.add_bill(bill(1, "Rent", "500"));
+
+        let mut ui = Script::new(&["Rent", "n"]);
+        assert!(Menu::remove_bill_menu(&mut ui, &mut bills).is_ok());
+        assert!(bills.list.contains_key(&1));
+
+        let mut ui = Script::new(&["re", "y"]);
+        assert!(Menu::remove_bill_menu(&mut ui, &mut bills).is_ok());
+        assert!(bills.list.is_empty());
+        assert!(ui.printed("using Rent"));
+    }
+
+    #[test]
+    fn get_bill_id_ignores_case_and_spacing() {
+        let mut bills = Bills::new();
+        bills.add_bill(bill(1, "Rent", "500"));
+        bills.add_bill(bill(2, "Rental car", "80"));
+        bills.add_bill(bill(3, "Power bill", "60"));
+
+        let mut ui = Script::new(&["rent "]);
+        let id = get_bill_id(&mut ui, &bills, "Bill name:");
+        assert_eq!(id.ok().flatten(), Some(1));
+
+        let mut ui = Script::new(&["POWER   bill"]);
+        let id = get_bill_id(&mut ui, &bills, "Bill name:");
+        assert_eq!(id.ok().flatten(), Some(3));
+
+        let mut ui = Script::new(&["#2"]);
+        let id = get_bill_id(&mut ui, &bills, "Bill name:");
+        assert_eq!(id.ok().flatten(), Some(2));
+    }
+
+    #[test]
+    fn get_bill_id_asks_when_ambiguous() {
+        let mut bills = Bills::new();
+        bills.add_bill(bill(1, "Rent", "500"));
+        bills.add_bill(bill(2, "rent", "450"));
+
+        let mut ui = Script::new(&["RENT", "rent"]);
+        let id = get_bill_id(&mut ui, &bills, "Bill name:");
+        assert_eq!(id.ok().flatten(), Some(2));
+        assert!(ui.printed("matching bills: Rent, rent"));
+    }
+
+    #[test]
+    fn get_bill_id_offers_closest_name() {
+        let mut bills = Bills::new();
+        bills.add_bill(bill(1, "Electricity", "90"));
+        bills.add_bill(bill(2, "Water", "30"));
+
+        let mut ui = Script::new(&["Electrcity", "y"]);
+        let id = get_bill_id(&mut ui, &bills, "Bill name:");
+        assert_eq!(id.ok().flatten(), Some(1));
+        assert!(ui.printed("bill not found, did you mean 'Electricity'? (y/N)"));
+
+        let mut ui = Script::new(&["Electrcity", "n"]);
+        let id = get_bill_id(&mut ui, &bills, "Bill name:");
+        assert_eq!(id.ok().flatten(), None);
+    }
+
+    #[test]
+    fn bills_with_the_same_name_are_kept_apart() {
+        let mut bills = Bills::new();
+        bills.add_bill(bill(1, "Water", "30"));
+        bills.add_bill(bill(2, "Water", "45"));
+
+        let mut ui = Script::new(&["Water", "#2"]);
+        let id = get_bill_id(&mut ui, &bills, "Bill name:");
+        assert_eq!(id.ok().flatten(), Some(2));
+        assert!(ui.printed("several bills are named Water, please enter an id:"));
+
+        assert!(bills.rename_bill(2, "Water (garden)"));
+        assert_eq!(bills.with_name("Water").len(), 1);
+        assert_eq!(bills.list[&1].amount, "30".parse().unwrap());
+    }
+
+    #[test]
+    fn view_bill_menu_remembers_choices() {
+        let mut bills = Bills::new();
+        let mut power = bill(1, "Power", "60");
+        power.category = Some("Home".to_string());
+        bills.add_bill(power);
+        bills.add_bill(bill(2, "Gym", "30"));
+        let fees = LateFees {
+            fee: Money::ZERO,
+            annual_rate: 0.0,
+        };
+
+        let mut prefs = ViewPrefs::default();
+        let mut ui = Script::new(&["u", "a", "y", "c"]);
+        assert!(
+            Menu::view_bill_menu(&mut ui, &bills, fees, &mut prefs, &Rates::default(), "EUR")
+                .is_ok()
+        );
+        assert_eq!(prefs.filter, Filter::Unpaid);
+        assert_eq!(prefs.sort, SortKey::Amount);
+        assert!(prefs.descending);
+        assert_eq!(prefs.grouping, Grouping::Category);
+
+        let mut ui = Script::new(&[]);
+        assert!(
+            Menu::view_bill_menu(&mut ui, &bills, fees, &mut prefs, &Rates::default(), "EUR")
+                .is_ok()
+        );
+        assert!(ui.printed("Sort by (n)ame, (a)mount, (d)ue date or (c)ategory? [a]"));
+        assert!(ui.printed("== Home (subtotal €60.00) == 66.7% #######..."));
+    }
+
+    #[test]
+    fn view_bill_menu_leaves_aging() {
+        let mut bills = Bills::new();
+        bills.add_bill(bill(1, "Gym", "30"));
+        let fees = LateFees {
+            fee: Money::ZERO,
+            annual_rate: 0.0,
+        };
+
+        let mut prefs = ViewPrefs::default();
+        let mut ui = Script::new(&["o"]);
+        assert!(
+            Menu::view_bill_menu(&mut ui, &bills, fees, &mut prefs, &Rates::default(), "EUR")
+                .is_ok()
+        );
+        assert_eq!(prefs.grouping, Grouping::Aging);
+
+        let mut ui = Script::new(&["u"]);
+        assert!(
+            Menu::view_bill_menu(&mut ui, &bills, fees, &mut prefs, &Rates::default(), "EUR")
+                .is_ok()
+        );
+        assert_eq!(prefs.filter, Filter::Unpaid);
+        assert_eq!(prefs.grouping, Grouping::Flat);
+        assert!(ui.printed("Sort by (n)ame, (a)mount, (d)ue date or (c)ategory? [d]"));
+    }
+
+    #[test]
+    fn payment_menu_rolls_over_recurring_bills() {
+        let mut bills = Bills::new();
+        let mut rent = bill(1, "Rent", "500");
+        rent.due = NaiveDate::from_ymd_opt(2024, 3, 1);
+        rent.recurrence = "monthly".parse().ok();
+        bills.add_bill(rent);
+        let max = "10000".parse().unwrap();
+
+        let mut ui = Script::new(&["1", "200", "2024-03-01"]);
+        assert!(Menu::payment_menu(&mut ui, &mut bills, max).is_ok());
+        assert!(ui.printed("Payment recorded, remaining: €300.00"));
+
+        let mut ui = Script::new(&["1", "300", "2024-03-02"]);
+        assert!(Menu::payment_menu(&mut ui, &mut bills, max).is_ok());
+        assert!(ui.printed("Payment recorded, bill is fully paid, next instance:"));
+        assert!(!ui.printed("Payment recorded, remaining"));
+        assert_eq!(bills.list[&1].due, NaiveDate::from_ymd_opt(2024, 4, 1));
+    }
+
+    #[test]
+    fn postponed_bill_keeps_its_schedule() {
+        let mut bills = Bills::new();
+        let mut rent = bill(1, "Rent", "500");
+        rent.due = NaiveDate::from_ymd_opt(2024, 3, 1);
+        rent.recurrence = "monthly".parse().ok();
+        bills.add_bill(rent);
+
+        assert_eq!(bills.postpone(1, 5), NaiveDate::from_ymd_opt(2024, 3, 6));
+        assert_eq!(bills.postpone(1, 2), NaiveDate::from_ymd_opt(2024, 3, 8));
+        let rent = &bills.list[&1];
+        assert_eq!(rent.postponed, 2);
+        assert_eq!(rent.original_due, NaiveDate::from_ymd_opt(2024, 3, 1));
+        assert!(rent.is_overdue(NaiveDate::from_ymd_opt(2024, 3, 9).unwrap()));
+        assert!(!rent.is_overdue(NaiveDate::from_ymd_opt(2024, 3, 7).unwrap()));
+
+        bills.pay_bill(1, NaiveDate::from_ymd_opt(2024, 3, 8).unwrap());
+        let next = &bills.list[&1];
+        assert_eq!(next.due, NaiveDate::from_ymd_opt(2024, 4, 1));
+        assert_eq!(next.postponed, 0);
+    }
+
+    #[test]
+    fn rounding_follows_currency_decimals() {
+        let amount: Money = "1234.50".parse().unwrap();
+        assert_eq!(amount.round(0, Rounding::HalfUp).format(0), "1235");
+        assert_eq!(amount.round(0, Rounding::HalfEven).format(0), "1234");
+        assert_eq!(amount.round(0, Rounding::Down).format(0), "1234");
+        let negative: Money = "-0.01".parse().unwrap();
+        assert_eq!(negative.round(0, Rounding::Up).format(0), "-1");
+        assert_eq!(currency::format_amount(amount, "JPY"), "¥1,235");
+        assert_eq!(currency::format_amount(amount, "EUR"), "€1,234.50");
+    }
+
+    #[test]
+    fn monthly_bills_are_suggested_as_recurring() {
+        let mut archive = Bills::new();
+        for (id, month) in [(1, 1), (2, 2), (3, 3)] {
+            let mut power = bill(id, "Power", "60");
+            power.due = NaiveDate::from_ymd_opt(2024, month, 2);
+            power.paid = power.due;
+            archive.add_bill(power);
+        }
+        let mut bills = Bills::new();
+        let mut power = bill(4, "power ", "60");
+        power.due = NaiveDate::from_ymd_opt(2024, 4, 1);
+        bills.add_bill(power);
+        let mut gym = bill(5, "Gym", "30");
+        gym.due = NaiveDate::from_ymd_opt(2024, 4, 1);
+        bills.add_bill(gym);
+
+        let suggestions = infer::suggest(bills.list.values(), archive.list.values());
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].id, 4);
+        assert_eq!(suggestions[0].recurrence.to_string(), "monthly");
+        assert_eq!(suggestions[0].occurrences, 4);
+    }
+
+    #[test]
+    fn calendar_grid_starts_on_monday() {
+        let grid = calendar::grid((2024, 3), &[1, 15]);
+        assert_eq!(grid[0], "Mo  Tu  We  Th  Fr  Sa  Su");
+        assert_eq!(grid[1], "                 1*  2   3");
+        assert_eq!(grid[3], "11  12  13  14  15* 16  17");
+        assert_eq!(
+            grid.last().map(String::as_str),
+            Some("25  26  27  28  29  30  31")
+        );
+    }
+
+    #[test]
+    fn menu_keys_map_to_item_numbers() {
+        assert_eq!(menu_number("1"), "1");
+        assert_eq!(menu_number("0"), "10");
+        assert_eq!(menu_number("a"), "11");
+        assert_eq!(menu_number("25"), "25");
+        assert_eq!(menu_key(10), Some('a'));
+        assert!(!MENU_KEYS.contains(['b', 'B', '?']));
+    }
+
+    #[test]
+    fn search_by_amount_range() {
+        let water = bill(1, "Water", "39.99");
+        let rent = bill(2, "Rent", "500");
+        let query = Query::parse("50..200").unwrap();
+        assert!(!query.matches(&water) && !query.matches(&rent));
+        let query = Query::parse("..40").unwrap();
+        assert!(query.matches(&water) && !query.matches(&rent));
+        let query = Query::parse("=39.99").unwrap();
+        assert!(query.matches(&water));
+        assert!(Query::parse("200..50").is_err());
+        assert!(Query::parse("..").is_err());
+        let error = Query::parse("rent  5..x9").unwrap_err();
+        assert_eq!(
+            error.render_line("rent  5..x9"),
+            "rent  5..x9\n         ^^ invalid amount"
+        );
+    }
+
+    #[test]
+    fn lock_reports_running_holder() {
+        let memory = Arc::new(vfs::Memory::new());
+        let data_file = Path::new("bills.csv");
+        let lock_file = lock::path(data_file);
+        memory.write(&lock_file, b"1\n").unwrap();
+        if cfg!(target_os = "linux") {
+            assert_eq!(
+                lock::acquire_in(memory.clone(), data_file).unwrap().err(),
+                Some(1)
+            );
+        }
+
+        memory.write(&lock_file, b"not a pid\n").unwrap();
+        let lock = lock::acquire_in(memory.clone(), data_file)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            memory.read_to_string(&lock_file).unwrap(),
+            format!("{}\n", std::process::id())
+        );
+        drop(lock);
+        assert_eq!(memory.paths(), Vec::<PathBuf>::new());
+    }
+
+    #[test]
+    fn high_priority_bills_come_first_when_unpaid() {
+        let mut bills = Bills::new();
+        bills.add_bill(bill(1, "Gym", "30"));
+        let mut rent = bill(2, "Rent", "500");
+        rent.priority = Priority::High;
+        bills.add_bill(rent);
+        let mut streaming = bill(3, "Streaming", "10");
+        streaming.priority = Priority::Low;
+        bills.add_bill(streaming);
+        let fees = LateFees {
+            fee: Money::ZERO,
+            annual_rate: 0.0,
+        };
+
+        let mut prefs = ViewPrefs::default();
+        let mut ui = Script::new(&["u", "n", "n", "n"]);
+        assert!(
+            Menu::view_bill_menu(&mut ui, &bills, fees, &mut prefs, &Rates::default(), "EUR")
+                .is_ok()
+        );
+        let position = |text: &str| ui.output.iter().position(|line| line.contains(text));
+        let rent = position("#2 Rent: €500.00 high priority");
+        let gym = position("#1 Gym: €30.00");
+        let streaming = position("#3 Streaming: €10.00 low priority");
+        assert!(rent.is_some() && rent < gym && gym < streaming);
+
+        let row = flatfile::write_record(&bill_fields(&bills.list[&2]), ',');
+        let fields = flatfile::parse_line(row.trim_end(), ',').unwrap();
+        assert_eq!(parse_bill(&fields, "EUR").unwrap().priority, Priority::High);
+    }
+
+    #[test]
+    fn natural_dates() {
+        let today = NaiveDate::from_ymd_opt(2024, 3, 13).unwrap();
+        let parse = |input| dates::parse(input, today, locale::Locale::En);
+        let date = |month, day| NaiveDate::from_ymd_opt(2024, month, day);
+        assert_eq!(parse("tomorrow"), date(3, 14));
+        assert_eq!(parse("next friday"), date(3, 15));
+        assert_eq!(parse("Wed"), date(3, 20));
+        assert_eq!(parse("in 2 weeks"), date(3, 27));
+        assert_eq!(parse("in a month"), date(4, 13));
+        assert_eq!(parse("15.03."), date(3, 15));
+        assert_eq!(parse("1.3."), NaiveDate::from_ymd_opt(2025, 3, 1));
+        assert_eq!(parse("4/5"), date(4, 5));
+        assert_eq!(dates::parse("4/5", today, locale::Locale::De), date(5, 4));
+        assert_eq!(parse("someday"), None);
+    }
+
+    #[test]
+    fn prompt_shows_help() {
+        let mut ui = Script::new(&["?", "x"]);
+        let input = ui.prompt("Name:", "The bill name.");
+        assert_eq!(input.ok().flatten().as_deref(), Some("x"));
+        assert_eq!(ui.output[0], "Name:");
+        assert_eq!(ui.output[1], "The bill name.");
+        assert_eq!(ui.output.last().map(String::as_str), Some("Name:"));
+    }
+}
@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+use std::{env, fmt, str::FromStr, sync::RwLock};
+
+static LOCALE: RwLock<Locale> = RwLock::new(Locale::En);
+
+/// Message ids of the main menu actions, in the order of their keys.
+pub const MENU: &[&str] = &[
+    "bills-menu-add-bill",
+    "bills-menu-view-bills",
+    "bills-menu-remove-bill",
+    "bills-menu-update-bill",
+    "bills-menu-bill-summary",
+    "bills-menu-export-bills-to-csv",
+    "bills-menu-import-bills-from-csv",
+    "bills-menu-mark-bill-as-paid",
+    "bills-menu-view-bills-by-category",
+    "bills-menu-edit-exchange-rates",
+    "bills-menu-search-bills",
+    "bills-menu-undo-last-change",
+    "bills-menu-rename-bill",
+    "bills-menu-record-payment",
+    "bills-menu-payment-history",
+    "bills-menu-set-category-budget",
+    "bills-menu-budget-status",
+    "bills-menu-monthly-report",
+    "bills-menu-archive-paid-bills",
+    "bills-menu-view-archive",
+    "bills-menu-unarchive-bill",
+    "bills-menu-show-bill-details",
+    "bills-menu-spending-chart",
+    "bills-menu-profiles",
+    "bills-menu-settings",
+    "bills-menu-split-bill",
+    "bills-menu-settle-up",
+    "bills-menu-export-html-report",
+    "bills-menu-reconcile-bank-statement",
+    "bills-menu-totals-by-payment-method",
+    "bills-menu-attach-receipt",
+    "bills-menu-calendar",
+    "bills-menu-postpone-bill",
+    "bills-menu-yearly-summary-and-tax-export",
+    "bills-menu-suggest-recurring-bills",
+    "bills-menu-print-bills-as-json",
+    "bills-menu-rotate-archive",
+    "bills-menu-action-history",
+    "bills-menu-redo-last-change",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+}
+impl Locale {
+    pub fn from_env() -> Locale {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| value.get(..2)?.parse().ok())
+            .unwrap_or_default()
+    }
+    pub fn date_format(&self) -> &'static str {
+        match self {
+            Locale::En => "%Y-%m-%d",
+            Locale::De => "%d.%m.%Y",
+        }
+    }
+    fn separators(&self) -> (char, char) {
+        match self {
+            Locale::En => ('.', ','),
+            Locale::De => (',', '.'),
+        }
+    }
+    pub fn symbol_first(&self) -> bool {
+        *self == Locale::En
+    }
+    pub fn format_number(&self, plain: &str) -> String {
+        let (decimal, group) = self.separators();
+        let (sign, digits) = match plain.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", plain),
+        };
+        let (whole, fraction) = match digits.split_once('.') {
+            Some((whole, fraction)) => (whole, Some(fraction)),
+            None => (digits, None),
+        };
+
+        let mut grouped = String::new();
+        for (num, digit) in whole.chars().enumerate() {
+            if num > 0 && (whole.len() - num).is_multiple_of(3) {
+                grouped.push(group);
+            }
+            grouped.push(digit);
+        }
+        match fraction {
+            Some(fraction) => format!("{}{}{}{}", sign, grouped, decimal, fraction),
+            None => format!("{}{}", sign, grouped),
+        }
+    }
+    pub fn parse_number(&self, input: &str) -> String {
+        let (decimal, group) = self.separators();
+        if !input.contains(decimal) {
+            return input.trim().to_string();
+        }
+        input
+            .trim()
+            .chars()
+            .filter(|c| *c != group)
+            .map(|c| if c == decimal { '.' } else { c })
+            .collect()
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Locale::En => write!(f, "en"),
+            Locale::De => write!(f, "de"),
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(locale: &str) -> Result<Self, Self::Err> {
+        match locale.trim().to_lowercase().as_str() {
+            "en" => Ok(Locale::En),
+            "de" => Ok(Locale::De),
+            _ => Err(format!("unsupported language {}", locale)),
+        }
+    }
+}
+
+pub fn set_locale(locale: Locale) {
+    if let Ok(mut current) = LOCALE.write() {
+        *current = locale;
+    }
+}
+
+pub fn current() -> Locale {
+    LOCALE.read().map_or(Locale::En, |locale| *locale)
+}
@@ -0,0 +1,74 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    process,
+    sync::Arc,
+};
+use vfs::{Disk, Vfs};
+
+#[derive(Debug)]
+pub struct Lock {
+    path: PathBuf,
+    vfs: Arc<dyn Vfs>,
+}
+impl Drop for Lock {
+    fn drop(&mut self) {
+        let _ = self.vfs.remove_file(&self.path);
+    }
+}
+
+pub fn path(data_file: &Path) -> PathBuf {
+    let mut file_name = data_file.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".lock");
+    data_file.with_file_name(file_name)
+}
+
+fn is_running(pid: u32) -> bool {
+    if cfg!(target_os = "linux") {
+        Path::new("/proc").join(pid.to_string()).exists()
+    } else {
+        true
+    }
+}
+
+fn holder(vfs: &dyn Vfs, path: &Path) -> io::Result<Option<u32>> {
+    let pid = match vfs.read_to_string(path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        result => result?.trim().parse().ok(),
+    };
+    Ok(pid.filter(|pid| *pid != process::id() && is_running(*pid)))
+}
+
+/// Locks `data_file` for this process, or returns the id of the process
+/// that already holds it. Locks left behind by crashed processes are taken over.
+pub fn acquire(data_file: &Path) -> io::Result<Result<Lock, u32>> {
+    acquire_in(Arc::new(Disk), data_file)
+}
+
+pub fn acquire_in(vfs: Arc<dyn Vfs>, data_file: &Path) -> io::Result<Result<Lock, u32>> {
+    let path = path(data_file);
+    // The pid is written first and then linked into place, so nobody sees a lock file without one.
+    let mut pending = path.as_os_str().to_os_string();
+    pending.push(format!(".{}", process::id()));
+    let pending = PathBuf::from(pending);
+    vfs.write(&pending, format!("{}\n", process::id()).as_bytes())?;
+    let result = loop {
+        match vfs.hard_link(&pending, &path) {
+            Ok(()) => {
+                let vfs = Arc::clone(&vfs);
+                break Ok(Ok(Lock { path, vfs }));
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => match holder(&*vfs, &path) {
+                Ok(Some(pid)) => break Ok(Err(pid)),
+                Ok(None) => match vfs.remove_file(&path) {
+                    Err(e) if e.kind() != io::ErrorKind::NotFound => break Err(e),
+                    _ => continue,
+                },
+                Err(e) => break Err(e),
+            },
+            Err(e) => break Err(e),
+        }
+    };
+    vfs.remove_file(&pending)?;
+    result
+}
@@ -0,0 +1,54 @@
+use migrate::{Migration, Table};
+
+/// How bills files changed since they were first written, see [`storage::Rows::MIGRATIONS`].
+pub const MIGRATIONS: &[Migration<Table>] = &[Migration {
+    version: 1,
+    description: "record paid dates as payments",
+    up: payments_from_paid,
+    down: paid_without_payments,
+}];
+
+/// Bills paid before partial payments existed get one payment of the whole
+/// amount on their paid date.
+fn payments_from_paid(table: &mut Table) {
+    for row in 0..table.rows.len() {
+        let paid = table.get(row, "paid");
+        if !paid.is_empty() && table.get(row, "payments").is_empty() {
+            let payment = format!("{}@{}", table.get(row, "amount"), paid);
+            table.set(row, "payments", payment);
+        }
+    }
+}
+
+fn paid_without_payments(table: &mut Table) {
+    for row in 0..table.rows.len() {
+        let whole = format!("{}@{}", table.get(row, "amount"), table.get(row, "paid"));
+        if table.get(row, "payments") == whole {
+            table.set(row, "payments", String::new());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paid_dates_become_payments_and_back() {
+        let old = Table::new(
+            vec!["name".into(), "amount".into(), "paid".into()],
+            vec![
+                vec!["Rent".into(), "500".into(), "2024-03-01".into()],
+                vec!["Gym".into(), "30".into(), "".into()],
+            ],
+        );
+        let mut table = old.clone();
+        migrate::migrate(MIGRATIONS, &mut table, 0, 1).unwrap();
+        assert_eq!(
+            table.select(&["name", "payments"]),
+            [["Rent", "500@2024-03-01"], ["Gym", ""]]
+        );
+        migrate::migrate(MIGRATIONS, &mut table, 1, 0).unwrap();
+        assert_eq!(table.select(&["name", "amount", "paid"]), old.rows);
+    }
+}
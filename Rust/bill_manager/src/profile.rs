@@ -0,0 +1,106 @@
+use crate::Opt;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+pub const DEFAULT_PROFILE: &str = "default";
+
+#[derive(Debug)]
+pub struct Profile {
+    pub name: String,
+    pub data_file: PathBuf,
+    pub budgets_file: PathBuf,
+    pub archive_file: PathBuf,
+    pub transfers_file: PathBuf,
+    pub attachments_dir: PathBuf,
+    pub log_file: PathBuf,
+}
+impl Profile {
+    pub fn new(opt: &Opt, data_file: &Path, name: &str) -> Profile {
+        let data_file = profile_path(data_file, name);
+        let (stem, _) = split_file_name(&data_file);
+        Profile {
+            name: name.to_string(),
+            attachments_dir: data_file.with_file_name(format!("{}-attachments", stem)),
+            log_file: data_file.with_file_name(format!("{}-history.log", stem)),
+            data_file,
+            budgets_file: profile_path(&opt.budgets_file, name),
+            archive_file: profile_path(&opt.archive_file, name),
+            transfers_file: profile_path(&opt.transfers_file, name),
+        }
+    }
+    pub fn remove_files(&self) -> io::Result<()> {
+        for file in [
+            &self.data_file,
+            &self.budgets_file,
+            &self.archive_file,
+            &self.transfers_file,
+            &self.log_file,
+            &crate::counter_file(&self.data_file),
+            &crate::counter_file(&self.archive_file),
+        ] {
+            match fs::remove_file(file) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e),
+                _ => {}
+            }
+        }
+        match fs::remove_dir_all(&self.attachments_dir) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
+
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn split_file_name(path: &Path) -> (String, String) {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|extension| format!(".{}", extension.to_string_lossy()))
+        .unwrap_or_default();
+    (stem, extension)
+}
+
+fn profile_path(base: &Path, name: &str) -> PathBuf {
+    if name == DEFAULT_PROFILE {
+        return base.to_path_buf();
+    }
+    let (stem, extension) = split_file_name(base);
+    base.with_file_name(format!("{}-{}{}", stem, name, extension))
+}
+
+pub fn list_profiles(data_file: &Path) -> io::Result<Vec<String>> {
+    let dir = match data_file.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let (stem, extension) = split_file_name(data_file);
+    let prefix = format!("{}-", stem);
+
+    let mut profiles = vec![];
+    for entry in fs::read_dir(dir)? {
+        let file_name = entry?.file_name();
+        let name = file_name
+            .to_str()
+            .and_then(|file_name| file_name.strip_prefix(&prefix))
+            .and_then(|rest| rest.strip_suffix(&extension));
+        if let Some(name) = name {
+            if is_valid_name(name) && name != DEFAULT_PROFILE {
+                profiles.push(name.to_string());
+            }
+        }
+    }
+    profiles.sort();
+    profiles.insert(0, DEFAULT_PROFILE.to_string());
+    Ok(profiles)
+}
@@ -0,0 +1,146 @@
+use crate::{config, Bill, DATE_FORMAT};
+use bill_core::Money;
+use chrono::NaiveDate;
+use flatfile::{Format, Record, Table};
+use std::io;
+
+const BANK_DATE_FORMATS: &[&str] = &[DATE_FORMAT, "%d.%m.%Y", "%d/%m/%Y", "%m/%d/%Y"];
+const DATE_COLUMNS: &[&str] = &["date", "booking date", "value date"];
+const DESCRIPTION_COLUMNS: &[&str] = &["description", "payee", "name", "text", "reference"];
+const AMOUNT_COLUMNS: &[&str] = &["amount", "value"];
+const MIN_WORD_LENGTH: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    pub date: NaiveDate,
+    pub description: String,
+    pub amount: Money,
+}
+
+struct Columns {
+    date: usize,
+    description: usize,
+    amount: usize,
+}
+impl Columns {
+    fn parse(&self, record: &Record) -> Option<Transaction> {
+        Some(Transaction {
+            date: parse_date(record.get(self.date)?.trim())?,
+            description: record.get(self.description)?.trim().to_string(),
+            amount: parse_amount(record.get(self.amount)?)?.abs(),
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Statement {
+    pub transactions: Vec<Transaction>,
+    pub skipped: Vec<usize>,
+}
+
+fn find_column(table: &Table, names: &[&str]) -> io::Result<usize> {
+    table.column(names).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("statement has no {} column", names[0]),
+        )
+    })
+}
+
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    config::parse_date(date).or_else(|| {
+        BANK_DATE_FORMATS
+            .iter()
+            .find_map(|format| NaiveDate::parse_from_str(date, format).ok())
+    })
+}
+
+fn parse_amount(amount: &str) -> Option<Money> {
+    let amount: String = amount
+        .chars()
+        .filter(|c| c.is_ascii_digit() || matches!(c, '-' | '.' | ','))
+        .collect();
+    let normalized = match (amount.rfind(','), amount.rfind('.')) {
+        (Some(comma), Some(dot)) if comma > dot => amount.replace('.', "").replace(',', "."),
+        (Some(_), Some(_)) => amount.replace(',', ""),
+        (Some(_), None) => amount.replace(',', "."),
+        _ => amount,
+    };
+    normalized.parse().ok()
+}
+
+pub fn parse_statement(statement: &str) -> io::Result<Statement> {
+    let table = flatfile::parse(statement, Format::detect(statement));
+    if table.header.is_empty() && table.records.is_empty() {
+        return Ok(Statement::default());
+    }
+    let columns = Columns {
+        date: find_column(&table, DATE_COLUMNS)?,
+        description: find_column(&table, DESCRIPTION_COLUMNS)?,
+        amount: find_column(&table, AMOUNT_COLUMNS)?,
+    };
+
+    let mut parsed = Statement::default();
+    for record in &table.records {
+        let transaction = match record {
+            Ok(record) if record.text.trim().is_empty() => continue,
+            Ok(record) => columns.parse(record).ok_or(record.line),
+            Err(e) => Err(e.line),
+        };
+        match transaction {
+            Ok(transaction) => parsed.transactions.push(transaction),
+            Err(line) => parsed.skipped.push(line),
+        }
+    }
+    Ok(parsed)
+}
+
+fn name_matches(bill: &Bill, description: &str) -> bool {
+    let description = description.to_lowercase();
+    let name = bill.name.to_lowercase();
+    description.contains(&name)
+        || name
+            .split_whitespace()
+            .filter(|word| word.chars().count() >= MIN_WORD_LENGTH)
+            .any(|word| description.contains(word))
+}
+
+pub fn match_transactions(
+    bills: &[&Bill],
+    transactions: Vec<Transaction>,
+) -> (Vec<(i64, Transaction)>, Vec<Transaction>) {
+    let mut open: Vec<&Bill> = bills
+        .iter()
+        .copied()
+        .filter(|bill| bill.paid.is_none())
+        .collect();
+    let mut matched = vec![];
+    let mut unmatched = vec![];
+
+    for transaction in transactions {
+        let same_amount: Vec<usize> = open
+            .iter()
+            .enumerate()
+            .filter(|(_, bill)| bill.remaining() == transaction.amount)
+            .map(|(index, _)| index)
+            .collect();
+        let by_name = same_amount
+            .iter()
+            .copied()
+            .find(|index| name_matches(open[*index], &transaction.description));
+        let index = match (by_name, same_amount.as_slice()) {
+            (Some(index), _) => Some(index),
+            (None, [index]) => Some(*index),
+            _ => None,
+        };
+
+        match index {
+            Some(index) => {
+                let bill = open.remove(index);
+                matched.push((bill.id, transaction));
+            }
+            None => unmatched.push(transaction),
+        }
+    }
+    (matched, unmatched)
+}
@@ -0,0 +1,63 @@
+use crate::{currency::Rates, Bill, UNCATEGORIZED};
+use bill_core::Money;
+use chrono::{Datelike, NaiveDate};
+use std::collections::BTreeMap;
+
+pub type Month = (i32, u32);
+
+pub fn month_of(date: NaiveDate) -> Month {
+    (date.year(), date.month())
+}
+
+pub fn previous_month((year, month): Month) -> Month {
+    if month == 1 {
+        (year - 1, 12)
+    } else {
+        (year, month - 1)
+    }
+}
+
+pub fn next_month((year, month): Month) -> Month {
+    if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct MonthReport {
+    pub billed: Money,
+    pub paid: Money,
+    pub by_category: BTreeMap<String, Money>,
+    pub unconverted: usize,
+}
+
+pub fn monthly_reports<'a>(
+    bills: impl Iterator<Item = &'a Bill>,
+    today: NaiveDate,
+    rates: &Rates,
+    base: &str,
+) -> BTreeMap<Month, MonthReport> {
+    let mut reports: BTreeMap<Month, MonthReport> = BTreeMap::new();
+    for bill in bills {
+        let report = reports.entry(bill.month(today)).or_default();
+        match rates.convert(bill.amount, &bill.currency, base) {
+            Some(amount) => {
+                report.billed += amount;
+                let category = bill.category.as_deref().unwrap_or(UNCATEGORIZED);
+                *report.by_category.entry(category.to_string()).or_default() += amount;
+            }
+            None => report.unconverted += 1,
+        }
+
+        for payment in &bill.payments {
+            let report = reports.entry(month_of(payment.date)).or_default();
+            match rates.convert(payment.amount, &bill.currency, base) {
+                Some(amount) => report.paid += amount,
+                None => report.unconverted += 1,
+            }
+        }
+    }
+    reports
+}
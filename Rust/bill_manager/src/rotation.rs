@@ -0,0 +1,204 @@
+use crate::{bill_fields, parse_bill, Bill, Bills, BILL_COLUMNS};
+use chrono::Datelike;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde_json::{Map, Value};
+use std::{
+    collections::BTreeMap,
+    io,
+    path::{Path, PathBuf},
+};
+use vfs::Vfs;
+
+/// Archives with more bills than this are worth rotating.
+pub const LARGE_ARCHIVE: usize = 200;
+const EXTENSION: &str = ".json.gz";
+
+fn year_of(bill: &Bill) -> Option<i32> {
+    bill.paid.or(bill.due).map(|date| date.year())
+}
+
+pub fn closed_years(archive: &Bills, this_year: i32) -> BTreeMap<i32, Vec<i64>> {
+    let mut years: BTreeMap<i32, Vec<i64>> = BTreeMap::new();
+    for bill in archive.list.values() {
+        if let Some(year) = year_of(bill).filter(|year| *year < this_year) {
+            years.entry(year).or_default().push(bill.id);
+        }
+    }
+    years
+}
+
+fn prefix(archive_file: &Path) -> String {
+    let stem = archive_file
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    format!("{}-", stem)
+}
+
+pub fn year_file(archive_file: &Path, year: i32) -> PathBuf {
+    archive_file.with_file_name(format!("{}{}{}", prefix(archive_file), year, EXTENSION))
+}
+
+pub fn rotated_years(vfs: &dyn Vfs, archive_file: &Path) -> io::Result<Vec<i32>> {
+    let dir = archive_file.parent().unwrap_or(Path::new(""));
+    let prefix = prefix(archive_file);
+    let mut years = vec![];
+    for path in vfs.list(dir)? {
+        let year = path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .and_then(|file_name| file_name.strip_prefix(&prefix))
+            .and_then(|rest| rest.strip_suffix(EXTENSION))
+            .and_then(|year| year.parse().ok());
+        if let Some(year) = year {
+            years.push(year);
+        }
+    }
+    years.sort();
+    Ok(years)
+}
+
+fn to_json(bill: &Bill) -> Value {
+    let fields = bill_fields(bill);
+    let map: Map<String, Value> = BILL_COLUMNS
+        .iter()
+        .zip(fields)
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(column, value)| (column.to_string(), Value::String(value)))
+        .collect();
+    Value::Object(map)
+}
+
+fn from_json(value: &Value, default_currency: &str) -> Option<Bill> {
+    let fields: Vec<String> = BILL_COLUMNS
+        .iter()
+        .map(|column| {
+            value
+                .get(column)
+                .and_then(Value::as_str)
+                .unwrap_or("")
+                .to_string()
+        })
+        .collect();
+    parse_bill(&fields, default_currency).ok()
+}
+
+/// Encrypts a year file's content like the bills file, when that is encrypted.
+#[cfg(feature = "encryption")]
+fn seal(data: Vec<u8>) -> io::Result<Vec<u8>> {
+    match crate::config::passphrase() {
+        Some(passphrase) => Ok(encryption::encrypt(&data, &passphrase)?),
+        None => Ok(data),
+    }
+}
+
+#[cfg(not(feature = "encryption"))]
+fn seal(data: Vec<u8>) -> io::Result<Vec<u8>> {
+    Ok(data)
+}
+
+/// Decrypts a year file's content; files rotated before encryption was on
+/// are read as they are.
+#[cfg(feature = "encryption")]
+fn unseal(data: Vec<u8>) -> io::Result<Vec<u8>> {
+    if !encryption::is_encrypted(&data) {
+        return Ok(data);
+    }
+    match crate::config::passphrase() {
+        Some(passphrase) => Ok(encryption::decrypt(&data, &passphrase)?.to_vec()),
+        None => Err(locked()),
+    }
+}
+
+#[cfg(not(feature = "encryption"))]
+fn unseal(data: Vec<u8>) -> io::Result<Vec<u8>> {
+    match data.starts_with(b"\x1f\x8b") {
+        true => Ok(data),
+        false => Err(locked()),
+    }
+}
+
+fn locked() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "year file is encrypted, turn on the encrypt setting to read it",
+    )
+}
+
+pub fn load_year(vfs: &dyn Vfs, file_name: &Path, default_currency: &str) -> io::Result<Bills> {
+    let data = unseal(vfs.read(file_name)?)?;
+    let values: Vec<Value> = serde_json::from_reader(GzDecoder::new(data.as_slice()))?;
+    let mut bills = Bills::new();
+    for bill in values
+        .iter()
+        .filter_map(|value| from_json(value, default_currency))
+    {
+        bills.add_bill(bill);
+    }
+    Ok(bills)
+}
+
+/// Adds `bills` to the file for `year`, keeping bills rotated into it before.
+pub fn save_year(
+    vfs: &dyn Vfs,
+    archive_file: &Path,
+    year: i32,
+    bills: &[&Bill],
+    default_currency: &str,
+) -> io::Result<PathBuf> {
+    let file_name = year_file(archive_file, year);
+    let mut year_bills = match load_year(vfs, &file_name, default_currency) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Bills::new(),
+        result => result?,
+    };
+    for bill in bills {
+        let mut bill = (*bill).clone();
+        if year_bills.list.contains_key(&bill.id) {
+            bill.id = year_bills.next_id();
+        }
+        year_bills.add_bill(bill);
+    }
+    let mut list = year_bills.view_bill();
+    list.sort_by_key(|bill| (bill.paid, bill.id));
+    let values: Vec<Value> = list.into_iter().map(to_json).collect();
+
+    let mut encoder = GzEncoder::new(vec![], Compression::default());
+    serde_json::to_writer(&mut encoder, &values)?;
+    let data = seal(encoder.finish()?)?;
+    vfs.write_atomic(&file_name, &data)?;
+    Ok(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::bill, Payment};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn rotated_years_load_back() {
+        let memory = vfs::Memory::new();
+        let archive_file = Path::new("bills/archive.csv");
+        let mut archive = Bills::new();
+        let mut rent = bill(1, "Rent, flat", "500");
+        rent.paid = NaiveDate::from_ymd_opt(2022, 12, 30);
+        rent.payments = vec![Payment {
+            amount: rent.amount,
+            date: rent.paid.unwrap(),
+        }];
+        rent.notes = Some("said \"thanks\"".to_string());
+        archive.add_bill(rent);
+        let mut water = bill(2, "Water", "30");
+        water.paid = NaiveDate::from_ymd_opt(2024, 1, 5);
+        archive.add_bill(water);
+
+        let years = closed_years(&archive, 2024);
+        assert_eq!(years.keys().collect::<Vec<_>>(), [&2022]);
+        let rent = &archive.list[&1];
+        let file_name = save_year(&memory, archive_file, 2022, &[rent], "EUR").unwrap();
+        assert_eq!(file_name, Path::new("bills/archive-2022.json.gz"));
+        assert_eq!(rotated_years(&memory, archive_file).unwrap(), [2022]);
+        let loaded = load_year(&memory, &file_name, "EUR").unwrap();
+        assert_eq!(loaded.list[&1], *rent);
+    }
+}
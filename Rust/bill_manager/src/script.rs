@@ -0,0 +1,198 @@
+use crate::{activity, config, dates, expr, locale, Bill, Bills, DATE_FORMAT};
+use bill_core::{Money, Priority};
+use chrono::{Local, NaiveDate};
+use flatfile::Writer;
+use std::{
+    io::{self, BufRead, Write},
+    path::Path,
+};
+use vfs::Vfs;
+
+const COMMANDS: &str = "add;name;amount[;due[;category]], update;bill;amount[;due], \
+    pay;bill[;date], payment;bill;amount[;date], remove;bill, list";
+
+fn bill_id(bills: &Bills, bill: &str) -> Result<i64, String> {
+    if let Ok(id) = bill.strip_prefix('#').unwrap_or(bill).parse() {
+        if bills.list.contains_key(&id) {
+            return Ok(id);
+        }
+    }
+    match bills.with_name(bill).as_slice() {
+        [bill] => Ok(bill.id),
+        [] => Err(format!("no bill {}", bill)),
+        _ => Err(format!("several bills are named {}, use an id", bill)),
+    }
+}
+
+fn amount(amount: &str, max: Money) -> Result<Money, String> {
+    match validate::non_negative(expr::evaluate(amount)?).map_err(|e| e.to_string())? {
+        amount if amount > max => Err(format!("amount cannot be more than {}", max)),
+        amount => Ok(amount),
+    }
+}
+
+fn date(date: Option<&&str>) -> Result<Option<NaiveDate>, String> {
+    let date = match date.filter(|date| !date.is_empty()) {
+        Some(date) => date,
+        None => return Ok(None),
+    };
+    validate::date(date, DATE_FORMAT)
+        .ok()
+        .or_else(|| config::parse_date(date))
+        .or_else(|| dates::parse(date, Local::now().date_naive(), locale::current()))
+        .map(Some)
+        .ok_or_else(|| format!("invalid date {}", date))
+}
+
+fn run_command(
+    fields: &[&str],
+    bills: &mut Bills,
+    default_currency: &str,
+    max: Money,
+    out: &mut impl Write,
+) -> Result<String, String> {
+    let today = Local::now().date_naive();
+    match fields {
+        ["add", name, rest @ ..] if !rest.is_empty() && !name.is_empty() => {
+            let id = bills.next_id();
+            bills.add_bill(Bill {
+                id,
+                name: name.to_string(),
+                amount: amount(rest[0], max)?,
+                due: date(rest.get(1))?,
+                recurrence: None,
+                paid: None,
+                category: rest.get(2).filter(|c| !c.is_empty()).map(|c| c.to_string()),
+                currency: default_currency.to_string(),
+                payments: vec![],
+                notes: None,
+                payee: None,
+                split: None,
+                method: None,
+                attachments: vec![],
+                original_due: None,
+                postponed: 0,
+                priority: Priority::Normal,
+            });
+            Ok(format!("ok;add;{}", id))
+        }
+        ["update", bill, rest @ ..] if !rest.is_empty() => {
+            let id = bill_id(bills, bill)?;
+            let amount = amount(rest[0], max)?;
+            let due = match rest.get(1) {
+                Some(_) => date(rest.get(1))?,
+                None => bills.list[&id].due,
+            };
+            let bill = bills.list.get_mut(&id).ok_or("bill not found")?;
+            bill.amount = amount;
+            bill.due = due;
+            Ok(format!("ok;update;{}", id))
+        }
+        ["pay", bill, rest @ ..] => {
+            let id = bill_id(bills, bill)?;
+            bills.pay_bill(id, date(rest.first())?.unwrap_or(today));
+            Ok(format!("ok;pay;{}", id))
+        }
+        ["payment", bill, paid, rest @ ..] => {
+            let id = bill_id(bills, bill)?;
+            let paid = amount(paid, max)?;
+            bills.record_payment(id, paid, date(rest.first())?.unwrap_or(today));
+            Ok(format!("ok;payment;{}", id))
+        }
+        ["remove", bill] => {
+            let id = bill_id(bills, bill)?;
+            bills.remove_bill(id);
+            Ok(format!("ok;remove;{}", id))
+        }
+        ["list"] => {
+            // Quoted like CSV, so names with a `;` keep the fields apart.
+            let writer = Writer {
+                delimiter: ';',
+                ..Writer::default()
+            };
+            let format_date = |date: Option<NaiveDate>| {
+                date.map(|date| date.format(DATE_FORMAT).to_string())
+                    .unwrap_or_default()
+            };
+            let list = bills.view_by_due();
+            for bill in &list {
+                let fields = [
+                    "bill".to_string(),
+                    bill.id.to_string(),
+                    bill.name.clone(),
+                    bill.amount.to_string(),
+                    bill.currency.clone(),
+                    format_date(bill.due),
+                    format_date(bill.paid),
+                ];
+                write!(out, "{}", writer.record(&fields)).map_err(|e| e.to_string())?;
+            }
+            Ok(format!("ok;list;{}", list.len()))
+        }
+        _ => Err(format!("unknown command, use {}", COMMANDS)),
+    }
+}
+
+/// Runs `;`-separated commands, one per line, and answers each with an
+/// `ok;...` or `error;line;message` line. Returns the number of errors.
+pub fn run(
+    input: impl BufRead,
+    out: &mut impl Write,
+    bills: &mut Bills,
+    vfs: &dyn Vfs,
+    log_file: &Path,
+    default_currency: &str,
+    max: Money,
+) -> io::Result<usize> {
+    let mut errors = 0;
+    for (num, line) in input.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(';').map(str::trim).collect();
+        let before = bills.list.clone();
+        let result = run_command(&fields, bills, default_currency, max, out);
+        let changes = activity::changes(&before, bills);
+        activity::append(vfs, log_file, Local::now().naive_local(), &changes)?;
+        match result {
+            Ok(result) => writeln!(out, "{}", result)?,
+            Err(e) => {
+                errors += 1;
+                writeln!(out, "error;{};{}", num + 1, e)?;
+            }
+        }
+    }
+    Ok(errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers_each_command() {
+        let memory = vfs::Memory::new();
+        let log_file = Path::new("bills.log");
+        let input = "add;Rent;1200;2024-03-01\npayment;Rent;200\nremove;Gym\nlist\n";
+        let mut out = vec![];
+        let mut bills = Bills::new();
+        let max = "10000".parse().unwrap();
+        let errors = run(
+            input.as_bytes(),
+            &mut out,
+            &mut bills,
+            &memory,
+            log_file,
+            "EUR",
+            max,
+        );
+        assert_eq!(errors.unwrap(), 1);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "ok;add;1\nok;payment;1\nerror;3;no bill Gym\nbill;1;Rent;1200.00;EUR;2024-03-01;\nok;list;1\n"
+        );
+        assert_eq!(activity::load(&memory, log_file).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::Bill;
+use bill_core::Money;
+use errors::Diagnostic;
+use std::ops::Range;
+
+#[derive(Debug)]
+enum Term {
+    Name(String),
+    Category(String),
+    AmountAbove(Money, bool),
+    AmountBelow(Money, bool),
+    AmountBetween(Option<Money>, Option<Money>),
+    AmountEquals(Money),
+}
+impl Term {
+    /// Reads `term`, which starts at byte `start` of the query.
+    fn parse(term: &str, start: usize) -> Result<Term, Diagnostic> {
+        let error = |message: &str, span: Range<usize>| {
+            Diagnostic::new(message, 1).at(start + span.start..start + span.end)
+        };
+        // Amounts come last in a term, except for the lower end of a range.
+        let amount_at = |value: &str, offset: usize| {
+            value
+                .parse::<Money>()
+                .map_err(|_| error("invalid amount", offset..offset + value.len()))
+        };
+        let amount = |value: &str| amount_at(value, term.len() - value.len());
+
+        if let Some(value) = term.strip_prefix(">=") {
+            Ok(Term::AmountAbove(amount(value)?, true))
+        } else if let Some(value) = term.strip_prefix('>') {
+            Ok(Term::AmountAbove(amount(value)?, false))
+        } else if let Some(value) = term.strip_prefix("<=") {
+            Ok(Term::AmountBelow(amount(value)?, true))
+        } else if let Some(value) = term.strip_prefix('<') {
+            Ok(Term::AmountBelow(amount(value)?, false))
+        } else if let Some(value) = term.strip_prefix('=') {
+            Ok(Term::AmountEquals(amount(value)?))
+        } else if let Some((min, max)) = term.split_once("..") {
+            let min = match min {
+                "" => None,
+                min => Some(amount_at(min, 0)?),
+            };
+            let max = match max {
+                "" => None,
+                max => Some(amount(max)?),
+            };
+            match (min, max) {
+                (None, None) => Err(error("a range needs a bound", 0..term.len())),
+                (Some(min), Some(max)) if min > max => {
+                    Err(error("the range starts above its end", 0..term.len()))
+                }
+                (min, max) => Ok(Term::AmountBetween(min, max)),
+            }
+        } else if let Some(category) = term.strip_prefix("cat:") {
+            Ok(Term::Category(category.to_lowercase()))
+        } else {
+            Ok(Term::Name(term.to_lowercase()))
+        }
+    }
+    fn matches(&self, bill: &Bill) -> bool {
+        match self {
+            Term::Name(name) => bill.name.to_lowercase().contains(name),
+            Term::Category(category) => bill
+                .category
+                .as_ref()
+                .is_some_and(|bill_category| bill_category.to_lowercase().contains(category)),
+            Term::AmountAbove(min, inclusive) => {
+                bill.amount > *min || (*inclusive && bill.amount == *min)
+            }
+            Term::AmountBelow(max, inclusive) => {
+                bill.amount < *max || (*inclusive && bill.amount == *max)
+            }
+            Term::AmountBetween(min, max) => {
+                min.is_none_or(|min| bill.amount >= min) && max.is_none_or(|max| bill.amount <= max)
+            }
+            Term::AmountEquals(amount) => bill.amount == *amount,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Query {
+    terms: Vec<Term>,
+}
+impl Query {
+    /// Reads a query, pointing at the term at fault if it can't.
+    pub fn parse(query: &str) -> Result<Query, Diagnostic> {
+        let mut terms = vec![];
+        let mut start = 0;
+        for term in query.split_whitespace() {
+            start += query[start..].find(term).unwrap_or(0);
+            terms.push(Term::parse(term, start)?);
+            start += term.len();
+        }
+        Ok(Query { terms })
+    }
+    pub fn matches(&self, bill: &Bill) -> bool {
+        self.terms.iter().all(|term| term.matches(bill))
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{Bill, DATE_FORMAT};
+use bill_core::Money;
+use chrono::NaiveDate;
+use errors::Diagnostic;
+use flatfile::{Format, Writer};
+use std::{cmp::Reverse, collections::BTreeMap, io, path::Path};
+use vfs::Vfs;
+
+#[derive(Debug)]
+pub struct Debt {
+    pub from: String,
+    pub to: String,
+    pub amount: Money,
+}
+
+#[derive(Debug, Clone)]
+pub struct Transfer {
+    pub date: NaiveDate,
+    pub from: String,
+    pub to: String,
+    pub amount: Money,
+    pub currency: String,
+}
+
+pub type Balances = BTreeMap<String, BTreeMap<String, Money>>;
+
+pub fn balances<'a>(bills: impl Iterator<Item = &'a Bill>, transfers: &[Transfer]) -> Balances {
+    let mut balances: Balances = BTreeMap::new();
+    for bill in bills {
+        let split = match &bill.split {
+            Some(split) => split,
+            None => continue,
+        };
+        let balance = balances.entry(bill.currency.clone()).or_default();
+        for (person, owed) in split.owed(bill.paid_amount()) {
+            *balance.entry(split.payer.clone()).or_default() += owed;
+            *balance.entry(person.to_string()).or_default() -= owed;
+        }
+    }
+    for transfer in transfers {
+        let balance = balances.entry(transfer.currency.clone()).or_default();
+        *balance.entry(transfer.from.clone()).or_default() += transfer.amount;
+        *balance.entry(transfer.to.clone()).or_default() -= transfer.amount;
+    }
+    balances
+}
+
+pub fn settle<'a>(
+    bills: impl Iterator<Item = &'a Bill>,
+    transfers: &[Transfer],
+) -> BTreeMap<String, Vec<Debt>> {
+    balances(bills, transfers)
+        .into_iter()
+        .map(|(currency, balance)| (currency, net_debts(balance)))
+        .collect()
+}
+
+fn net_debts(balance: BTreeMap<String, Money>) -> Vec<Debt> {
+    let mut creditors: Vec<(String, Money)> = vec![];
+    let mut debtors: Vec<(String, Money)> = vec![];
+    for (person, amount) in balance {
+        if amount > Money::ZERO {
+            creditors.push((person, amount));
+        } else if amount.is_negative() {
+            debtors.push((person, amount.abs()));
+        }
+    }
+    creditors.sort_by_key(|&(_, amount)| Reverse(amount));
+    debtors.sort_by_key(|&(_, amount)| Reverse(amount));
+
+    let mut debts = vec![];
+    let (mut creditor, mut debtor) = (0, 0);
+    while creditor < creditors.len() && debtor < debtors.len() {
+        let amount = creditors[creditor].1.min(debtors[debtor].1);
+        debts.push(Debt {
+            from: debtors[debtor].0.clone(),
+            to: creditors[creditor].0.clone(),
+            amount,
+        });
+        creditors[creditor].1 -= amount;
+        debtors[debtor].1 -= amount;
+        if creditors[creditor].1 == Money::ZERO {
+            creditor += 1;
+        }
+        if debtors[debtor].1 == Money::ZERO {
+            debtor += 1;
+        }
+    }
+    debts
+}
+
+fn parse_transfer(fields: &[String]) -> Option<Transfer> {
+    match fields {
+        [date, from, to, amount, code] => Some(Transfer {
+            date: validate::date(date, DATE_FORMAT).ok()?,
+            from: from.to_string(),
+            to: to.to_string(),
+            amount: amount.parse().ok()?,
+            currency: validate::currency(code).ok()?,
+        }),
+        _ => None,
+    }
+}
+
+pub fn load_transfers(vfs: &dyn Vfs, input_file: &Path) -> io::Result<Vec<Transfer>> {
+    let buffer = match vfs.read_to_string(input_file) {
+        Ok(buffer) => buffer,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+
+    let mut transfers = vec![];
+    for record in flatfile::parse(&buffer, Format::default()).records {
+        let result = record.map_err(Diagnostic::from).and_then(|record| {
+            parse_transfer(&record.fields)
+                .ok_or_else(|| Diagnostic::new("invalid transfer", record.line))
+        });
+        match result {
+            Ok(transfer) => transfers.push(transfer),
+            Err(e) => tracing::warn!("{}", e.render(input_file.display(), &buffer)),
+        }
+    }
+    Ok(transfers)
+}
+
+pub fn save_transfers(vfs: &dyn Vfs, file_name: &Path, transfers: &[Transfer]) -> io::Result<()> {
+    let header = ["date", "from", "to", "amount", "currency"];
+    let rows = transfers.iter().map(|transfer| {
+        [
+            transfer.date.format(DATE_FORMAT).to_string(),
+            transfer.from.clone(),
+            transfer.to.clone(),
+            transfer.amount.to_string(),
+            transfer.currency.clone(),
+        ]
+    });
+    let text = Writer::default().table(&header, rows);
+    vfs.write(file_name, text.as_bytes())
+}
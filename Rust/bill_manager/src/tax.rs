@@ -0,0 +1,95 @@
+use crate::{currency::Rates, Bill, DATE_FORMAT, UNCATEGORIZED};
+use bill_core::Money;
+use chrono::{Datelike, NaiveDate};
+use flatfile::Writer;
+use std::{collections::BTreeMap, io, path::Path};
+use vfs::Vfs;
+
+#[derive(Debug, Default)]
+pub struct YearSummary {
+    pub by_category: BTreeMap<String, Money>,
+    pub total: Money,
+    pub unconverted: usize,
+}
+
+pub struct TaxRow<'a> {
+    pub date: NaiveDate,
+    pub bill: &'a Bill,
+    pub amount: Money,
+}
+
+fn category(bill: &Bill) -> &str {
+    bill.category.as_deref().unwrap_or(UNCATEGORIZED)
+}
+
+pub fn is_tax_category(category: &str, tax_categories: &[String]) -> bool {
+    tax_categories
+        .iter()
+        .any(|tax| tax.eq_ignore_ascii_case(category))
+}
+
+pub fn year_summary<'a>(
+    bills: impl Iterator<Item = &'a Bill>,
+    year: i32,
+    rates: &Rates,
+    base: &str,
+) -> YearSummary {
+    let mut summary = YearSummary::default();
+    for bill in bills {
+        for payment in bill.payments.iter().filter(|p| p.date.year() == year) {
+            match rates.convert(payment.amount, &bill.currency, base) {
+                Some(amount) => {
+                    summary.total += amount;
+                    *summary
+                        .by_category
+                        .entry(category(bill).to_string())
+                        .or_default() += amount;
+                }
+                None => summary.unconverted += 1,
+            }
+        }
+    }
+    summary
+}
+
+pub fn tax_rows<'a>(
+    bills: impl Iterator<Item = &'a Bill>,
+    year: i32,
+    tax_categories: &[String],
+) -> Vec<TaxRow<'a>> {
+    let mut rows: Vec<TaxRow> = bills
+        .filter(|bill| is_tax_category(category(bill), tax_categories))
+        .flat_map(|bill| {
+            bill.payments
+                .iter()
+                .filter(|payment| payment.date.year() == year)
+                .map(move |payment| TaxRow {
+                    date: payment.date,
+                    bill,
+                    amount: payment.amount,
+                })
+        })
+        .collect();
+    rows.sort_by(|a, b| a.date.cmp(&b.date).then(a.bill.name.cmp(&b.bill.name)));
+    rows
+}
+
+pub fn save_tax_rows(vfs: &dyn Vfs, file_name: &Path, rows: &[TaxRow]) -> io::Result<()> {
+    let header = ["date", "bill", "category", "amount", "currency", "payee"];
+    let rows = rows.iter().map(|row| {
+        let payee = match row.bill.payee {
+            Some(payee) => payee.to_string(),
+            None => "".to_string(),
+        };
+        [
+            row.date.format(DATE_FORMAT).to_string(),
+            row.bill.name.clone(),
+            category(row.bill).to_string(),
+            row.amount.to_string(),
+            row.bill.currency.clone(),
+            payee,
+        ]
+    });
+    let text = Writer::default().table(&header, rows);
+    vfs.write(file_name, text.as_bytes())
+}
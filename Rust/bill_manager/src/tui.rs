@@ -0,0 +1,439 @@
+use crate::{
+    config::{self, Config},
+    currency, dates,
+    fees::LateFees,
+    locale, save_bills, totals_by_currency, Bill, Bills, Filter, UNCATEGORIZED,
+};
+use bill_core::{Money, Priority};
+use chrono::{Local, NaiveDate};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    DefaultTerminal, Frame,
+};
+use render::Align;
+use std::{io, path::Path};
+
+enum FormKind {
+    Add,
+    Edit(i64),
+}
+
+struct Form {
+    kind: FormKind,
+    /// The message id of each field's label, with what was typed into it.
+    fields: Vec<(&'static str, String)>,
+    focus: usize,
+}
+impl Form {
+    fn add(config: &Config) -> Form {
+        Form {
+            kind: FormKind::Add,
+            fields: vec![
+                ("bills-field-name", String::new()),
+                ("bills-field-amount", String::new()),
+                ("bills-field-due", String::new()),
+                (
+                    "bills-field-category",
+                    config.default_category.clone().unwrap_or_default(),
+                ),
+            ],
+            focus: 0,
+        }
+    }
+    fn edit(bill: &Bill) -> Form {
+        let due = match bill.due {
+            Some(due) => config::format_date(due),
+            None => String::new(),
+        };
+        Form {
+            kind: FormKind::Edit(bill.id),
+            fields: vec![
+                ("bills-field-amount", bill.amount.to_string()),
+                ("bills-field-due", due),
+            ],
+            focus: 0,
+        }
+    }
+    fn value(&self, label: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| *field == label)
+            .map(|(_, value)| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+    fn move_focus(&mut self, forward: bool) {
+        let count = self.fields.len();
+        self.focus = if forward {
+            (self.focus + 1) % count
+        } else {
+            (self.focus + count - 1) % count
+        };
+    }
+}
+
+struct App<'a> {
+    bills: &'a mut Bills,
+    data_file: &'a Path,
+    config: &'a Config,
+    max_amount: Money,
+    fees: LateFees,
+    state: ListState,
+    form: Option<Form>,
+    message: String,
+    quit: bool,
+}
+impl App<'_> {
+    fn upcoming(&self) -> Vec<&Bill> {
+        self.bills
+            .view_by_due()
+            .into_iter()
+            .filter(|bill| Filter::Unpaid.matches(bill))
+            .collect()
+    }
+    fn selected(&self) -> Option<&Bill> {
+        let index = self.state.selected()?;
+        self.upcoming().get(index).copied()
+    }
+    fn save(&mut self) {
+        if let Err(e) = save_bills(self.data_file, self.bills) {
+            self.message = i18n::t!("bills-save-failed", error = e);
+        }
+    }
+    fn handle_key(&mut self, code: KeyCode) {
+        if self.form.is_some() {
+            self.handle_form_key(code);
+            return;
+        }
+
+        let count = self.upcoming().len();
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Down | KeyCode::Char('j') if count > 0 => {
+                let next = self.state.selected().map_or(0, |index| index + 1);
+                self.state.select(Some(next.min(count - 1)));
+            }
+            KeyCode::Up | KeyCode::Char('k') if count > 0 => {
+                let previous = self
+                    .state
+                    .selected()
+                    .map_or(0, |index| index.saturating_sub(1));
+                self.state.select(Some(previous));
+            }
+            KeyCode::Char('a') => self.form = Some(Form::add(self.config)),
+            KeyCode::Char('e') => match self.selected() {
+                Some(bill) => self.form = Some(Form::edit(bill)),
+                None => self.message = i18n::t!("bills-none-selected"),
+            },
+            KeyCode::Char('p') => self.pay_selected(),
+            _ => {}
+        }
+    }
+    fn handle_form_key(&mut self, code: KeyCode) {
+        let form = match self.form.as_mut() {
+            Some(form) => form,
+            None => return,
+        };
+        match code {
+            KeyCode::Esc => {
+                self.form = None;
+                self.message.clear();
+            }
+            KeyCode::Tab | KeyCode::Down => form.move_focus(true),
+            KeyCode::BackTab | KeyCode::Up => form.move_focus(false),
+            KeyCode::Backspace => {
+                form.fields[form.focus].1.pop();
+            }
+            KeyCode::Char(c) => form.fields[form.focus].1.push(c),
+            KeyCode::Enter => {
+                if let Some(form) = self.form.take() {
+                    if let Err(e) = self.submit(&form) {
+                        self.message = e;
+                        self.form = Some(form);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    fn submit(&mut self, form: &Form) -> Result<(), String> {
+        let amount: Money = form
+            .value("bills-field-amount")
+            .ok_or_else(|| i18n::t!("bills-amount-required"))?
+            .parse()
+            .map_err(|_| i18n::t!("bills-amount-not-a-number"))?;
+        if validate::non_negative(amount).is_err() || amount > self.max_amount {
+            return Err(i18n::t!("bills-amount-range", max = self.max_amount));
+        }
+        let due = match form.value("bills-field-due") {
+            Some(due) => Some(
+                config::parse_date(due)
+                    .or_else(|| dates::parse(due, Local::now().date_naive(), locale::current()))
+                    .ok_or_else(|| {
+                        i18n::t!("bills-enter-date", example = config::date_example())
+                    })?,
+            ),
+            None => None,
+        };
+
+        match &form.kind {
+            FormKind::Add => {
+                let name = form
+                    .value("bills-field-name")
+                    .ok_or_else(|| i18n::t!("bills-name-required"))?
+                    .to_string();
+                let id = self.bills.next_id();
+                self.bills.add_bill(Bill {
+                    id,
+                    name,
+                    amount,
+                    due,
+                    recurrence: None,
+                    paid: None,
+                    category: form.value("bills-field-category").map(str::to_string),
+                    currency: self.config.currency.clone(),
+                    payments: vec![],
+                    notes: None,
+                    payee: None,
+                    split: None,
+                    method: None,
+                    attachments: vec![],
+                    original_due: None,
+                    postponed: 0,
+                    priority: Priority::Normal,
+                });
+                self.message = i18n::t!("bills-added");
+            }
+            FormKind::Edit(id) => {
+                let bill = self
+                    .bills
+                    .list
+                    .get(id)
+                    .ok_or_else(|| i18n::t!("bills-not-found"))?;
+                let (recurrence, notes, payee) = (bill.recurrence, bill.notes.clone(), bill.payee);
+                self.bills
+                    .update_bill(*id, amount, due, recurrence, notes, payee);
+                self.message = i18n::t!("bills-updated");
+            }
+        }
+        self.save();
+        Ok(())
+    }
+    fn pay_selected(&mut self) {
+        let (id, name) = match self.selected() {
+            Some(bill) => (bill.id, bill.name.clone()),
+            None => {
+                self.message = i18n::t!("bills-none-selected");
+                return;
+            }
+        };
+        self.bills.pay_bill(id, Local::now().date_naive());
+        self.message = i18n::t!("bills-paid-name", name = name);
+        self.save();
+
+        let count = self.upcoming().len();
+        if self.state.selected().is_some_and(|index| index >= count) {
+            self.state.select(count.checked_sub(1));
+        }
+    }
+    fn draw(&mut self, frame: &mut Frame) {
+        let today = Local::now().date_naive();
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(5),
+                Constraint::Length(10),
+                Constraint::Length(1),
+            ])
+            .split(frame.area());
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(rows[0]);
+
+        let items: Vec<ListItem> = self
+            .upcoming()
+            .into_iter()
+            .map(|bill| {
+                let due = match bill.due {
+                    Some(due) => config::format_date(due),
+                    None => "-".repeat(config::date_example().len()),
+                };
+                let line = format!(
+                    "{}  {}  {}",
+                    due,
+                    bill.name,
+                    currency::format_amount(bill.remaining(), &bill.currency)
+                );
+                let style = if bill.is_overdue(today) {
+                    Style::default().fg(Color::Red)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(line).style(style)
+            })
+            .collect();
+        let upcoming = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(i18n::t!("bills-tui-upcoming")),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(upcoming, panes[0], &mut self.state);
+
+        let categories: Vec<ListItem> = self
+            .bills
+            .view_by_category()
+            .into_iter()
+            .map(|(category, list)| {
+                let subtotal = totals_by_currency(
+                    list.iter()
+                        .map(|bill| (bill.currency.as_str(), bill.amount)),
+                );
+                ListItem::new(format!(
+                    "{}: {}",
+                    category,
+                    currency::format_totals(&subtotal)
+                ))
+            })
+            .collect();
+        frame.render_widget(
+            List::new(categories).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(i18n::t!("bills-tui-categories")),
+            ),
+            panes[1],
+        );
+
+        let (title, lines) = match &self.form {
+            Some(form) => {
+                let title = match &form.kind {
+                    FormKind::Add => i18n::t!("bills-tui-new"),
+                    FormKind::Edit(id) => match self.bills.list.get(id) {
+                        Some(bill) => i18n::t!("bills-tui-edit", name = bill.name),
+                        None => i18n::t!("bills-tui-edit", name = format!("#{}", id)),
+                    },
+                };
+                let lines = form
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (label, value))| {
+                        let marker = if index == form.focus { ">" } else { " " };
+                        format!("{} {} {}", marker, i18n::t!(label), value)
+                    })
+                    .collect();
+                (title, lines)
+            }
+            None => (i18n::t!("bills-tui-details"), self.detail_lines(today)),
+        };
+        frame.render_widget(
+            Paragraph::new(lines.join("\n"))
+                .block(Block::default().borders(Borders::ALL).title(title)),
+            rows[1],
+        );
+
+        let footer = if !self.message.is_empty() {
+            self.message.clone()
+        } else if self.form.is_some() {
+            i18n::t!("bills-tui-form-keys")
+        } else {
+            i18n::t!("bills-tui-keys")
+        };
+        frame.render_widget(Paragraph::new(footer), rows[2]);
+    }
+    fn detail_lines(&self, today: NaiveDate) -> Vec<String> {
+        let bill = match self.selected() {
+            Some(bill) => bill,
+            None => return vec![i18n::t!("bills-no-unpaid")],
+        };
+        let mut fields = vec![
+            ("bills-field-name", bill.name.clone()),
+            (
+                "bills-field-amount",
+                currency::format_amount(bill.amount, &bill.currency),
+            ),
+            (
+                "bills-field-remaining",
+                currency::format_amount(bill.remaining(), &bill.currency),
+            ),
+            (
+                "bills-field-category",
+                bill.category
+                    .as_deref()
+                    .unwrap_or(UNCATEGORIZED)
+                    .to_string(),
+            ),
+        ];
+        if let Some(recurrence) = bill.recurrence {
+            fields.push(("bills-field-repeats", recurrence.to_string()));
+        }
+        let late = self.fees.accrued(bill, today);
+        if late > Money::ZERO {
+            fields.push((
+                "bills-field-late",
+                currency::format_amount(late, &bill.currency),
+            ));
+        }
+        if let Some(notes) = &bill.notes {
+            fields.push(("bills-field-notes", notes.clone()));
+        }
+        let labels: Vec<String> = fields.iter().map(|(id, _)| i18n::t!(id)).collect();
+        let width = labels
+            .iter()
+            .map(|label| render::width(label))
+            .max()
+            .unwrap_or(0);
+        labels
+            .iter()
+            .zip(fields)
+            .map(|(label, (_, value))| {
+                format!("{} {}", render::pad(label, width, Align::Left), value)
+            })
+            .collect()
+    }
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> io::Result<()> {
+    while !app.quit {
+        terminal.draw(|frame| app.draw(frame))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                if app.form.is_none() {
+                    app.message.clear();
+                }
+                app.handle_key(key.code);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn run(
+    bills: &mut Bills,
+    data_file: &Path,
+    config: &Config,
+    max_amount: Money,
+    fees: LateFees,
+) -> io::Result<()> {
+    let mut app = App {
+        bills,
+        data_file,
+        config,
+        max_amount,
+        fees,
+        state: ListState::default().with_selected(Some(0)),
+        form: None,
+        message: String::new(),
+        quit: false,
+    };
+
+    let mut terminal = ratatui::try_init()?;
+    let result = event_loop(&mut terminal, &mut app);
+    ratatui::restore();
+    result
+}
@@ -0,0 +1,445 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::{fs, process};
+use tempfile::TempDir;
+
+fn bills(dir: &TempDir, data_file: &str) -> Command {
+    let mut cmd = Command::cargo_bin("Rust").unwrap();
+    cmd.current_dir(dir.path())
+        .env("XDG_CONFIG_HOME", dir.path())
+        .env("HOME", dir.path())
+        .env_remove("XDG_DATA_HOME")
+        .env_remove("XDG_CACHE_HOME")
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .env_remove("LANG")
+        .args(["-d", data_file]);
+    cmd
+}
+
+/// Output for snapshots, which review any change to what users see.
+fn stdout(cmd: &mut Command) -> String {
+    String::from_utf8(cmd.output().unwrap().stdout).unwrap()
+}
+
+fn stderr(cmd: &mut Command) -> String {
+    String::from_utf8(cmd.output().unwrap().stderr).unwrap()
+}
+
+#[test]
+fn add_list_pay_remove() {
+    let dir = tempfile::tempdir().unwrap();
+    bills(&dir, "bills.csv")
+        .write_stdin("add;Rent, flat;500;2026-11-01;Home\nadd;Gym;10*3\nlist\n")
+        .assert()
+        .success()
+        .stdout(
+            "ok;add;1\nok;add;2\n\
+             bill;1;Rent, flat;500.00;EUR;2026-11-01;\n\
+             bill;2;Gym;30.00;EUR;;\n\
+             ok;list;2\n",
+        );
+
+    bills(&dir, "bills.csv")
+        .write_stdin("pay;Rent, flat;2026-10-30\nremove;Gym\nlist\n")
+        .assert()
+        .success()
+        .stdout(
+            "ok;pay;1\nok;remove;2\n\
+             bill;1;Rent, flat;500.00;EUR;2026-11-01;2026-10-30\n\
+             ok;list;1\n",
+        );
+
+    bills(&dir, "bills.csv")
+        .arg("--json")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"name\": \"Rent, flat\""))
+        .stdout(predicate::str::contains("Gym").not());
+    let history = fs::read_to_string(dir.path().join("bills-history.log")).unwrap();
+    assert!(history.contains("#2 removed Gym €30.00"));
+}
+
+#[test]
+fn export_and_import() {
+    let dir = tempfile::tempdir().unwrap();
+    bills(&dir, "bills.csv")
+        .write_stdin("add;Rent;500\n")
+        .assert()
+        .success();
+    bills(&dir, "bills.csv")
+        .args(["--export", "backup.csv"])
+        .assert()
+        .success()
+        .stdout("bills exported\n");
+
+    bills(&dir, "other.csv")
+        .args(["--import", "backup.csv"])
+        .assert()
+        .success()
+        .stdout("1 bills imported\n");
+    bills(&dir, "other.csv")
+        .args(["--json", "-o", "other.json"])
+        .assert()
+        .success()
+        .stdout("");
+    let json = fs::read_to_string(dir.path().join("other.json")).unwrap();
+    assert!(json.contains("\"name\": \"Rent\""));
+}
+
+#[test]
+fn queries_leave_the_data_file_alone() {
+    let dir = tempfile::tempdir().unwrap();
+    let data = "#version=1\n\
+        name,amount,due,recurrence,paid,category,currency,id,payments,notes,payee,split,method,attachments,original_due,postponed,priority\n\
+        Rent,500.00,2020-01-01,monthly,,,EUR,1,,,,,,,,0,normal\n";
+    fs::write(dir.path().join("bills.csv"), data).unwrap();
+
+    bills(&dir, "bills.csv")
+        .arg("--json")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"due\": \"2020-02-01\""));
+    bills(&dir, "bills.csv")
+        .args(["--export", "backup.csv"])
+        .assert()
+        .success();
+    let backup = fs::read_to_string(dir.path().join("backup.csv")).unwrap();
+    assert!(backup.contains("2020-02-01"));
+    assert_eq!(
+        fs::read_to_string(dir.path().join("bills.csv")).unwrap(),
+        data
+    );
+    assert!(!dir.path().join("bills.csv.ids").exists());
+}
+
+#[test]
+fn lang_overrides_the_locale_setting() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("import.csv"), "name,amount\nRent,500\n").unwrap();
+    bills(&dir, "bills.csv")
+        .args(["--lang", "de", "--import", "import.csv"])
+        .env("BILL_MANAGER_LOCALE", "en")
+        .assert()
+        .success()
+        .stdout("1 Rechnungen importiert\n");
+    bills(&dir, "bills.csv")
+        .args(["--export", "backup.csv"])
+        .env("LANG", "de_CH.UTF-8")
+        .assert()
+        .success()
+        .stdout("Rechnungen exportiert\n");
+    bills(&dir, "bills.csv")
+        .args(["--lang", "fr"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("unsupported language fr"));
+}
+
+#[test]
+fn script_errors_name_the_line() {
+    let dir = tempfile::tempdir().unwrap();
+    bills(&dir, "bills.csv")
+        .write_stdin("# comment\npay;Nope\nadd;Rent;-5\nfly;away\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "error;2;no bill Nope\nerror;3;amount cannot be negative\nerror;4;unknown command",
+        ));
+}
+
+#[test]
+fn list_and_stats_snapshots() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("bills.csv"),
+        "name,amount,due,recurrence,paid,category,currency,id,payments\n\
+         \"Rent, flat\",500,2036-11-01,monthly,,Home,EUR,1,\n\
+         Gym,30,,,,,CHF,2,10@2020-01-02\n\
+         Phone,20,2020-03-01,,2020-02-28,,EUR,3,\n",
+    )
+    .unwrap();
+    insta::assert_snapshot!(
+        "list",
+        stdout(bills(&dir, "bills.csv").write_stdin("list\n"))
+    );
+    insta::assert_snapshot!("stats", stdout(bills(&dir, "bills.csv").arg("--json")));
+}
+
+#[test]
+fn error_snapshots() {
+    let dir = tempfile::tempdir().unwrap();
+    insta::assert_snapshot!(
+        "script_errors",
+        stdout(bills(&dir, "bills.csv").write_stdin("pay;Nope\nadd;Rent;-5\nfly;away\n"))
+    );
+    insta::assert_snapshot!(
+        "check_without_self_update",
+        stderr(bills(&dir, "bills.csv").arg("--check"))
+    );
+    fs::write(
+        dir.path().join("import.csv"),
+        "name,amount\nRent,500\n\"Gym,30\nPhone,abc\n",
+    )
+    .unwrap();
+    insta::assert_snapshot!(
+        "import_warnings",
+        stderr(bills(&dir, "bills.csv").args(["--import", "import.csv"]))
+    );
+}
+
+#[test]
+fn rejects_bad_arguments() {
+    let dir = tempfile::tempdir().unwrap();
+    bills(&dir, "bills.csv")
+        .args(["--tui", "--json"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("cannot be used with"));
+    bills(&dir, "bills.csv")
+        .args(["-o", "out.json"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--json"));
+    bills(&dir, "bills.csv")
+        .args(["--late-fee", "lots"])
+        .assert()
+        .code(2);
+    bills(&dir, "bills.csv")
+        .args(["--self-update", "--json"])
+        .assert()
+        .code(2);
+    bills(&dir, "bills.csv")
+        .arg("--check")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--self-update"));
+}
+
+#[test]
+fn refuses_a_locked_data_file() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("bills.csv.lock"),
+        format!("{}\n", process::id()),
+    )
+    .unwrap();
+    bills(&dir, "bills.csv")
+        .write_stdin("list\n")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(format!(
+            "bills.csv is in use by process {}",
+            process::id()
+        )));
+}
+
+#[test]
+fn import_warns_about_broken_lines() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(
+        dir.path().join("import.csv"),
+        "name,amount\nRent,500\n\"Gym,30\nPhone,abc\n",
+    )
+    .unwrap();
+    bills(&dir, "bills.csv")
+        .args(["--import", "import.csv"])
+        .assert()
+        .success()
+        .stdout("1 bills imported\n")
+        .stderr(predicate::str::contains(
+            "error: unterminated quote\n --> import.csv:3:1\n",
+        ))
+        .stderr(predicate::str::contains(
+            "--> import.csv:4:7\n  |\n4 | Phone,abc\n  |       ^^^\n",
+        ));
+}
+
+#[test]
+fn environment_overrides_config_file() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::create_dir(dir.path().join("bill_manager")).unwrap();
+    fs::write(
+        dir.path().join("bill_manager/config.toml"),
+        "currency = \"USD\"\nreminder_days = 3\n",
+    )
+    .unwrap();
+    bills(&dir, "bills.csv")
+        .write_stdin("add;Rent;500\n")
+        .assert()
+        .success();
+    bills(&dir, "bills.csv")
+        .env("BILL_MANAGER_CURRENCY", "CHF")
+        .write_stdin("add;Gym;30\nlist\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("bill;1;Rent;500.00;USD;;"))
+        .stdout(predicate::str::contains("bill;2;Gym;30.00;CHF;;"));
+    bills(&dir, "bills.csv")
+        .env("BILL_MANAGER_REMINDER_DAYS", "soon")
+        .write_stdin("list\n")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "invalid BILL_MANAGER_REMINDER_DAYS",
+        ));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn storage_setting_switches_backends() {
+    let dir = tempfile::tempdir().unwrap();
+    bills(&dir, "bills.json")
+        .env("BILL_MANAGER_STORAGE", "json")
+        .write_stdin("add;Rent, flat;500\nlist\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("bill;1;Rent, flat;500.00;EUR;;"));
+    let json = fs::read_to_string(dir.path().join("bills.json")).unwrap();
+    assert!(json.contains("\"name\": \"Rent, flat\""));
+    bills(&dir, "bills.json")
+        .env("BILL_MANAGER_STORAGE", "json")
+        .args(["--export", "backup.csv"])
+        .assert()
+        .success();
+    assert!(fs::read_to_string(dir.path().join("backup.csv"))
+        .unwrap()
+        .starts_with("#version=1\nname,amount,"));
+    bills(&dir, "bills.csv")
+        .env("BILL_MANAGER_STORAGE", "yaml")
+        .write_stdin("list\n")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("invalid BILL_MANAGER_STORAGE"));
+}
+
+#[cfg(feature = "encryption")]
+#[test]
+fn encrypt_setting_encrypts_the_data_file() {
+    let dir = tempfile::tempdir().unwrap();
+    bills(&dir, "bills.csv")
+        .write_stdin("add;Rent;500\n")
+        .assert()
+        .success();
+    bills(&dir, "bills.csv")
+        .env("BILL_MANAGER_ENCRYPT", "true")
+        .env("BILL_MANAGER_PASSPHRASE", "secret")
+        .write_stdin("add;Gym;30\nlist\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("bill;1;Rent;500.00;EUR;;"));
+    let data = fs::read(dir.path().join("bills.csv")).unwrap();
+    assert!(data.starts_with(b"STUDYENC"));
+    assert!(!String::from_utf8_lossy(&data).contains("Rent"));
+    bills(&dir, "bills.csv")
+        .env("BILL_MANAGER_ENCRYPT", "true")
+        .env("BILL_MANAGER_PASSPHRASE", "secret")
+        .write_stdin("list\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("bill;2;Gym;30.00;EUR;;"));
+    bills(&dir, "bills.csv")
+        .env("BILL_MANAGER_ENCRYPT", "true")
+        .env("BILL_MANAGER_PASSPHRASE", "wrong")
+        .write_stdin("list\n")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "caused by: wrong passphrase or damaged file",
+        ));
+}
+
+#[test]
+fn migrate_upgrades_old_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let old = "name,amount,due,recurrence,paid,category,currency,id\n\
+               Rent,500,,,2024-03-01,,EUR,1\n";
+    fs::write(dir.path().join("bills.csv"), old).unwrap();
+    bills(&dir, "bills.csv")
+        .args(["--migrate", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(
+            "bills.csv: format version 0 to 1\n  \
+             up 1: record paid dates as payments\n\
+             dry run, nothing was written\n",
+        );
+    assert_eq!(
+        fs::read_to_string(dir.path().join("bills.csv")).unwrap(),
+        old
+    );
+    bills(&dir, "bills.csv").arg("--migrate").assert().success();
+    let data = fs::read_to_string(dir.path().join("bills.csv")).unwrap();
+    assert!(data.starts_with("#version=1\n"));
+    assert!(data.ends_with(",EUR,1,500@2024-03-01\n"));
+    bills(&dir, "bills.csv")
+        .arg("--migrate")
+        .assert()
+        .success()
+        .stdout("bills.csv is at format version 1, nothing to migrate\n");
+    fs::write(dir.path().join("bills.csv"), format!("#version=9\n{}", old)).unwrap();
+    bills(&dir, "bills.csv")
+        .write_stdin("list\n")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "caused by: stored at version 9, newer than version 1 this build knows",
+        ));
+}
+
+#[test]
+fn first_run_creates_app_directories() {
+    let dir = tempfile::tempdir().unwrap();
+    bills(&dir, "bills.csv")
+        .write_stdin("list\n")
+        .assert()
+        .success();
+    for app_dir in [
+        "bill_manager",
+        ".local/share/bill_manager/backups",
+        ".cache/bill_manager",
+    ] {
+        assert!(dir.path().join(app_dir).is_dir(), "{} is missing", app_dir);
+    }
+}
+
+#[cfg(not(feature = "tui"))]
+#[test]
+fn slim_builds_explain_missing_features() {
+    let dir = tempfile::tempdir().unwrap();
+    bills(&dir, "bills.csv")
+        .arg("--tui")
+        .assert()
+        .code(1)
+        .stderr("error: tui is not built in, rebuild with the `tui` feature\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn unknown_arguments_run_plugins() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let plugin = dir.path().join("bill_manager-hello");
+    fs::write(
+        &plugin,
+        "#!/bin/sh\necho \"$BILL_MANAGER_DATA_FILE $*\"\necho \"$BILL_MANAGER_HANDSHAKE\"\n",
+    )
+    .unwrap();
+    fs::set_permissions(&plugin, fs::Permissions::from_mode(0o755)).unwrap();
+    bills(&dir, "bills.csv")
+        .env("PATH", dir.path())
+        .args(["hello", "--name", "Ann"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("bills.csv --name Ann\n"))
+        .stdout(predicate::str::contains(
+            "\"command\":\"hello\",\"args\":[\"--name\",\"Ann\"],\"data_file\":\"bills.csv\"",
+        ));
+    bills(&dir, "bills.csv")
+        .env("PATH", dir.path())
+        .arg("bye")
+        .assert()
+        .failure()
+        .stderr("error: no such plugin: bill_manager-bye is not on the PATH\n");
+}
@@ -0,0 +1,223 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{
+    borrow::ToOwned,
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use chrono::NaiveDate;
+use errors::FieldError;
+use ids::Counter;
+use thiserror::Error;
+
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub id: i64,
+    pub name: String,
+    pub email: Option<String>,
+    pub birthday: Option<NaiveDate>,
+}
+
+#[derive(Debug, Default)]
+pub struct Records {
+    list: BTreeMap<i64, Record>,
+}
+impl Records {
+    pub fn new() -> Self {
+        Self {
+            list: BTreeMap::new(),
+        }
+    }
+    pub fn add(&mut self, record: Record) {
+        self.list.insert(record.id, record);
+    }
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+    pub fn into_vec(self) -> Vec<Record> {
+        self.list.into_values().collect()
+    }
+    pub fn next_id(&self) -> i64 {
+        ids::sequential(self.list.keys().next_back().copied(), |id| {
+            self.list.contains_key(&id)
+        })
+    }
+    /// The next id from `counter`, which also remembers the ids of deleted
+    /// records so they are never handed out again.
+    pub fn issue_id(&self, counter: &mut Counter) -> i64 {
+        let largest = self.list.keys().next_back().copied().unwrap_or(0);
+        counter.last = counter.last.max(largest);
+        counter.next(|id| self.list.contains_key(&id))
+    }
+    pub fn search(&self, name: &str) -> Vec<&Record> {
+        self.list
+            .values()
+            .filter(|rec| rec.name.to_lowercase().contains(&name.to_lowercase()))
+            .collect()
+    }
+    pub fn get(&self, id: i64) -> Option<&Record> {
+        self.list.get(&id)
+    }
+    pub fn remove(&mut self, id: i64) -> Option<Record> {
+        self.list.remove(&id)
+    }
+    pub fn edit(
+        &mut self,
+        id: i64,
+        name: &str,
+        email: Option<String>,
+        birthday: Option<NaiveDate>,
+    ) {
+        self.list.insert(
+            id,
+            Record {
+                id,
+                name: name.to_string(),
+                email,
+                birthday,
+            },
+        );
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ParseError {
+    #[error("invalid id")]
+    InvalidId(#[from] core::num::ParseIntError),
+    #[error("empty record")]
+    EmptyRecord,
+    #[error("missing fields {0}")]
+    MissingField(String),
+    #[error("invalid date")]
+    InvalidDate(#[from] validate::Invalid),
+}
+
+/// Reads a record, saying which field is at fault if it can't.
+pub fn parse_record(fields: &[String]) -> Result<Record, FieldError<ParseError>> {
+    let id = match fields.first() {
+        Some(id) => id
+            .parse()
+            .map_err(|e| FieldError::new(0, ParseError::from(e)))?,
+        None => return Err(FieldError::new(0, ParseError::EmptyRecord)),
+    };
+    let name = match fields.get(1).filter(|name| !name.is_empty()) {
+        Some(name) => name.to_string(),
+        None => {
+            return Err(FieldError::new(
+                1,
+                ParseError::MissingField("name".to_owned()),
+            ))
+        }
+    };
+    let email = fields
+        .get(2)
+        .map(|email| email.to_string())
+        .filter(|email| !email.is_empty());
+    let birthday = match fields.get(3).filter(|date| !date.is_empty()) {
+        Some(date) => Some(
+            validate::date(date, DATE_FORMAT)
+                .map_err(|e| FieldError::new(3, ParseError::from(e)))?,
+        ),
+        None => None,
+    };
+
+    Ok(Record {
+        id,
+        name,
+        email,
+        birthday,
+    })
+}
+
+fn record_fields(record: &Record) -> Vec<String> {
+    let birthday = match record.birthday {
+        Some(date) => date.format(DATE_FORMAT).to_string(),
+        None => "".to_string(),
+    };
+    vec![
+        record.id.to_string(),
+        record.name.clone(),
+        record.email.clone().unwrap_or_default(),
+        birthday,
+    ]
+}
+
+pub fn format_record(record: &Record) -> String {
+    flatfile::write_record(&record_fields(record), ',')
+}
+
+#[cfg(feature = "storage")]
+impl storage::Rows for Records {
+    type Context = ();
+    const COLUMNS: &'static [&'static str] = &["id", "name", "email", "birthday"];
+    fn to_rows(&self) -> Vec<Vec<String>> {
+        self.list.values().map(record_fields).collect()
+    }
+    fn from_rows(rows: Vec<storage::Row>, _: &()) -> (Self, Vec<errors::Diagnostic>) {
+        let mut records = Records::new();
+        let mut skipped = vec![];
+        for row in rows {
+            match parse_record(&row.fields) {
+                Ok(record) => records.add(record),
+                Err(e) => skipped.push(row.diagnostic(e.error, e.index)),
+            }
+        }
+        (records, skipped)
+    }
+}
+
+#[cfg(feature = "undo")]
+impl undo::Table for Records {
+    type Key = i64;
+    type Value = Record;
+    fn get(&self, id: &i64) -> Option<&Record> {
+        self.list.get(id)
+    }
+    fn keys(&self) -> Vec<i64> {
+        self.list.keys().copied().collect()
+    }
+    fn insert(&mut self, id: i64, record: Record) {
+        self.list.insert(id, record);
+    }
+    fn remove(&mut self, id: &i64) {
+        self.list.remove(id);
+    }
+}
+
+#[cfg(feature = "undo")]
+impl undo::Fields for Record {
+    type Context = ();
+    fn to_fields(&self) -> Vec<String> {
+        record_fields(self)
+    }
+    fn from_fields(fields: &[String], _: &()) -> Option<Self> {
+        parse_record(fields).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_records_in_id_order() {
+        let mut records = Records::new();
+        for (id, name) in [(7, "Grace"), (2, "Ada")] {
+            records.edit(id, name, None, None);
+        }
+        assert_eq!(records.next_id(), 8);
+        assert_eq!(records.issue_id(&mut Counter::after(3)), 8);
+        assert_eq!(records.issue_id(&mut Counter::after(9)), 10);
+        let names: Vec<String> = records.into_vec().into_iter().map(|r| r.name).collect();
+        assert_eq!(names, ["Ada", "Grace"]);
+    }
+}
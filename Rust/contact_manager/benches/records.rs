@@ -0,0 +1,47 @@
+use chrono::NaiveDate;
+use contact_manager::{format_record, parse_records, Record};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::{hint::black_box, path::Path};
+
+const SIZES: [usize; 3] = [10_000, 100_000, 1_000_000];
+
+fn book(size: usize) -> String {
+    let mut file = String::from("id,name,email,birthday\n");
+    for id in 1..=size {
+        let birthday = NaiveDate::from_ymd_opt(1950 + (id % 60) as i32, 1 + (id % 12) as u32, 1);
+        file.push_str(&format_record(&Record {
+            id: id as i64,
+            name: format!("Contact {}, number {}", id % 9973, id),
+            email: Some(format!("contact{}@example.com", id)),
+            birthday: birthday.filter(|_| id % 3 == 0),
+        }));
+    }
+    file
+}
+
+fn records(c: &mut Criterion) {
+    let mut group = c.benchmark_group("records");
+    group.sample_size(10);
+    for size in SIZES {
+        let file = book(size);
+        group.throughput(Throughput::Elements(size as u64));
+
+        group.bench_with_input(BenchmarkId::new("load", size), &file, |b, file| {
+            b.iter(|| parse_records(black_box(file), Path::new("bench.csv")).unwrap())
+        });
+
+        let loaded = parse_records(&file, Path::new("bench.csv")).unwrap();
+        group.bench_with_input(BenchmarkId::new("search", size), &loaded, |b, loaded| {
+            b.iter(|| loaded.search(black_box("number 4242")).len())
+        });
+
+        let list = loaded.into_vec();
+        group.bench_with_input(BenchmarkId::new("save", size), &list, |b, list| {
+            b.iter(|| list.iter().map(format_record).collect::<String>())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, records);
+criterion_main!(benches);
@@ -0,0 +1,601 @@
+use crate::{Record, Records, DATE_FORMAT};
+use chrono::{NaiveDate, Utc};
+use clap::{builder::Styles, Parser, Subcommand, ValueEnum, ValueHint};
+use plugin::Plugin;
+use prompt::{Back, Terminal, UserInterface};
+use render::{Align, Table, Theme};
+use serde::{Deserialize, Serialize};
+use settings::{Layers, Settings};
+use std::{
+    env,
+    ffi::OsString,
+    fs::File,
+    io::{self, Write},
+    path::PathBuf,
+    process,
+};
+use storage::{Backend, Storage};
+use undo::{Changes, History, Journal};
+
+fn birthday(record: &Record) -> String {
+    match record.birthday {
+        Some(date) => date.format(DATE_FORMAT).to_string(),
+        None => "-".to_string(),
+    }
+}
+
+fn print_record_card(record: &Record) {
+    let fields = [
+        (i18n::t!("contacts-card-name"), record.name.clone()),
+        (
+            i18n::t!("contacts-card-email"),
+            record.email.clone().unwrap_or_else(|| "-".to_string()),
+        ),
+        (i18n::t!("contacts-card-birthday"), birthday(record)),
+    ];
+    let title = i18n::t!("contacts-card-title", id = record.id);
+    for line in render::card(&title, &fields) {
+        println!("{}", line);
+    }
+}
+
+fn print_record_table<'a>(records: impl IntoIterator<Item = &'a Record>) {
+    let mut table = Table::new(&[
+        i18n::t!("contacts-column-id"),
+        i18n::t!("contacts-column-name"),
+        i18n::t!("contacts-column-email"),
+        i18n::t!("contacts-column-birthday"),
+    ])
+    .align(0, Align::Right);
+    for record in records {
+        table.row(&[
+            record.id.to_string(),
+            record.name.clone(),
+            record.email.clone().unwrap_or_else(|| "-".to_string()),
+            birthday(record),
+        ]);
+    }
+    for line in table.render(render::terminal_width()) {
+        println!("{}", line);
+    }
+}
+
+fn load_records(store: &dyn Storage<Records>) -> io::Result<Records> {
+    let records = store.load()?;
+    tracing::debug!(
+        "loaded {} records from {}",
+        records.len(),
+        store.path().display()
+    );
+    Ok(records)
+}
+
+fn save_records(store: &dyn Storage<Records>, records: &Records) -> io::Result<()> {
+    store.save(records)?;
+    tracing::debug!(
+        "saved {} records to {}",
+        records.len(),
+        store.path().display()
+    );
+    Ok(())
+}
+
+const HISTORY_LIMIT: usize = 20;
+
+/// Where the last id handed out is kept, next to the contacts file.
+fn counter_file(store: &dyn Storage<Records>) -> PathBuf {
+    let mut file_name = store.path().file_name().unwrap_or_default().to_os_string();
+    file_name.push(".ids");
+    store.path().with_file_name(file_name)
+}
+
+/// The last id handed out, which encrypted contacts go without like the undo
+/// history, so their new ids start after the largest one left.
+fn load_counter(store: &dyn Storage<Records>) -> io::Result<ids::Counter> {
+    match store.encrypted() {
+        true => Ok(ids::Counter::default()),
+        false => ids::Counter::load(&*store.vfs(), &counter_file(store)),
+    }
+}
+
+fn save_counter(store: &dyn Storage<Records>, counter: &ids::Counter) -> io::Result<()> {
+    let vfs = store.vfs();
+    match store.encrypted() {
+        true => match vfs.remove_file(&counter_file(store)) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        },
+        false => counter.save(&*vfs, &counter_file(store)),
+    }
+}
+
+/// What takes back recent changes, kept next to the contacts file between runs.
+fn history_journal(store: &dyn Storage<Records>) -> Journal {
+    Journal::beside(store.path(), "undo")
+}
+
+/// The undo history, which encrypted contacts go without since it is plaintext.
+fn load_history(store: &dyn Storage<Records>) -> io::Result<History<Changes<i64, Record>>> {
+    match store.encrypted() {
+        true => Ok(History::new(HISTORY_LIMIT)),
+        false => history_journal(store).load_history(HISTORY_LIMIT, &()),
+    }
+}
+
+fn save_history(
+    store: &dyn Storage<Records>,
+    history: &History<Changes<i64, Record>>,
+) -> io::Result<()> {
+    match store.encrypted() {
+        true => history_journal(store).clear(),
+        false => history_journal(store).save_history(history),
+    }
+}
+
+fn change_records(
+    store: &dyn Storage<Records>,
+    recs: &mut Records,
+    changes: Changes<i64, Record>,
+) -> io::Result<()> {
+    let mut history = load_history(store)?;
+    history.execute(changes, recs);
+    save_records(store, recs)?;
+    save_history(store, &history)
+}
+
+fn step_history(store: &dyn Storage<Records>, redo: bool) -> io::Result<()> {
+    let mut recs = load_records(store)?;
+    let mut history = load_history(store)?;
+    let stepped = match redo {
+        true => history.redo(&mut recs).is_some(),
+        false => history.undo(&mut recs).is_some(),
+    };
+    if stepped {
+        save_records(store, &recs)?;
+        save_history(store, &history)?;
+    }
+    let message = match (stepped, redo) {
+        (true, false) => "contacts-undone",
+        (true, true) => "contacts-redone",
+        (false, false) => "contacts-nothing-to-undo",
+        (false, true) => "contacts-nothing-to-redo",
+    };
+    println!("{}", i18n::t!(message));
+    Ok(())
+}
+
+fn migrate(store: &dyn Storage<Records>, to: Option<u32>, dry_run: bool) -> io::Result<()> {
+    let migrated = store.migrate(to, dry_run)?;
+    let file = store.path().display();
+    if migrated.steps.is_empty() {
+        println!(
+            "{}",
+            i18n::t!(
+                "contacts-migration-current",
+                file = file,
+                version = migrated.from
+            )
+        );
+        return Ok(());
+    }
+    println!(
+        "{}",
+        i18n::t!(
+            "contacts-migration",
+            file = file,
+            from = migrated.from,
+            to = migrated.to
+        )
+    );
+    for step in &migrated.steps {
+        println!("  {}", step);
+    }
+    if dry_run {
+        println!("{}", i18n::t!("contacts-migration-dry-run"));
+    }
+    Ok(())
+}
+
+fn escape_ics_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace(['\r', '\n'], "\\n")
+}
+
+/// Octets per line before iCalendar lines must be folded.
+const ICS_LINE_LIMIT: usize = 75;
+
+/// Writes one content line, folded onto continuation lines that start with
+/// a space so none is longer than 75 octets, without splitting characters.
+fn write_ics_line<W: Write>(out: &mut W, line: &str) -> io::Result<()> {
+    let mut start = 0;
+    let mut limit = ICS_LINE_LIMIT;
+    for (index, c) in line.char_indices() {
+        if index + c.len_utf8() - start > limit {
+            out.write_all(&line.as_bytes()[start..index])?;
+            out.write_all(b"\r\n ")?;
+            start = index;
+            // The leading space counts towards the limit.
+            limit = ICS_LINE_LIMIT - 1;
+        }
+    }
+    out.write_all(&line.as_bytes()[start..])?;
+    out.write_all(b"\r\n")
+}
+
+fn write_ics<W: Write>(out: &mut W, records: Records) -> io::Result<()> {
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ");
+
+    write_ics_line(out, "BEGIN:VCALENDAR")?;
+    write_ics_line(out, "VERSION:2.0")?;
+    write_ics_line(out, "PRODID:-//study-topics//contact_manager//EN")?;
+    for record in records.into_vec() {
+        let birthday = match record.birthday {
+            Some(date) => date,
+            None => continue,
+        };
+
+        let summary = escape_ics_text(&i18n::t!("contacts-birthday-event", name = record.name));
+        for line in [
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:contact-{}-birthday@contact_manager", record.id),
+            format!("DTSTAMP:{}", stamp),
+            format!("DTSTART;VALUE=DATE:{}", birthday.format("%Y%m%d")),
+            "RRULE:FREQ=YEARLY".to_string(),
+            format!("SUMMARY:{}", summary),
+            "END:VEVENT".to_string(),
+        ] {
+            write_ics_line(out, &line)?;
+        }
+    }
+    write_ics_line(out, "END:VCALENDAR")?;
+    out.flush()
+}
+
+#[derive(ValueEnum, Clone, Debug)]
+enum ExportFormat {
+    Ics,
+}
+
+fn export_records(
+    records: Records,
+    format: ExportFormat,
+    output: Option<PathBuf>,
+) -> io::Result<()> {
+    match (format, output) {
+        (ExportFormat::Ics, Some(file_name)) => write_ics(&mut File::create(file_name)?, records),
+        (ExportFormat::Ics, None) => write_ics(&mut io::stdout().lock(), records),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct Config {
+    data_file: PathBuf,
+    storage: Backend,
+    theme: Theme,
+    /// Keep the contacts file encrypted with a passphrase.
+    encrypt: bool,
+}
+impl Settings for Config {
+    const APP: &'static str = "contact_manager";
+}
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            data_file: PathBuf::from("p2_data.csv"),
+            storage: Backend::Csv,
+            theme: Theme::Default,
+            encrypt: false,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+#[command(about = "Contact Manager", version, styles = Styles::styled())]
+struct Opt {
+    #[arg(
+        short,
+        long,
+        global = true,
+        value_hint = ValueHint::FilePath,
+        help = "contacts file, defaults to the data_file setting"
+    )]
+    data_file: Option<PathBuf>,
+    #[arg(
+        long,
+        global = true,
+        value_hint = ValueHint::FilePath,
+        help = "config file, defaults to config.toml in the contact_manager config directory"
+    )]
+    config: Option<PathBuf>,
+    #[command(subcommand)]
+    cmd: Command,
+    #[arg(
+        short,
+        long,
+        global = true,
+        help = "log debug details; CONTACT_MANAGER_LOG overrides the level"
+    )]
+    verbose: bool,
+    #[arg(
+        long,
+        global = true,
+        value_hint = ValueHint::FilePath,
+        help = "also append log output to this file"
+    )]
+    log_file: Option<PathBuf>,
+    #[arg(
+        long,
+        global = true,
+        help = "language for messages, like de or de-AT, defaults to LANG"
+    )]
+    lang: Option<String>,
+    #[arg(
+        short,
+        long,
+        global = true,
+        value_hint = ValueHint::FilePath,
+        help = "write exports to this file instead of stdout"
+    )]
+    output: Option<PathBuf>,
+}
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List all contacts
+    #[command(visible_alias = "ls")]
+    List {},
+    /// Add a contact, asking for the details when no name is given
+    #[command(visible_alias = "new")]
+    Add {
+        name: Option<String>,
+        #[arg(short, long, value_parser = validate::email)]
+        email: Option<String>,
+        #[arg(short, long, value_parser = parse_birthday, help = "birthday as YYYY-MM-DD")]
+        birthday: Option<NaiveDate>,
+    },
+    /// Find contacts by name
+    #[command(visible_alias = "find")]
+    Search { query: String },
+    /// Show one contact as a card
+    Show { id: i64 },
+    /// Remove a contact
+    #[command(visible_alias = "rm")]
+    Remove { id: i64 },
+    /// Replace a contact's details
+    #[command(visible_alias = "edit")]
+    Update {
+        id: i64,
+        name: String,
+        #[arg(value_parser = validate::email)]
+        email: Option<String>,
+        #[arg(short, long, value_parser = parse_birthday, help = "birthday as YYYY-MM-DD")]
+        birthday: Option<NaiveDate>,
+    },
+    /// Take back the last add, update or remove
+    Undo,
+    /// Make the last undone change again
+    Redo,
+    /// Rewrite the contacts file in another version of its format
+    Migrate {
+        #[arg(long, help = "format version to write, defaults to the latest")]
+        to: Option<u32>,
+        #[arg(long, help = "only list the migrations that would run")]
+        dry_run: bool,
+    },
+    /// Export birthdays as an iCalendar file
+    Export {
+        #[arg(long, value_enum, default_value_t = ExportFormat::Ics)]
+        format: ExportFormat,
+    },
+    /// Download and install the latest release
+    SelfUpdate {
+        #[arg(long, help = "only report whether a newer release exists")]
+        check: bool,
+    },
+    #[command(external_subcommand)]
+    External(Vec<String>),
+}
+
+fn parse_birthday(input: &str) -> Result<NaiveDate, validate::Invalid> {
+    validate::date(input, DATE_FORMAT)
+}
+
+fn ask_contact(ui: &mut dyn UserInterface) -> Result<Option<Record>, Back> {
+    let name = match ui.prompt(
+        &i18n::t!("contacts-ask-name"),
+        &i18n::t!("contacts-ask-name-help"),
+    )? {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+    let email = prompt::parsed(
+        ui,
+        &i18n::t!("contacts-ask-email"),
+        &i18n::t!("contacts-ask-email-help"),
+        |input| validate::email(input).map_err(|_| i18n::t!("contacts-invalid-email")),
+    )?;
+    let birthday = prompt::parsed(
+        ui,
+        &i18n::t!("contacts-ask-birthday"),
+        &i18n::t!("contacts-ask-birthday-help"),
+        |input| parse_birthday(input).map_err(|_| i18n::t!("contacts-invalid-birthday")),
+    )?;
+    if !prompt::confirm(ui, &i18n::t!("contacts-confirm-add", name = name))? {
+        return Ok(None);
+    }
+    Ok(Some(Record {
+        id: 0,
+        name,
+        email,
+        birthday,
+    }))
+}
+
+#[cfg(feature = "encryption")]
+fn open_records(config: &Config) -> io::Result<Box<dyn Storage<Records>>> {
+    if !config.encrypt {
+        return Ok(storage::open(config.storage, &config.data_file, ())?);
+    }
+    // Ask twice when the passphrase is about to be chosen.
+    let confirm = !encryption::is_encrypted_file(&config.data_file);
+    let passphrase = encryption::passphrase(Config::APP, confirm)?;
+    Ok(storage::open_encrypted(
+        config.storage,
+        &config.data_file,
+        (),
+        passphrase,
+    )?)
+}
+
+#[cfg(not(feature = "encryption"))]
+fn open_records(config: &Config) -> io::Result<Box<dyn Storage<Records>>> {
+    if config.encrypt {
+        return Err(errors::not_built_in("encryption"));
+    }
+    Ok(storage::open(config.storage, &config.data_file, ())?)
+}
+
+fn run_plugin(config: &Config, verbose: bool, args: Vec<String>) -> io::Result<()> {
+    let (name, plugin_args) = match args.split_first() {
+        Some(split) => split,
+        None => return Ok(()),
+    };
+    let plugin = Plugin::find(Config::APP, name).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            i18n::t!("contacts-no-such-subcommand", name = name),
+        )
+    })?;
+    let context = plugin::Context {
+        data_file: &config.data_file,
+        storage: config.storage,
+        verbose,
+    };
+    let code = plugin.run(Config::APP, env!("CARGO_PKG_VERSION"), context, plugin_args)?;
+    if code != 0 {
+        process::exit(code);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "self-update")]
+fn self_update(check: bool) -> io::Result<()> {
+    let status = update::self_update("contact_manager", env!("CARGO_PKG_VERSION"), check)?;
+    println!("{}", status);
+    Ok(())
+}
+
+#[cfg(not(feature = "self-update"))]
+fn self_update(_check: bool) -> io::Result<()> {
+    Err(errors::not_built_in("self-update"))
+}
+
+fn run(opt: Opt) -> Result<(), std::io::Error> {
+    logging::init("contact_manager", opt.verbose, opt.log_file.as_deref())?;
+    i18n::set_language(opt.lang.as_deref());
+    settings::first_run(Config::APP);
+    let config: Config = Layers::new()
+        .file(opt.config)
+        .flag("data_file", opt.data_file)
+        .load()?;
+    render::set_theme(config.theme);
+    let store = open_records(&config)?;
+    let store = store.as_ref();
+    match opt.cmd {
+        Command::Search { query } => {
+            let recs = load_records(store)?;
+            let results = recs.search(&query);
+            if results.is_empty() {
+                println!("{}", i18n::t!("contacts-none-found"))
+            } else {
+                print_record_table(results);
+            }
+        }
+        Command::Add {
+            name,
+            email,
+            birthday,
+        } => {
+            let record = match name {
+                Some(name) => Record {
+                    id: 0,
+                    name,
+                    email,
+                    birthday,
+                },
+                None => match ask_contact(&mut Terminal::new()) {
+                    Ok(Some(record)) => record,
+                    Ok(None) | Err(Back) => return Ok(()),
+                },
+            };
+            let mut recs = load_records(store)?;
+            let mut counter = load_counter(store)?;
+            let id = recs.issue_id(&mut counter);
+            let changes = Changes::default().set(id, Some(Record { id, ..record }));
+            change_records(store, &mut recs, changes)?;
+            save_counter(store, &counter)?;
+        }
+        Command::List { .. } => {
+            let recs = load_records(store)?;
+            print_record_table(&recs.into_vec());
+        }
+        Command::Show { id } => {
+            let recs = load_records(store)?;
+            match recs.get(id) {
+                Some(record) => print_record_card(record),
+                None => println!("{}", i18n::t!("contacts-not-found")),
+            }
+        }
+        Command::Remove { id } => {
+            let mut recs = load_records(store)?;
+            if recs.get(id).is_some() {
+                change_records(store, &mut recs, Changes::default().set(id, None))?;
+                println!("{}", i18n::t!("contacts-deleted"));
+            } else {
+                println!("{}", i18n::t!("contacts-not-found"))
+            }
+        }
+        Command::Update {
+            id,
+            name,
+            email,
+            birthday,
+        } => {
+            let mut recs = load_records(store)?;
+            let record = Record {
+                id,
+                name,
+                email,
+                birthday,
+            };
+            change_records(store, &mut recs, Changes::default().set(id, Some(record)))?;
+        }
+        Command::Migrate { to, dry_run } => migrate(store, to, dry_run)?,
+        Command::Undo => step_history(store, false)?,
+        Command::Redo => step_history(store, true)?,
+        Command::Export { format } => {
+            let recs = load_records(store)?;
+            export_records(recs, format, opt.output)?;
+        }
+        Command::SelfUpdate { check } => self_update(check)?,
+        Command::External(args) => run_plugin(&config, opt.verbose, args)?,
+    }
+    Ok(())
+}
+
+/// Runs the contact manager on `args`, the first being the program name,
+/// and exits when it fails.
+pub fn main<I, T>(args: I)
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    let opt = Opt::parse_from(args);
+    if let Err(e) = run(opt) {
+        errors::report(&e);
+        process::exit(1);
+    }
+}
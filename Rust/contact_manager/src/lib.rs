@@ -0,0 +1,24 @@
+pub mod cli;
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+use storage::{Csv, Storage};
+
+pub use contact_core::{format_record, parse_record, ParseError, Record, Records, DATE_FORMAT};
+
+pub fn parse_records(records: &str, file_name: &Path) -> io::Result<Records> {
+    Ok(storage::parse_csv(records, file_name, &())?)
+}
+
+/// Loads a CSV contacts file, whatever the `storage` setting says.
+pub fn load_records(input_file: PathBuf) -> io::Result<Records> {
+    let records = Csv::<Records>::new(&input_file, ()).load()?;
+    tracing::debug!(
+        "loaded {} records from {}",
+        records.len(),
+        input_file.display()
+    );
+    Ok(records)
+}
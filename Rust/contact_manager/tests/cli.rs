@@ -0,0 +1,513 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn contacts(dir: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("contact_manager").unwrap();
+    cmd.current_dir(dir.path())
+        .env("PATH", "")
+        .env("XDG_CONFIG_HOME", dir.path())
+        .env("HOME", dir.path())
+        .env_remove("CONTACT_MANAGER_DATA_FILE")
+        .env_remove("CONTACT_MANAGER_STORAGE")
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .env_remove("LANG");
+    cmd
+}
+
+/// Output for snapshots, which review any change to what users see.
+fn stdout(cmd: &mut Command) -> String {
+    String::from_utf8(cmd.output().unwrap().stdout).unwrap()
+}
+
+fn stderr(cmd: &mut Command) -> String {
+    String::from_utf8(cmd.output().unwrap().stderr).unwrap()
+}
+
+fn data_dir() -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("p2_data.csv"), "id,name,email,birthday\n").unwrap();
+    dir
+}
+
+#[test]
+fn add_list_search_remove() {
+    let dir = data_dir();
+    contacts(&dir)
+        .args([
+            "add",
+            "Ann Lee",
+            "-e",
+            "ann@example.com",
+            "-b",
+            "1990-02-03",
+        ])
+        .assert()
+        .success();
+    contacts(&dir).args(["new", "Bob"]).assert().success();
+
+    contacts(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Ann Lee"))
+        .stdout(predicate::str::contains("Bob"));
+    contacts(&dir)
+        .args(["search", "ann"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("ann@example.com").and(predicate::str::contains("Bob").not()),
+        );
+    contacts(&dir)
+        .args(["show", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Birthday: 1990-02-03"));
+
+    contacts(&dir)
+        .args(["rm", "1"])
+        .assert()
+        .success()
+        .stdout("record deleted\n");
+    contacts(&dir)
+        .args(["search", "ann"])
+        .assert()
+        .success()
+        .stdout("no records found!\n");
+    assert_eq!(
+        fs::read_to_string(dir.path().join("p2_data.csv")).unwrap(),
+        "id,name,email,birthday\n2,Bob,,\n"
+    );
+}
+
+#[test]
+fn quoted_names_survive_a_round_trip() {
+    let dir = data_dir();
+    contacts(&dir).args(["add", "Lee, Ann"]).assert().success();
+    contacts(&dir)
+        .args(["update", "1", "Lee, Ann", "ann@example.com"])
+        .assert()
+        .success();
+    contacts(&dir)
+        .args(["show", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Name:     Lee, Ann"))
+        .stdout(predicate::str::contains("Email:    ann@example.com"));
+}
+
+#[cfg(feature = "encryption")]
+#[test]
+fn encrypt_setting_encrypts_the_data_file() {
+    let dir = data_dir();
+    contacts(&dir).args(["add", "Ann"]).assert().success();
+    let encrypted = || {
+        let mut cmd = contacts(&dir);
+        cmd.env("CONTACT_MANAGER_ENCRYPT", "true")
+            .env("CONTACT_MANAGER_PASSPHRASE", "secret");
+        cmd
+    };
+    encrypted().args(["add", "Bob"]).assert().success();
+    let data = fs::read(dir.path().join("p2_data.csv")).unwrap();
+    assert!(data.starts_with(b"STUDYENC"));
+    assert!(!String::from_utf8_lossy(&data).contains("Ann"));
+    assert!(!dir.path().join("p2_data.csv.undo").exists());
+    assert!(!dir.path().join("p2_data.csv.ids").exists());
+    encrypted()
+        .args(["search", "Ann"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Ann"));
+    encrypted()
+        .arg("undo")
+        .assert()
+        .success()
+        .stdout("nothing to undo\n");
+    contacts(&dir)
+        .env("CONTACT_MANAGER_ENCRYPT", "true")
+        .env("CONTACT_MANAGER_PASSPHRASE", "wrong")
+        .arg("list")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "caused by: wrong passphrase or damaged file",
+        ));
+}
+
+#[test]
+fn migrate_reports_the_format_version() {
+    let dir = data_dir();
+    contacts(&dir)
+        .args(["migrate", "--dry-run"])
+        .assert()
+        .success()
+        .stdout("p2_data.csv is at format version 0, nothing to migrate\n");
+    contacts(&dir)
+        .args(["migrate", "--to", "1"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "there is no version 1, this build knows up to version 0",
+        ));
+}
+
+#[test]
+fn undo_and_redo_across_runs() {
+    let dir = data_dir();
+    let data = || fs::read_to_string(dir.path().join("p2_data.csv")).unwrap();
+    contacts(&dir).args(["add", "Ann"]).assert().success();
+    contacts(&dir)
+        .args(["update", "1", "Ann Lee"])
+        .assert()
+        .success();
+    contacts(&dir).args(["rm", "1"]).assert().success();
+    contacts(&dir)
+        .arg("undo")
+        .assert()
+        .success()
+        .stdout("undid the last change\n");
+    contacts(&dir).arg("undo").assert().success();
+    assert_eq!(data(), "id,name,email,birthday\n1,Ann,,\n");
+
+    contacts(&dir)
+        .arg("redo")
+        .assert()
+        .success()
+        .stdout("redid the last change\n");
+    assert_eq!(data(), "id,name,email,birthday\n1,Ann Lee,,\n");
+    contacts(&dir).args(["add", "Bob"]).assert().success();
+    contacts(&dir)
+        .arg("redo")
+        .assert()
+        .success()
+        .stdout("nothing to redo\n");
+    for _ in 0..3 {
+        contacts(&dir).arg("undo").assert().success();
+    }
+    assert_eq!(data(), "id,name,email,birthday\n");
+    contacts(&dir)
+        .arg("undo")
+        .assert()
+        .success()
+        .stdout("nothing to undo\n");
+}
+
+#[test]
+fn list_and_show_snapshots() {
+    let dir = data_dir();
+    fs::write(
+        dir.path().join("p2_data.csv"),
+        "id,name,email,birthday\n1,Ann Lee,ann@example.com,1990-02-03\n2,\"Lee, Bob\",,\n",
+    )
+    .unwrap();
+    insta::assert_snapshot!("list", stdout(contacts(&dir).arg("list")));
+    insta::assert_snapshot!("show", stdout(contacts(&dir).args(["show", "1"])));
+    insta::assert_snapshot!(
+        "show_without_details",
+        stdout(contacts(&dir).args(["show", "2"]))
+    );
+}
+
+#[test]
+fn error_snapshots() {
+    let dir = data_dir();
+    fs::write(
+        dir.path().join("broken.csv"),
+        "id,name,email,birthday\nx,Ann,,\n3,\"Bob,,\n",
+    )
+    .unwrap();
+    insta::assert_snapshot!(
+        "broken_lines",
+        stderr(contacts(&dir).args(["-d", "broken.csv", "list"]))
+    );
+    insta::assert_snapshot!(
+        "invalid_birthday",
+        stderr(contacts(&dir).args(["add", "Ann", "-b", "1990-13-01"]))
+    );
+    insta::assert_snapshot!(
+        "invalid_email",
+        stderr(contacts(&dir).args(["add", "Ann", "-e", "ann.example.com"]))
+    );
+    insta::assert_snapshot!(
+        "unknown_subcommand",
+        stderr(contacts(&dir).arg("frobnicate"))
+    );
+}
+
+#[test]
+fn lang_picks_the_message_catalog() {
+    let dir = data_dir();
+    contacts(&dir)
+        .args(["add", "Ann", "-b", "1990-02-03"])
+        .assert()
+        .success();
+    contacts(&dir)
+        .args(["--lang", "de-AT", "show", "1"])
+        .assert()
+        .success()
+        .stdout("Kontakt #1\n  Name:       Ann\n  E-Mail:     -\n  Geburtstag: 1990-02-03\n");
+    contacts(&dir)
+        .args(["rm", "2"])
+        .env("LANG", "de_DE.UTF-8")
+        .assert()
+        .success()
+        .stdout("Kontakt nicht gefunden\n");
+    contacts(&dir)
+        .args(["--lang", "fr", "rm", "2"])
+        .env("LANG", "de_DE.UTF-8")
+        .assert()
+        .success()
+        .stdout("record not found\n");
+}
+
+#[test]
+fn exports_birthdays_to_a_file() {
+    let dir = data_dir();
+    contacts(&dir)
+        .args(["add", "Ann", "-b", "1990-02-03"])
+        .assert()
+        .success();
+    contacts(&dir)
+        .args(["export", "-o", "birthdays.ics"])
+        .assert()
+        .success();
+    let calendar = fs::read_to_string(dir.path().join("birthdays.ics")).unwrap();
+    assert!(calendar.contains("SUMMARY:Birthday: Ann"));
+}
+
+#[test]
+fn never_reuses_ids_of_removed_contacts() {
+    let dir = data_dir();
+    for name in ["Ann", "Bob"] {
+        contacts(&dir).args(["add", name]).assert().success();
+    }
+    contacts(&dir).args(["remove", "2"]).assert().success();
+    contacts(&dir).args(["add", "Cara"]).assert().success();
+    contacts(&dir)
+        .args(["show", "2"])
+        .assert()
+        .success()
+        .stdout("record not found\n");
+    contacts(&dir)
+        .args(["show", "3"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cara"));
+}
+
+#[test]
+fn folds_long_calendar_lines() {
+    let dir = data_dir();
+    let name = format!("{}\nZoë", "Müller-Lüdenscheidt ".repeat(5));
+    contacts(&dir)
+        .args(["add", &name, "-b", "1990-02-03"])
+        .assert()
+        .success();
+    contacts(&dir)
+        .args(["export", "-o", "birthdays.ics"])
+        .assert()
+        .success();
+    let calendar = fs::read_to_string(dir.path().join("birthdays.ics")).unwrap();
+    let lines: Vec<&str> = calendar.split_terminator("\r\n").collect();
+    assert!(lines.iter().all(|line| line.len() <= 75), "{:?}", lines);
+    assert!(lines.iter().any(|line| line.starts_with(' ')));
+    let unfolded = calendar.replace("\r\n ", "");
+    assert!(unfolded.contains(&format!(
+        "SUMMARY:Birthday: {}\\nZoë\r\n",
+        "Müller-Lüdenscheidt ".repeat(5)
+    )));
+}
+
+#[test]
+fn reports_errors_with_exit_codes() {
+    let dir = data_dir();
+    contacts(&dir)
+        .args(["remove", "7"])
+        .assert()
+        .success()
+        .stdout("record not found\n");
+    contacts(&dir)
+        .args(["add", "Ann", "-b", "someday"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("invalid value 'someday'"));
+    contacts(&dir)
+        .args(["-d", "missing.csv", "list"])
+        .assert()
+        .code(1)
+        .stderr(predicate::str::starts_with("error: "));
+    contacts(&dir)
+        .arg("frobnicate")
+        .assert()
+        .code(1)
+        .stderr("error: no such subcommand: frobnicate\n");
+}
+
+#[test]
+fn warns_about_broken_lines() {
+    let dir = data_dir();
+    fs::write(
+        dir.path().join("p2_data.csv"),
+        "id,name,email,birthday\n1,Ann,,\nx,Bob,,\n",
+    )
+    .unwrap();
+    contacts(&dir)
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Bob").not())
+        .stderr(predicate::str::contains(" --> p2_data.csv:3:1\n"))
+        .stderr(predicate::str::contains("3 | x,Bob,,\n"));
+    contacts(&dir)
+        .args(["-v", "--log-file", "contacts.log", "list"])
+        .env("CONTACT_MANAGER_LOG", "error")
+        .assert()
+        .success()
+        .stderr("");
+    contacts(&dir)
+        .args(["-v", "--log-file", "contacts.log", "list"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "loaded 1 records from p2_data.csv",
+        ));
+    let log = fs::read_to_string(dir.path().join("contacts.log")).unwrap();
+    assert!(log.contains("DEBUG") && log.contains("loaded 1 records"));
+}
+
+#[test]
+fn add_asks_for_missing_details() {
+    let dir = data_dir();
+    contacts(&dir)
+        .arg("add")
+        .write_stdin("Ann Lee\nann@example.com\n1990-02-30\n1990-02-03\ny\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Please enter a date like 1990-02-03",
+        ));
+    contacts(&dir)
+        .arg("add")
+        .write_stdin("Bob\n\n\nn\n")
+        .assert()
+        .success();
+    assert_eq!(
+        fs::read_to_string(dir.path().join("p2_data.csv")).unwrap(),
+        "id,name,email,birthday\n1,Ann Lee,ann@example.com,1990-02-03\n"
+    );
+}
+
+#[test]
+fn data_file_comes_from_config_env_or_flag() {
+    let dir = data_dir();
+    fs::create_dir(dir.path().join("contact_manager")).unwrap();
+    fs::write(
+        dir.path().join("contact_manager/config.toml"),
+        "data_file = \"config.csv\"\n",
+    )
+    .unwrap();
+    for file in ["config.csv", "env.csv", "flag.csv"] {
+        fs::write(dir.path().join(file), "id,name,email,birthday\n").unwrap();
+    }
+    contacts(&dir).args(["add", "Ann"]).assert().success();
+    contacts(&dir)
+        .args(["add", "Bob"])
+        .env("CONTACT_MANAGER_DATA_FILE", "env.csv")
+        .assert()
+        .success();
+    contacts(&dir)
+        .args(["-d", "flag.csv", "add", "Cid"])
+        .env("CONTACT_MANAGER_DATA_FILE", "env.csv")
+        .assert()
+        .success();
+    for (file, name) in [
+        ("config.csv", "Ann"),
+        ("env.csv", "Bob"),
+        ("flag.csv", "Cid"),
+    ] {
+        let contents = fs::read_to_string(dir.path().join(file)).unwrap();
+        assert_eq!(contents, format!("id,name,email,birthday\n1,{},,\n", name));
+    }
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn storage_setting_switches_backends() {
+    let dir = data_dir();
+    contacts(&dir)
+        .args(["-d", "contacts.json", "add", "Ann"])
+        .env("CONTACT_MANAGER_STORAGE", "json")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("No such file"));
+    fs::write(dir.path().join("contacts.json"), "[]").unwrap();
+    contacts(&dir)
+        .args(["-d", "contacts.json", "add", "Ann"])
+        .env("CONTACT_MANAGER_STORAGE", "json")
+        .assert()
+        .success();
+    assert_eq!(
+        fs::read_to_string(dir.path().join("contacts.json")).unwrap(),
+        "[\n  {\n    \"id\": \"1\",\n    \"name\": \"Ann\"\n  }\n]\n"
+    );
+}
+
+#[cfg(not(feature = "self-update"))]
+#[test]
+fn slim_builds_explain_missing_features() {
+    let dir = data_dir();
+    contacts(&dir)
+        .args(["self-update", "--check"])
+        .assert()
+        .code(1)
+        .stderr("error: self-update is not built in, rebuild with the `self-update` feature\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn unknown_subcommands_run_plugins() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = data_dir();
+    let plugin = dir.path().join("contact_manager-hello");
+    fs::write(
+        &plugin,
+        "#!/bin/sh\nread -r answer\necho \"$CONTACT_MANAGER_DATA_FILE $* $answer\"\n\
+         echo \"$CONTACT_MANAGER_HANDSHAKE\"\nexit 4\n",
+    )
+    .unwrap();
+    fs::set_permissions(&plugin, fs::Permissions::from_mode(0o755)).unwrap();
+    contacts(&dir)
+        .env("PATH", dir.path())
+        .args(["hello", "Ann"])
+        .write_stdin("yes\n")
+        .assert()
+        .code(4)
+        .stdout(predicate::str::starts_with("p2_data.csv Ann yes\n"))
+        .stdout(predicate::str::contains(
+            "\"tool\":\"contact_manager\",\"version\":\"0.1.0\",\"command\":\"hello\"",
+        ));
+}
+
+#[test]
+fn keeps_fields_with_line_breaks() {
+    let dir = data_dir();
+    contacts(&dir)
+        .args(["add", "Ann\nSmith", "-e", "a@b.co"])
+        .assert()
+        .success();
+    contacts(&dir).args(["add", "Bob"]).assert().success();
+    assert_eq!(
+        fs::read_to_string(dir.path().join("p2_data.csv")).unwrap(),
+        "id,name,email,birthday\n1,\"Ann\nSmith\",a@b.co,\n2,Bob,,\n"
+    );
+    contacts(&dir)
+        .args(["show", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Smith"))
+        .stderr("");
+}
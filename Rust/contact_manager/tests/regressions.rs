@@ -0,0 +1,17 @@
+use contact_manager::{parse_records, Record};
+use std::path::Path;
+
+#[test]
+fn next_id_after_the_largest_id() {
+    let file = "id,name,email,birthday\n9223372036854775807,Ann,,\n";
+    let mut records = parse_records(file, Path::new("p2_data.csv")).unwrap();
+    let id = records.next_id();
+    assert_eq!(id, 1);
+    records.add(Record {
+        id,
+        name: "Bob".to_string(),
+        email: None,
+        birthday: None,
+    });
+    assert_eq!(records.into_vec().len(), 2);
+}
@@ -0,0 +1,32 @@
+use chrono::NaiveDate;
+use contact_manager::{format_record, parse_records, Record};
+use proptest::prelude::*;
+use std::path::Path;
+
+/// Mostly the characters CSV has to escape, sometimes any printable text.
+fn text() -> impl Strategy<Value = String> {
+    prop_oneof!["[a-zA-Z0-9 ,;\"'éß漢字🙂]{1,20}", "\\PC{1,20}",]
+}
+
+fn date() -> impl Strategy<Value = NaiveDate> {
+    (1900..2100, 1u32..=12, 1u32..=28)
+        .prop_map(|(year, month, day)| NaiveDate::from_ymd_opt(year, month, day).unwrap())
+}
+
+proptest! {
+    #[test]
+    fn records_round_trip(
+        id in 1..i64::MAX,
+        name in text(),
+        email in proptest::option::of(text()),
+        birthday in proptest::option::of(date()),
+    ) {
+        let record = Record { id, name, email, birthday };
+        let file = format!("id,name,email,birthday\n{}", format_record(&record));
+        let mut records = parse_records(&file, Path::new("p2_data.csv"))
+            .unwrap()
+            .into_vec();
+        prop_assert_eq!(records.pop(), Some(record));
+        prop_assert!(records.is_empty());
+    }
+}
@@ -0,0 +1,236 @@
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::Path,
+};
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+/// A passphrase that is wiped from memory when dropped.
+pub type Passphrase = Zeroizing<String>;
+
+const MAGIC: &[u8; 8] = b"STUDYENC";
+pub const VERSION: u8 = 1;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+/// Magic, version, the three key derivation costs, salt and nonce.
+const HEADER_LEN: usize = MAGIC.len() + 1 + 3 * 4 + SALT_LEN + NONCE_LEN;
+
+/// Argon2id costs: memory in KiB, passes and lanes. New files get
+/// [`Kdf::DEFAULT`]; the ones a file was written with are read from its header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Kdf {
+    pub memory: u32,
+    pub passes: u32,
+    pub lanes: u32,
+}
+impl Kdf {
+    pub const DEFAULT: Kdf = Kdf {
+        memory: 19 * 1024,
+        passes: 2,
+        lanes: 1,
+    };
+    /// More than any file written by this crate asks for, so a forged
+    /// header can't make opening it take all memory.
+    const MAX_MEMORY: u32 = 1024 * 1024;
+
+    fn key(self, passphrase: &str, salt: &[u8]) -> Result<Zeroizing<[u8; 32]>, Error> {
+        if self.memory > Self::MAX_MEMORY {
+            return Err(Error::Kdf(format!("{} KiB of memory", self.memory)));
+        }
+        let params = Params::new(self.memory, self.passes, self.lanes, Some(32))
+            .map_err(|e| Error::Kdf(e.to_string()))?;
+        let mut key = Zeroizing::new([0; 32]);
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+            .map_err(|e| Error::Kdf(e.to_string()))?;
+        Ok(key)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("not an encrypted file")]
+    NotEncrypted,
+    #[error("encrypted with format version {0}, this build reads version {VERSION}")]
+    UnsupportedVersion(u8),
+    #[error("encrypted file is cut off")]
+    Truncated,
+    #[error("wrong passphrase or damaged file")]
+    Decrypt,
+    #[error("invalid key derivation settings: {0}")]
+    Kdf(String),
+    #[error("could not get random bytes: {0}")]
+    Random(String),
+}
+impl From<Error> for io::Error {
+    fn from(error: Error) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
+
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+/// Whether the file at `path` starts like an encrypted one; false when it can't be read.
+pub fn is_encrypted_file(path: &Path) -> bool {
+    let mut start = [0; MAGIC.len()];
+    fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut start))
+        .is_ok_and(|()| is_encrypted(&start))
+}
+
+/// Encrypts `plain` with XChaCha20-Poly1305 under a key derived from
+/// `passphrase` with Argon2id. The header is authenticated along with the data.
+pub fn encrypt(plain: &[u8], passphrase: &str) -> Result<Vec<u8>, Error> {
+    encrypt_with(plain, passphrase, Kdf::DEFAULT)
+}
+
+pub fn encrypt_with(plain: &[u8], passphrase: &str, kdf: Kdf) -> Result<Vec<u8>, Error> {
+    let mut salt = [0; SALT_LEN];
+    let mut nonce = [0; NONCE_LEN];
+    getrandom::fill(&mut salt).map_err(|e| Error::Random(e.to_string()))?;
+    getrandom::fill(&mut nonce).map_err(|e| Error::Random(e.to_string()))?;
+
+    let mut data = Vec::with_capacity(HEADER_LEN + plain.len() + 16);
+    data.extend_from_slice(MAGIC);
+    data.push(VERSION);
+    for cost in [kdf.memory, kdf.passes, kdf.lanes] {
+        data.extend_from_slice(&cost.to_le_bytes());
+    }
+    data.extend_from_slice(&salt);
+    data.extend_from_slice(&nonce);
+
+    let key = kdf.key(passphrase, &salt)?;
+    let sealed = XChaCha20Poly1305::new(&(*key).into())
+        .encrypt(
+            &XNonce::from(nonce),
+            Payload {
+                msg: plain,
+                aad: &data,
+            },
+        )
+        .map_err(|_| Error::Decrypt)?;
+    data.extend_from_slice(&sealed);
+    Ok(data)
+}
+
+pub fn decrypt(data: &[u8], passphrase: &str) -> Result<Zeroizing<Vec<u8>>, Error> {
+    if !is_encrypted(data) {
+        return Err(Error::NotEncrypted);
+    }
+    if data.len() < HEADER_LEN {
+        return Err(Error::Truncated);
+    }
+    let (header, sealed) = data.split_at(HEADER_LEN);
+    let version = header[MAGIC.len()];
+    if version != VERSION {
+        return Err(Error::UnsupportedVersion(version));
+    }
+    let cost = |index: usize| {
+        let start = MAGIC.len() + 1 + index * 4;
+        u32::from_le_bytes([
+            header[start],
+            header[start + 1],
+            header[start + 2],
+            header[start + 3],
+        ])
+    };
+    let kdf = Kdf {
+        memory: cost(0),
+        passes: cost(1),
+        lanes: cost(2),
+    };
+    let salt = &header[HEADER_LEN - NONCE_LEN - SALT_LEN..HEADER_LEN - NONCE_LEN];
+    let mut nonce = [0; NONCE_LEN];
+    nonce.copy_from_slice(&header[HEADER_LEN - NONCE_LEN..]);
+
+    let key = kdf.key(passphrase, salt)?;
+    XChaCha20Poly1305::new(&(*key).into())
+        .decrypt(
+            &XNonce::from(nonce),
+            Payload {
+                msg: sealed,
+                aad: header,
+            },
+        )
+        .map(Zeroizing::new)
+        .map_err(|_| Error::Decrypt)
+}
+
+/// The passphrase from `<TOOL>_PASSPHRASE`, or else asked for on the
+/// terminal without echo. With `confirm` it is asked twice, so a typo can't
+/// lock data away under a passphrase nobody knows.
+pub fn passphrase(tool: &str, confirm: bool) -> io::Result<Passphrase> {
+    let variable = format!("{}_PASSPHRASE", tool.to_uppercase());
+    if let Ok(passphrase) = env::var(&variable) {
+        return Ok(Passphrase::new(passphrase));
+    }
+    let ask = |text: String| {
+        rpassword::prompt_password(text + " ").map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                i18n::t!("encryption-no-terminal", variable = variable),
+            )
+        })
+    };
+    loop {
+        let passphrase = Passphrase::new(ask(i18n::t!("encryption-passphrase"))?);
+        if passphrase.is_empty() {
+            eprintln!("{}", i18n::t!("encryption-empty"));
+            continue;
+        }
+        if !confirm || *Passphrase::new(ask(i18n::t!("encryption-repeat"))?) == *passphrase {
+            return Ok(passphrase);
+        }
+        eprintln!("{}", i18n::t!("encryption-mismatch"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cheap enough for debug builds.
+    const FAST: Kdf = Kdf {
+        memory: 64,
+        passes: 1,
+        lanes: 1,
+    };
+
+    #[test]
+    fn round_trips_and_rejects_tampering() {
+        let data = encrypt_with(b"id,name\n1,Ann\n", "secret", FAST).unwrap();
+        assert!(is_encrypted(&data));
+        assert_eq!(
+            decrypt(&data, "secret").unwrap().as_slice(),
+            b"id,name\n1,Ann\n"
+        );
+        assert!(matches!(decrypt(&data, "wrong"), Err(Error::Decrypt)));
+
+        // The costs are part of the authenticated header.
+        let mut costlier = data.clone();
+        costlier[MAGIC.len() + 1] ^= 1;
+        assert!(matches!(decrypt(&costlier, "secret"), Err(Error::Decrypt)));
+        let mut newer = data.clone();
+        newer[MAGIC.len()] = VERSION + 1;
+        assert!(matches!(
+            decrypt(&newer, "secret"),
+            Err(Error::UnsupportedVersion(_))
+        ));
+        assert!(matches!(
+            decrypt(&data[..HEADER_LEN - 1], "secret"),
+            Err(Error::Truncated)
+        ));
+        assert!(matches!(
+            decrypt(b"id,name\n", "secret"),
+            Err(Error::NotEncrypted)
+        ));
+    }
+}
@@ -0,0 +1,169 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::{fmt, ops::Range};
+#[cfg(feature = "std")]
+use std::{error::Error, io};
+use thiserror::Error;
+
+/// An error at a position in a text file, rendered with the offending line.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{message}")]
+pub struct Diagnostic {
+    pub message: String,
+    pub line: usize,
+    /// The byte range of the offending text within its line.
+    pub span: Option<Range<usize>>,
+}
+impl Diagnostic {
+    pub fn new(message: impl fmt::Display, line: usize) -> Self {
+        Self {
+            message: message.to_string(),
+            line,
+            span: None,
+        }
+    }
+    pub fn at(self, span: Range<usize>) -> Self {
+        Self {
+            span: Some(span),
+            ..self
+        }
+    }
+    /// The 1-based column the span starts at in `line`, counting characters.
+    pub fn column(&self, line: &str) -> Option<usize> {
+        let start = self.span.as_ref()?.start;
+        Some(
+            line.get(..start)
+                .map_or(start, |before| before.chars().count())
+                + 1,
+        )
+    }
+
+    /// `line` with the span underlined, or all of it without one.
+    fn underline(&self, line: &str) -> String {
+        let (start, end) = match &self.span {
+            Some(span) => (span.start.min(line.len()), span.end.min(line.len())),
+            None => (0, line.len()),
+        };
+        let chars = |range: Range<usize>| line.get(range).map_or(0, |text| text.chars().count());
+        format!(
+            "{}{}",
+            " ".repeat(chars(0..start)),
+            "^".repeat(chars(start..end).max(1))
+        )
+    }
+
+    /// Renders the diagnostic under a single line of input, like a search query:
+    ///
+    /// ```text
+    /// rent >abc
+    ///       ^^^ invalid amount
+    /// ```
+    pub fn render_line(&self, source: &str) -> String {
+        format!("{}\n{} {}", source, self.underline(source), self.message)
+    }
+
+    /// Renders the diagnostic against `source`, the text it was found in:
+    ///
+    /// ```text
+    /// error: unterminated quote
+    ///  --> bills.csv:3:1
+    ///   |
+    /// 3 | "Rent,500
+    ///   | ^
+    /// ```
+    ///
+    /// Without a span the whole line is underlined.
+    pub fn render(&self, name: impl fmt::Display, source: &str) -> String {
+        let text = source
+            .split('\n')
+            .nth(self.line.saturating_sub(1))
+            .unwrap_or("")
+            .trim_end_matches('\r');
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let location = match self.column(text) {
+            Some(column) => format!("{}:{}:{}", name, self.line, column),
+            None => format!("{}:{}", name, self.line),
+        };
+        format!(
+            "error: {}\n{}--> {}\n{} |\n{} | {}\n{} | {}\n",
+            self.message,
+            gutter,
+            location,
+            gutter,
+            number,
+            text,
+            gutter,
+            self.underline(text)
+        )
+    }
+}
+
+/// An error in the field at `index` of a row, so the field can be pointed at.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{error}")]
+pub struct FieldError<E> {
+    pub index: usize,
+    pub error: E,
+}
+impl<E> FieldError<E> {
+    pub fn new(index: usize, error: E) -> Self {
+        Self { index, error }
+    }
+}
+
+/// The error for a capability this build was compiled without, so slim builds
+/// explain themselves instead of rejecting the flag as unknown.
+#[cfg(feature = "std")]
+pub fn not_built_in(feature: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!(
+            "{} is not built in, rebuild with the `{}` feature",
+            feature, feature
+        ),
+    )
+}
+
+/// Prints `error` and everything that caused it to stderr, the way every binary reports failures.
+#[cfg(feature = "std")]
+pub fn report(error: &(dyn Error + 'static)) {
+    eprintln!("error: {}", error);
+    let mut source = error.source();
+    while let Some(cause) = source {
+        eprintln!("  caused by: {}", cause);
+        source = cause.source();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_line_with_caret() {
+        let source = "name,amount\nRent,500\nGym,abc\n";
+        assert_eq!(
+            Diagnostic::new("invalid amount", 3)
+                .at(4..7)
+                .render("bills.csv", source),
+            "error: invalid amount\n --> bills.csv:3:5\n  |\n3 | Gym,abc\n  |     ^^^\n"
+        );
+        assert_eq!(
+            Diagnostic::new("invalid amount", 1)
+                .at(7..10)
+                .render_line("Café >abc"),
+            "Café >abc\n      ^^^ invalid amount"
+        );
+        assert_eq!(
+            Diagnostic::new("missing name", 2).render("bills.csv", source),
+            "error: missing name\n --> bills.csv:2\n  |\n2 | Rent,500\n  | ^^^^^^^^\n"
+        );
+    }
+}
@@ -0,0 +1,440 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{
+    borrow::Cow,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::ops::Range;
+use errors::Diagnostic;
+use thiserror::Error;
+
+const DELIMITERS: [char; 3] = [',', ';', '\t'];
+/// The byte order mark some spreadsheets put in front of UTF-8 files.
+pub const BOM: char = '\u{feff}';
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    #[error("unterminated quote")]
+    UnterminatedQuote,
+    #[error("unexpected text after closing quote")]
+    TextAfterQuote,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("line {line}, column {column}: {kind}")]
+pub struct Error {
+    pub line: usize,
+    pub column: usize,
+    /// The bytes of the line at fault.
+    pub span: Range<usize>,
+    pub kind: ErrorKind,
+}
+
+impl From<Error> for Diagnostic {
+    fn from(error: Error) -> Self {
+        Diagnostic::new(error.kind, error.line).at(error.span)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Format {
+    pub delimiter: char,
+    pub header: bool,
+}
+impl Default for Format {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            header: true,
+        }
+    }
+}
+impl Format {
+    /// Uses whichever of `,`, `;` and tab occurs most often in the first line.
+    pub fn detect(text: &str) -> Self {
+        let first = text.lines().next().unwrap_or("");
+        let delimiter = DELIMITERS
+            .iter()
+            .copied()
+            .max_by_key(|delimiter| (first.matches(*delimiter).count(), *delimiter == ','))
+            .unwrap_or(',');
+        Self {
+            delimiter,
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record<'a> {
+    pub line: usize,
+    pub text: &'a str,
+    pub fields: Vec<String>,
+    /// Where each field sits in `text`, as byte ranges.
+    pub spans: Vec<Range<usize>>,
+}
+impl Record<'_> {
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.fields.get(index).map(String::as_str)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Table<'a> {
+    pub header: Vec<String>,
+    pub records: Vec<Result<Record<'a>, Error>>,
+}
+impl Table<'_> {
+    /// Finds the first header column matching one of `names`, ignoring case and surrounding spaces.
+    pub fn column(&self, names: &[&str]) -> Option<usize> {
+        self.header.iter().position(|column| {
+            let column = column.trim();
+            names.iter().any(|name| column.eq_ignore_ascii_case(name))
+        })
+    }
+}
+
+/// A record's fields, the byte range each takes up in the text, quotes
+/// included, and the byte where the record ends.
+type Split = (Vec<String>, Vec<Range<usize>>, usize);
+
+/// Splits the record at the start of `text`. Quoted fields may span lines;
+/// anywhere else a line break ends the record.
+fn split(text: &str, line: usize, delimiter: char) -> Result<Split, Error> {
+    let error = |span: Range<usize>, kind| Error {
+        line,
+        column: text[..span.start].chars().count() + 1,
+        span,
+        kind,
+    };
+    let mut fields = vec![];
+    let mut spans = vec![];
+    let mut field = String::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    let mut end = 'record: loop {
+        match chars.peek() {
+            Some((_, '"')) => {
+                chars.next();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) if chars.peek().map(|(_, c)| *c) == Some('"') => {
+                            field.push('"');
+                            chars.next();
+                        }
+                        Some((_, '"')) => break,
+                        Some((_, c)) => field.push(c),
+                        None => return Err(error(start..text.len(), ErrorKind::UnterminatedQuote)),
+                    }
+                }
+                while chars.next_if(|(_, c)| *c == ' ').is_some() {}
+                match chars.next() {
+                    Some((index, c)) if c == delimiter => {
+                        fields.push(core::mem::take(&mut field));
+                        spans.push(start..index);
+                        start = index + c.len_utf8();
+                    }
+                    Some((index, '\n')) => break index,
+                    Some((index, '\r')) if matches!(chars.peek(), None | Some((_, '\n'))) => {
+                        break index
+                    }
+                    Some((index, _)) => {
+                        let end = text[index..]
+                            .find([delimiter, '\r', '\n'])
+                            .map_or(text.len(), |end| index + end);
+                        return Err(error(index..end, ErrorKind::TextAfterQuote));
+                    }
+                    None => break text.len(),
+                }
+            }
+            _ => loop {
+                match chars.next() {
+                    Some((index, c)) if c == delimiter => {
+                        fields.push(core::mem::take(&mut field));
+                        spans.push(start..index);
+                        start = index + c.len_utf8();
+                        break;
+                    }
+                    Some((index, '\n')) => break 'record index,
+                    Some((_, c)) => field.push(c),
+                    None => break 'record text.len(),
+                }
+            },
+        }
+    };
+    // An unquoted last field keeps the `\r` of a `\r\n` line ending otherwise.
+    if text[..end].ends_with('\r') && field.ends_with('\r') {
+        field.pop();
+        end -= 1;
+    }
+    fields.push(field);
+    spans.push(start..end);
+    Ok((fields, spans, end))
+}
+
+/// Splits a single line into its fields. Quoted fields may contain the
+/// delimiter and `""` for a quote.
+pub fn parse_line(line: &str, delimiter: char) -> Result<Vec<String>, Error> {
+    split(line, 1, delimiter).map(|(fields, _, _)| fields)
+}
+
+/// Parses every non-empty record, taking the first one as header if the format
+/// has one. A quoted field may hold line breaks, but a quote left open drops
+/// only its own line, so the rest of the file is still read.
+pub fn parse(text: &str, format: Format) -> Table<'_> {
+    let mut table = Table::default();
+    let mut rest = text.strip_prefix(BOM).unwrap_or(text);
+    let mut line = 1;
+    while !rest.is_empty() {
+        let line_end = rest.find('\n').map_or(rest.len(), |end| end + 1);
+        let first = rest[..line_end].trim_end_matches('\n');
+        let first = first.strip_suffix('\r').unwrap_or(first);
+        let (record, length) = if first.is_empty() {
+            (None, line_end)
+        } else {
+            match split(rest, line, format.delimiter) {
+                Ok((fields, spans, end)) => {
+                    let length = match &rest[end..] {
+                        after if after.starts_with("\r\n") => end + 2,
+                        after if after.starts_with('\n') => end + 1,
+                        _ => end,
+                    };
+                    let text = &rest[..end];
+                    (
+                        Some(Ok(Record {
+                            line,
+                            text,
+                            fields,
+                            spans,
+                        })),
+                        length,
+                    )
+                }
+                // Read the line on its own, so it gets its own error.
+                Err(_) => {
+                    let record =
+                        split(first, line, format.delimiter).map(|(fields, spans, _)| Record {
+                            line,
+                            text: first,
+                            fields,
+                            spans,
+                        });
+                    (Some(record), line_end)
+                }
+            }
+        };
+        line += rest[..length].matches('\n').count();
+        rest = &rest[length..];
+        match record {
+            Some(Ok(record))
+                if format.header && table.header.is_empty() && table.records.is_empty() =>
+            {
+                table.header = record.fields
+            }
+            Some(record) => table.records.push(record),
+            None => {}
+        }
+    }
+    table
+}
+
+/// Quotes fields holding the delimiter, a quote or a line break.
+pub fn quote(field: &str, delimiter: char) -> Cow<'_, str> {
+    if field.contains([delimiter, '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    /// What Windows programs expect.
+    CrLf,
+}
+impl LineEnding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Writes records that [`parse`] and spreadsheets read back as they were.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Writer {
+    pub delimiter: char,
+    pub line_ending: LineEnding,
+    /// Start tables with [`BOM`], so spreadsheets know they are UTF-8.
+    pub bom: bool,
+}
+impl Default for Writer {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            line_ending: LineEnding::Lf,
+            bom: false,
+        }
+    }
+}
+impl Writer {
+    /// Joins the fields into a line, quoting them where needed.
+    pub fn record<S: AsRef<str>>(&self, fields: &[S]) -> String {
+        let mut line = fields
+            .iter()
+            .map(|field| quote(field.as_ref(), self.delimiter))
+            .collect::<Vec<_>>()
+            .join(&self.delimiter.to_string());
+        line.push_str(self.line_ending.as_str());
+        line
+    }
+    /// A whole file: the header line, then one line per row.
+    pub fn table<H, S, R>(&self, header: &[H], rows: impl IntoIterator<Item = R>) -> String
+    where
+        H: AsRef<str>,
+        S: AsRef<str>,
+        R: AsRef<[S]>,
+    {
+        let mut text = String::new();
+        if self.bom {
+            text.push(BOM);
+        }
+        text.push_str(&self.record(header));
+        for row in rows {
+            text.push_str(&self.record(row.as_ref()));
+        }
+        text
+    }
+}
+
+/// One record as the default [`Writer`] with `delimiter` writes it.
+pub fn write_record<S: AsRef<str>>(fields: &[S], delimiter: char) -> String {
+    Writer {
+        delimiter,
+        ..Writer::default()
+    }
+    .record(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn any_fields_round_trip(
+            fields in prop::collection::vec("(\\PC|[\r\n])*", 1..8),
+            delimiter in prop::sample::select(vec![',', ';', '\t']),
+        ) {
+            let line = write_record(&fields, delimiter);
+            prop_assert_eq!(parse_line(line.trim_end_matches('\n'), delimiter).unwrap(), fields);
+        }
+    }
+
+    #[test]
+    fn quoted_fields_round_trip() {
+        let fields = ["Rent, flat", "say \"hi\"", "", "\"quoted\""];
+        let line = write_record(&fields, ',');
+        assert_eq!(
+            line,
+            "\"Rent, flat\",\"say \"\"hi\"\"\",,\"\"\"quoted\"\"\"\n"
+        );
+        assert_eq!(parse_line(line.trim_end(), ',').unwrap(), fields);
+        assert_eq!(parse_line("a;\"b;c\"", ';').unwrap(), ["a", "b;c"]);
+    }
+
+    #[test]
+    fn writes_tables_for_spreadsheets() {
+        let writer = Writer {
+            delimiter: ';',
+            line_ending: LineEnding::CrLf,
+            bom: true,
+        };
+        let text = writer.table(&["name", "notes"], [["Rent", "a;b"], ["Gym", "two\nlines"]]);
+        assert_eq!(
+            text,
+            "\u{feff}name;notes\r\nRent;\"a;b\"\r\nGym;\"two\nlines\"\r\n"
+        );
+        let table = parse(&text, Format::detect(&text));
+        assert_eq!(table.header, ["name", "notes"]);
+        let records: Vec<_> = table.records.into_iter().map(Result::unwrap).collect();
+        assert_eq!(records[0].fields, ["Rent", "a;b"]);
+        assert_eq!(records[1].fields, ["Gym", "two\nlines"]);
+        assert_eq!(records[1].line, 3);
+    }
+
+    #[test]
+    fn quoted_fields_span_lines() {
+        let text = "name,notes\n\"Ann\r\nSmith\",x\nBob,\"a\n\n\"\"b\"\"\"\n\"open,1\nCarl,2\n";
+        let table = parse(text, Format::default());
+        let records: Vec<_> = table
+            .records
+            .iter()
+            .map(|record| match record {
+                Ok(record) => Ok((record.line, record.fields.clone())),
+                Err(e) => Err(e.to_string()),
+            })
+            .collect();
+        assert_eq!(
+            records,
+            [
+                Ok((2, vec!["Ann\r\nSmith".to_string(), "x".to_string()])),
+                Ok((4, vec!["Bob".to_string(), "a\n\n\"b\"".to_string()])),
+                Err("line 7, column 1: unterminated quote".to_string()),
+                Ok((8, vec!["Carl".to_string(), "2".to_string()])),
+            ]
+        );
+    }
+
+    #[test]
+    fn errors_point_at_line_and_column() {
+        let table = parse(
+            "name,amount\nok,1\n\"open,2\r\n\"closed\"x,3\n",
+            Format::default(),
+        );
+        assert_eq!(table.header, ["name", "amount"]);
+        assert_eq!(table.column(&["Amount"]), Some(1));
+        let errors: Vec<String> = table
+            .records
+            .iter()
+            .filter_map(|record| record.as_ref().err().map(Error::to_string))
+            .collect();
+        assert_eq!(
+            errors,
+            [
+                "line 3, column 1: unterminated quote",
+                "line 4, column 9: unexpected text after closing quote"
+            ]
+        );
+        assert_eq!(table.records[0].as_ref().unwrap().line, 2);
+        assert_eq!(table.records[2].as_ref().unwrap_err().span, 8..9);
+
+        let record = parse(
+            "a,\"b, c\" ,é,\n",
+            Format {
+                header: false,
+                ..Format::default()
+            },
+        )
+        .records
+        .remove(0)
+        .unwrap();
+        assert_eq!(record.spans, [0..1, 2..9, 10..12, 13..13]);
+    }
+
+    #[test]
+    fn detects_delimiter() {
+        assert_eq!(Format::detect("date;text;amount\n").delimiter, ';');
+        assert_eq!(Format::detect("date,text;amount\n").delimiter, ',');
+        assert_eq!(Format::detect("").delimiter, ',');
+    }
+}
@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use storage::Backend;
+
+fuzz_target!(|data: &[u8]| {
+    for backend in [Backend::Csv, Backend::Json] {
+        // Broken files may fail to load, but never panic.
+        let _ = bill_manager::load_bills_from(backend, data);
+    }
+});
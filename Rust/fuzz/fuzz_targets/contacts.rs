@@ -0,0 +1,22 @@
+#![no_main]
+
+use contact_manager::{format_record, parse_records};
+use libfuzzer_sys::fuzz_target;
+use std::path::Path;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(records) = parse_records(text, Path::new("fuzz.csv")) else {
+        return;
+    };
+    records.next_id();
+    for record in records.into_vec() {
+        let file = format!("id,name,email,birthday\n{}", format_record(&record));
+        let reparsed = parse_records(&file, Path::new("fuzz.csv"))
+            .unwrap()
+            .into_vec();
+        assert_eq!(reparsed, [record]);
+    }
+});
@@ -0,0 +1,24 @@
+#![no_main]
+
+use errors::Diagnostic;
+use flatfile::Format;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+    let format = Format::detect(text);
+    for record in flatfile::parse(text, format).records {
+        match record {
+            Ok(record) => {
+                let line = flatfile::write_record(&record.fields, format.delimiter);
+                let reparsed = flatfile::parse_line(line.trim_end_matches('\n'), format.delimiter);
+                assert_eq!(reparsed.as_ref(), Ok(&record.fields));
+            }
+            Err(e) => {
+                Diagnostic::from(e).render("fuzz.csv", text);
+            }
+        }
+    }
+});
@@ -0,0 +1,381 @@
+bills-title = Rechnungen verwalten
+bills-selection = Auswahl eingeben:
+bills-selection-help = Taste vor einer Aktion drücken; Enter speichert und beendet.
+
+## Main menu, in the order of its keys
+bills-menu-add-bill = Rechnung hinzufügen
+bills-menu-view-bills = Rechnungen anzeigen
+bills-menu-remove-bill = Rechnung entfernen
+bills-menu-update-bill = Rechnung ändern
+bills-menu-bill-summary = Übersicht
+bills-menu-export-bills-to-csv = Rechnungen als CSV exportieren
+bills-menu-import-bills-from-csv = Rechnungen aus CSV importieren
+bills-menu-mark-bill-as-paid = Rechnung als bezahlt markieren
+bills-menu-view-bills-by-category = Rechnungen nach Kategorie
+bills-menu-edit-exchange-rates = Wechselkurse bearbeiten
+bills-menu-search-bills = Rechnungen suchen
+bills-menu-undo-last-change = Letzte Änderung rückgängig machen
+bills-menu-rename-bill = Rechnung umbenennen
+bills-menu-record-payment = Zahlung erfassen
+bills-menu-payment-history = Zahlungsverlauf
+bills-menu-set-category-budget = Budget für Kategorie festlegen
+bills-menu-budget-status = Budgetstatus
+bills-menu-monthly-report = Monatsbericht
+bills-menu-archive-paid-bills = Bezahlte Rechnungen archivieren
+bills-menu-view-archive = Archiv anzeigen
+bills-menu-unarchive-bill = Rechnung aus Archiv holen
+bills-menu-show-bill-details = Rechnungsdetails
+bills-menu-spending-chart = Ausgabendiagramm
+bills-menu-profiles = Profile
+bills-menu-settings = Einstellungen
+bills-menu-split-bill = Rechnung aufteilen
+bills-menu-settle-up = Abrechnen
+bills-menu-export-html-report = HTML-Bericht exportieren
+bills-menu-reconcile-bank-statement = Kontoauszug abgleichen
+bills-menu-totals-by-payment-method = Summen nach Zahlungsart
+bills-menu-attach-receipt = Beleg anhängen
+bills-menu-calendar = Kalender
+bills-menu-postpone-bill = Rechnung verschieben
+bills-menu-yearly-summary-and-tax-export = Jahresübersicht und Steuerexport
+bills-menu-suggest-recurring-bills = Wiederkehrende Rechnungen vorschlagen
+bills-menu-print-bills-as-json = Rechnungen als JSON ausgeben
+bills-menu-rotate-archive = Archiv nach Jahren auslagern
+bills-menu-action-history = Änderungsverlauf
+bills-menu-redo-last-change = Rückgängig gemachte Änderung wiederherstellen
+
+bills-invalid-profile = ungültiger Profilname: { $name }
+bills-invalid-date-format = ungültiges Datumsformat: { $format }
+bills-in-use = { $file } wird von Prozess { $pid } benutzt
+bills-open-read-only = Schreibgeschützt öffnen?
+bills-recovered = { $count } ungespeicherte Änderungen aus dem Journal wiederhergestellt
+bills-new-recurring = Neue wiederkehrende Rechnungen:
+bills-imported = { $count } Rechnungen importiert
+bills-exported = Rechnungen exportiert
+bills-no-such-plugin = kein solches Plugin: bill_manager-{ $name } ist nicht im PATH
+bills-migration = { $file }: Formatversion { $from } zu { $to }
+bills-migration-current = { $file } hat Formatversion { $version }, nichts zu migrieren
+bills-migration-dry-run = Probelauf, nichts wurde geschrieben
+bills-name-taken = Eine Rechnung mit diesem Namen gibt es schon:
+bills-name-ambiguous = mehrere Rechnungen haben diesen Namen, nimm stattdessen Rechnung ändern
+bills-added = Rechnung hinzugefügt
+bills-no-unpaid = keine offenen Rechnungen
+bills-bucket-heading = == { $bucket } (Zwischensumme { $subtotal }) ==
+bills-not-found = Rechnung nicht gefunden
+bills-removed = Rechnung entfernt
+bills-updated = Rechnung geändert
+bills-total-owed = Offen insgesamt: { $amount }
+bills-late-charges = Verzugskosten: { $amount }
+bills-paid-this-month = Diesen Monat bezahlt: { $amount }
+bills-count = Anzahl Rechnungen: { $count }
+bills-largest = Größte Rechnung: { $bill }
+bills-largest-none = Größte Rechnung: -
+bills-none-found = keine Rechnungen gefunden
+bills-renamed = Rechnung umbenannt
+bills-receipt-open-failed = Beleg konnte nicht geöffnet werden: { $error }
+bills-no-such-receipt = diesen Beleg gibt es nicht
+bills-already-paid = Rechnung ist schon bezahlt
+bills-no-due-date = Rechnung hat kein Fälligkeitsdatum
+bills-enter-days = Bitte eine ganze Zahl von Tagen eingeben
+bills-now-due = Rechnung jetzt fällig am { $date }
+bills-postpone-failed = Rechnung konnte nicht verschoben werden
+bills-not-a-file = { $path } ist keine Datei
+bills-file-name-semicolon = Dateinamen dürfen kein ';' enthalten
+bills-receipt-copy-failed = Beleg konnte nicht kopiert werden: { $error }
+bills-receipt-attached = Beleg angehängt
+bills-nothing-to-archive = keine bezahlten Rechnungen zum Archivieren
+bills-archived = { $count } bezahlte Rechnungen archiviert
+bills-archive-large = das Archiv enthält { $count } Rechnungen, Archiv nach Jahren auslagern verschiebt vergangene Jahre in Jahresdateien
+bills-archive-empty = Archiv ist leer
+bills-rotated-years = Rechnungen aus { $years } liegen in Jahresdateien und zählen in der Jahresübersicht
+bills-history-read-failed = Verlauf konnte nicht gelesen werden: { $error }
+bills-no-matching-history = kein passender Verlauf
+bills-no-past-years = keine archivierten Rechnungen aus vergangenen Jahren
+bills-year-count = { $year }: { $count } Rechnungen
+bills-rotated = { $count } Rechnungen nach { $file } verschoben
+bills-rotate-failed = { $year } konnte nicht ausgelagert werden: { $error }
+bills-unarchived = Rechnung aus dem Archiv geholt
+bills-not-in-archive = Rechnung nicht im Archiv gefunden
+bills-undone = Änderungen an { $names } rückgängig gemacht
+bills-nothing-to-undo = nichts rückgängig zu machen
+bills-redone = Änderungen an { $names } wiederhergestellt
+bills-nothing-to-redo = nichts wiederherzustellen
+bills-enter-other-currency = Bitte einen dreibuchstabigen Währungscode außer { $base } eingeben
+bills-rate-saved = Kurs gespeichert
+bills-rate-removed = Kurs entfernt
+bills-profiles-list-failed = Profile konnten nicht aufgelistet werden: { $error }
+bills-profile-in-use = das aktive oder Standardprofil kann nicht gelöscht werden
+bills-profile-not-found = Profil nicht gefunden
+bills-profile-deleted = Profil gelöscht
+bills-profile-delete-failed = Profil konnte nicht gelöscht werden: { $error }
+bills-profile-name-chars = Bitte nur Buchstaben, Ziffern, '-' und '_' verwenden
+bills-profile-created = Profil angelegt
+bills-setting-currency = 1. Währung: { $value }
+bills-setting-default-category = 2. Standardkategorie: { $value }
+bills-setting-data-file = 3. Datendatei: { $value }
+bills-setting-date-format = 4. Datumsformat: { $value }
+bills-setting-date-format-default = 4. Datumsformat: { $value } (aus der Sprache)
+bills-setting-reminder-days = 5. Tage für Erinnerungen: { $value }
+bills-setting-rounding = 6. Rundung: { $value }
+bills-setting-language = 7. Sprache: { $value }
+bills-setting-language-default = 7. Sprache: { $value } (aus LANG)
+bills-setting-tax-categories-none = 8. Steuerkategorien: -
+bills-setting-tax-categories = 8. Steuerkategorien: { $value }
+bills-setting-theme = 9. Farbschema: { $value }
+bills-enter-date-format = Bitte ein Format mit Tag, Monat und Jahr wie %d.%m.%Y eingeben
+bills-enter-setting = Bitte eine Zahl von 1 bis 9 eingeben
+bills-split-name-chars = Namen dürfen kein ':', ',' oder '%' enthalten
+bills-split-removed = Aufteilung entfernt
+bills-enter-split-names = Bitte Namen ohne ':' oder '%' eingeben
+bills-enter-percent = Bitte eine Zahl zwischen 0 und 100 eingeben
+bills-shares-total = Anteile ergeben { $percent }%, nicht 100%
+bills-split = Rechnung aufgeteilt
+bills-balances = Salden in { $currency }:
+bills-settled = alle sind quitt
+bills-owes = { $from } schuldet { $to } { $amount }
+bills-transfers-recorded = Überweisungen eingetragen, alle sind quitt
+bills-paid-next = Rechnung bezahlt, nächste Fälligkeit: { $next }
+bills-paid = Rechnung bezahlt
+bills-remaining = Offen: { $amount }
+bills-payment-settled-next = Zahlung eingetragen, Rechnung ist ganz bezahlt, nächste Fälligkeit: { $next }
+bills-payment-remaining = Zahlung eingetragen, offen: { $amount }
+bills-payment-settled = Zahlung eingetragen, Rechnung ist ganz bezahlt
+bills-no-payments = keine Zahlungen eingetragen
+bills-paid-of = { $paid } von { $amount } bezahlt, offen { $remaining }
+bills-budget-saved = Budget gespeichert
+bills-budget-removed = Budget entfernt
+bills-no-budgets = keine Budgets festgelegt
+bills-budgets-for = Budgets für { $month }:
+bills-budget-line = { $category }: { $spent } von { $limit } ({ $percent }%){ $over }
+bills-nothing-to-report = keine Rechnungen für einen Bericht
+bills-report-months = Monate mit Daten: { $months }
+bills-report-billed = In Rechnung gestellt: { $amount }
+bills-report-paid = Bezahlt: { $amount }
+bills-report-change = Änderung zum Vormonat: { $sign }{ $amount } ({ $percent }%)
+bills-report-change-new = Änderung zum Vormonat: +{ $amount }
+bills-report-change-none = Änderung zum Vormonat: k. A.
+bills-report-unconverted = ({ $count } Beträge ohne Wechselkurs nach { $base } nicht berücksichtigt)
+bills-report-exported = Bericht exportiert
+bills-report-export-failed = Bericht konnte nicht exportiert werden: { $error }
+bills-statement-read-failed = Kontoauszug konnte nicht gelesen werden: { $error }
+bills-statement-skipped = unlesbare Zeilen { $lines } übersprungen
+bills-unmatched = Nicht zugeordnete Buchungen:
+bills-no-matches = keine Buchung passt zu einer offenen Rechnung
+bills-reconciled = Rechnungen abgeglichen
+bills-none-due-this-month = diesen Monat sind keine Rechnungen fällig
+bills-enter-next-or-previous = Bitte n oder p eingeben
+bills-nothing-to-chart = keine Ausgaben für ein Diagramm
+bills-enter-year = Bitte ein Jahr wie { $example } eingeben
+bills-read-failed = { $file } konnte nicht gelesen werden: { $error }
+bills-no-payments-in = keine Zahlungen in { $year }
+bills-tax-category = { $category }: { $amount } (Steuer)
+bills-total-paid = Insgesamt bezahlt: { $amount }
+bills-tax-relevant = Steuerlich relevant: { $amount }
+bills-tax-unconverted = { $count } Zahlungen ohne Wechselkurs nach { $base } nicht berücksichtigt
+bills-no-tax-categories = keine Steuerkategorien festgelegt, zum Exportieren in den Einstellungen wählen
+bills-payments-exported = { $count } Zahlungen exportiert
+bills-payments-export-failed = Zahlungen konnten nicht exportiert werden: { $error }
+bills-no-repeating = keine sich wiederholenden Rechnungen gefunden
+bills-suggestion = #{ $id } { $name }: { $amount } { $count }-mal gesehen, wirkt { $recurrence }
+bills-repeats = { $name } wiederholt sich { $recurrence }
+bills-exported-to-file = Rechnungen exportiert
+bills-export-failed = Rechnungen konnten nicht exportiert werden: { $error }
+bills-import-failed = Rechnungen konnten nicht importiert werden: { $error }
+bills-using = nehme { $name }
+bills-matching = passende Rechnungen: { $names }
+bills-enter-id = mehrere Rechnungen heißen { $name }, bitte eine ID eingeben:
+bills-amount-negative = Der Betrag darf nicht negativ sein
+bills-amount-too-large = Der Betrag darf nicht größer als { $max } sein
+bills-enter-number = Bitte eine Zahl eingeben ({ $error })
+bills-enter-date = Bitte ein Datum wie { $example }, tomorrow oder in 2 weeks eingeben
+bills-no-rate-for = { $amount } (kein Kurs für { $currencies })
+bills-enter-method = { $error }, nimm direct debit, card, transfer oder cash
+bills-enter-priority = { $error }, nimm high, normal oder low
+bills-contact-not-found = Kontakt nicht gefunden
+bills-matching-contacts = passende Kontakte: { $names }
+bills-over-budget = { $warning }: { $category } ist über dem Budget ({ $spent } von { $limit })
+bills-overdue = Überfällig:
+bills-due-within = Fällig in den nächsten { $days } Tagen:
+bills-archive-save-failed = Archiv konnte nicht gespeichert werden: { $error }
+bills-history-write-failed = Verlauf konnte nicht geschrieben werden: { $error }
+bills-journal-clear-failed = Journal konnte nicht geleert werden: { $error }
+bills-save-failed = Rechnungen konnten nicht gespeichert werden: { $error }
+bills-opened-read-only = { $file } wird von Prozess { $pid } benutzt, schreibgeschützt geöffnet
+bills-read-only-action = { $file } ist schreibgeschützt geöffnet, diese Aktion gibt es nicht
+bills-view-save-failed = Ansichtseinstellungen konnten nicht gespeichert werden: { $error }
+bills-rates-save-failed = Kurse konnten nicht gespeichert werden: { $error }
+bills-back-to-menu = zurück zum Hauptmenü
+bills-budgets-save-failed = Budgets konnten nicht gespeichert werden: { $error }
+bills-switched-profile = zu Profil { $name } gewechselt
+bills-transfers-save-failed = Überweisungen konnten nicht gespeichert werden: { $error }
+bills-settings-save-failed = Einstellungen konnten nicht gespeichert werden: { $error }
+bills-no-config-dir = kein Konfigurationsverzeichnis, Einstellungen gelten nur für diese Sitzung
+bills-using-data-file = nutze Datendatei { $file }
+bills-journal-write-failed = Journal konnte nicht geschrieben werden: { $error }
+bills-prompt-name = Name der Rechnung:
+bills-help-name = Der Name, unter dem die Rechnung angezeigt und gesucht wird.
+bills-prompt-name-taken = (a) noch eine Rechnung mit diesem Namen, (r) ersetzen oder (c) abbrechen? [c]
+bills-help-name-taken = a behält beide Rechnungen, r ersetzt die vorhandene, c bricht ab.
+bills-prompt-amount = Betrag:
+bills-prompt-filter = (a) alle, (u) offene, (p) bezahlte Rechnungen oder (o) Überfälligkeit anzeigen? [{ $current }]
+bills-help-filter = a zeigt alle Rechnungen, u nur offene und p nur bezahlte; o gruppiert offene Rechnungen danach, wie lange sie überfällig sind.
+bills-prompt-sort = Sortieren nach (n) Name, (a) Betrag, (d) Fälligkeit oder (c) Kategorie? [{ $current }]
+bills-help-sort = Rechnungen mit gleichem Wert werden nach Namen sortiert.
+bills-help-descending = y zeigt die größten, spätesten oder letzten Werte zuerst.
+bills-prompt-group = Gruppieren nach (n) nichts oder (c) Kategorie? [{ $current }]
+bills-help-group = c zeigt die Rechnungen unter einer Überschrift je Kategorie.
+bills-confirm-remove = Diese Rechnung entfernen?
+bills-prompt-update = Zu ändernde Rechnung:
+bills-prompt-search = Suche (Namenstext, >100, 50..200, =39.99, cat:utilities; Begriffe werden kombiniert):
+bills-help-search = Wörter passen auf Rechnungsnamen, cat:text auf Kategorien; >, >=, <, <= und = vergleichen Beträge und min..max behält Beträge in einem Bereich.
+bills-prompt-rename = Umzubenennende Rechnung:
+bills-prompt-new-name = Neuer Name:
+bills-help-new-name = Rechnungen behalten beim Umbenennen ihre ID.
+bills-field-name = Name:
+bills-field-amount = Betrag:
+bills-field-category = Kategorie:
+bills-field-due = Fällig:
+bills-field-postponed = Verschoben:
+bills-postponed-times = { $count }-mal, ursprünglich fällig { $date }
+bills-field-repeats = Wiederholung:
+bills-field-paid = Bezahlt:
+bills-field-remaining = Offen:
+bills-field-late = Verzug:
+bills-field-payments = Zahlungen:
+bills-field-notes = Notizen:
+bills-field-method = Zahlungsart:
+bills-field-priority = Priorität:
+bills-field-split = Aufteilung:
+bills-payee-missing = #{ $id } (nicht in den Kontakten)
+bills-field-payee = Empfänger:
+bills-field-receipts = Belege:
+bills-prompt-open-receipt = Beleg öffnen (Nummer, leer zum Überspringen):
+bills-help-open-receipt = Öffnet die Datei mit dem Standardprogramm deines Systems.
+bills-prompt-postpone = Zu verschiebende Rechnung:
+bills-prompt-postpone-days = Tage verschieben [7]:
+bills-help-postpone-days = Das Fälligkeitsdatum rückt um so viele Tage nach hinten; das ursprüngliche Datum bleibt erhalten.
+bills-prompt-attach = Rechnung, an die ein Beleg angehängt wird:
+bills-prompt-receipt-file = Belegdatei:
+bills-help-receipt-file = Der Pfad eines Scans, Fotos oder PDFs des Belegs.
+bills-confirm-copy-receipt = Nach { $dir } kopieren?
+bills-confirm-archive = { $count } bezahlte Rechnungen archivieren?
+bills-prompt-history = Rechnung (#ID oder Name, leer für alle):
+bills-help-history = Zeigt, wann Rechnungen hinzugefügt, entfernt, geändert oder bezahlt wurden.
+bills-confirm-rotate = In komprimierte Jahresdateien verschieben?
+bills-prompt-unarchive = Aus dem Archiv zu holende Rechnung:
+bills-prompt-rate-currency = Währung, die in { $base } umgerechnet wird (leer zum Beenden):
+bills-help-rate-currency = Gib einen Währungscode ein, um seinen Kurs zu { $base } festzulegen.
+bills-prompt-profile = Profil zum Wechseln oder Anlegen (-name zum Löschen):
+bills-help-profile = Ein vorhandener Name wechselt dorthin, ein neuer Name legt es an und -name löscht es.
+bills-confirm-delete-profile = Profil { $name } mit allen Dateien löschen?
+bills-prompt-setting = Zu ändernde Einstellung (leer zum Beenden):
+bills-help-setting = Wähle eine Nummer aus der Liste oben; Einstellungen werden beim Beenden gespeichert.
+bills-prompt-default-category = Standardkategorie für neue Rechnungen (- für keine):
+bills-help-default-category = Wird genommen, wenn die Kategorie einer neuen Rechnung leer bleibt.
+bills-prompt-data-file = Datendatei:
+bills-help-data-file = Pfad der CSV-Datei mit den Rechnungen; andere Profile liegen daneben.
+bills-prompt-date-format = Datumsformat (wie %d.%m.%Y, - für den Standard der Sprache):
+bills-help-date-format = Ein strftime-Format mit Tag, Monat und Jahr zum Anzeigen und Eingeben von Daten.
+bills-prompt-reminder-days = Tage im Voraus, an fällige Rechnungen zu erinnern:
+bills-help-reminder-days = Rechnungen, die innerhalb so vieler Tage fällig sind, werden beim Start angezeigt.
+bills-prompt-rounding = Rundung (half-up, half-even, down oder up):
+bills-help-rounding = Wie Summen und umgerechnete Beträge auf die Nachkommastellen ihrer Währung gerundet werden.
+bills-prompt-language = Sprache (en oder de, - um LANG zu folgen):
+bills-help-language = Legt die Sprache der Menüs fest und wie Zahlen und Standarddaten geschrieben werden.
+bills-prompt-tax-categories = Steuerkategorien (durch Kommas getrennt, - für keine):
+bills-help-tax-categories = Zahlungen in diesen Kategorien stehen im Steuerexport.
+bills-prompt-theme = Farbschema (default, light oder mono):
+bills-help-theme = Die Farben für überfällige und bezahlte Rechnungen, Warnungen und Überschriften.
+bills-prompt-split = Aufzuteilende Rechnung:
+bills-prompt-payer = Bezahlt von (leer, um die Aufteilung zu entfernen):
+bills-help-payer = Die Person, die die Rechnung bezahlt; alle anderen schulden ihr ihren Anteil.
+bills-prompt-people = Geteilt zwischen (Namen durch Kommas getrennt):
+bills-help-people = Alle, die einen Teil der Rechnung zahlen, meist auch die zahlende Person.
+bills-prompt-shares = (e) gleiche oder (c) eigene Anteile? [e]
+bills-help-shares = e teilt die Rechnung gleichmäßig, c fragt nach einem Prozentsatz je Person.
+bills-prompt-percent = Prozent für { $name }:
+bills-help-percent = Der Teil der Rechnung, den diese Person zahlt, z. B. 40.
+bills-confirm-transfers = Diese Überweisungen als bezahlt eintragen?
+bills-method-not-set = nicht festgelegt
+bills-prompt-pay = Als bezahlt zu markierende Rechnung:
+bills-prompt-payment = Rechnung, für die eine Zahlung eingetragen wird:
+bills-prompt-payment-date = Zahlungsdatum (wie { $example }, leer für heute):
+bills-help-payment-date = Der Tag, an dem das Geld gezahlt wurde.
+bills-prompt-budget = Monatliches Limit in { $currency } (leer zum Entfernen):
+bills-prompt-month = Monat (JJJJ-MM, leer für alle):
+bills-help-month = Wähle einen der oben aufgeführten Monate.
+bills-prompt-report-file = Bericht in Datei exportieren (z. B. report.html):
+bills-help-report-file = Pfad der zu schreibenden HTML-Datei; eine vorhandene Datei wird überschrieben.
+bills-prompt-statement = Datei mit dem Kontoauszug:
+bills-help-statement = Ein CSV-Export deiner Bank mit Spalten für Datum, Beschreibung und Betrag.
+bills-confirm-reconcile = { $count } Rechnungen als bezahlt markieren?
+bills-prompt-calendar = (n) nächster oder (p) vorheriger Monat, leer zum Beenden:
+bills-help-calendar = An Tagen mit * sind Rechnungen fällig.
+bills-prompt-chart = Diagramm nach (c) Kategorie oder (m) Monat? [c]
+bills-help-chart = c summiert die Ausgaben je Kategorie, m je Monat.
+bills-help-tax-year = Zahlungen in diesem Jahr werden summiert, auch von archivierten Rechnungen.
+bills-prompt-tax-file = Steuerlich relevante Zahlungen in Datei exportieren (leer zum Überspringen):
+bills-help-csv-file = Pfad der zu schreibenden CSV-Datei; eine vorhandene Datei wird überschrieben.
+bills-prompt-mark-recurring = Als wiederkehrend markieren? (y) ja, (n) nein oder (q) beenden [n]
+bills-help-mark-recurring = y lässt die neueste Rechnung sich wiederholen, sodass beim Bezahlen die nächste angelegt wird.
+bills-prompt-export-file = In Datei exportieren:
+bills-prompt-import-file = Aus Datei importieren:
+bills-help-import-file = Pfad einer CSV-Datei im Exportformat; Rechnungen mit gleichem Namen werden ersetzt.
+bills-help-bill = Gib die ID wie #3, den Namen oder einen eindeutigen Anfang ein; Tab ergänzt Namen.
+bills-did-you-mean = Rechnung nicht gefunden, meintest du '{ $name }'?
+bills-help-amount = Gib einen Betrag wie 12.50 oder eine Summe wie 12.50+3.20*2 ein, zwischen 0 und { $max }.
+bills-prompt-due = Fälligkeitsdatum (wie { $example }, leer für keins):
+bills-prompt-due-current = Fälligkeitsdatum [{ $due }] (leer zum Behalten, - zum Löschen):
+bills-help-due = Der Tag, bis zu dem die Rechnung bezahlt sein muss; danach gilt sie als überfällig. Versteht auch today, tomorrow, next friday, in 2 weeks oder 15.03.
+bills-prompt-recurrence = Wiederholung (weekly, monthly, yearly, every N weeks/months/years; leer für nie):
+bills-prompt-recurrence-current = Wiederholung [{ $recurrence }] (leer zum Behalten, - zum Löschen):
+bills-help-recurrence = Das Bezahlen einer sich wiederholenden Rechnung legt die nächste an, z. B. monthly oder every 2 weeks.
+bills-prompt-category-default = Kategorie [{ $default }] (- für keine):
+bills-prompt-category = Kategorie (leer für keine):
+bills-help-category = Wähle eine Nummer aus der Liste oder gib eine neue Kategorie ein.
+bills-prompt-rate = Wert von 1 { $code } in { $base } (leer zum Entfernen):
+bills-help-rate = Wie viele { $base } ein { $code } wert ist, z. B. 0.92.
+bills-enter-positive = Bitte eine positive Zahl eingeben
+bills-prompt-currency = Währung [{ $default }]:
+bills-help-currency = Ein dreibuchstabiger Code wie EUR, USD oder GBP.
+bills-enter-currency = Bitte einen dreibuchstabigen Währungscode wie EUR eingeben
+bills-prompt-notes-current = Notizen [{ $notes }] (leer zum Behalten, - zum Löschen):
+bills-prompt-notes = Notizen (leer für keine):
+bills-help-notes = Freier Text, der in den Details der Rechnung steht.
+bills-prompt-method-current = Zahlungsart [{ $method }] (leer zum Behalten, - zum Löschen):
+bills-prompt-method = Zahlungsart (leer für keine):
+bills-help-method = Wie die Rechnung bezahlt wird: direct debit, card, transfer oder cash.
+bills-prompt-priority = Priorität (h) hoch, (n) normal oder (l) niedrig? [{ $current }]
+bills-help-priority = Rechnungen mit hoher Priorität stehen unter den offenen zuerst und fallen in Erinnerungen auf.
+bills-prompt-payee-current = Empfänger [#{ $id }] (ID oder Name, leer zum Behalten, - zum Löschen):
+bills-prompt-payee = Empfänger (ID oder Name, leer für keinen):
+bills-help-payee = Eine Kontakt-ID oder ein Teil eines Namens aus dem Kontaktmanager.
+bills-prompt-year = Jahr [{ $default }]:
+bills-card-title = Rechnung #{ $id }
+bills-over-budget-marker = ÜBER BUDGET
+bills-repeats-never = nie
+bills-prompt-descending = Absteigend? (y/n) [{ $current }]
+bills-line-due = (fällig { $date })
+bills-line-postponed = { $count }x verschoben
+bills-line-priority = Priorität { $priority }
+bills-line-paid = bezahlt { $date }
+bills-line-remaining = (offen { $amount })
+bills-line-overdue = ÜBERFÄLLIG
+bills-line-late = { $bill } + { $late } Verzug
+bills-warning = Warnung
+bills-reminders = == Erinnerungen ==
+bills-none-selected = keine Rechnung ausgewählt
+bills-amount-required = Betrag fehlt
+bills-amount-not-a-number = bitte eine Zahl als Betrag eingeben
+bills-amount-range = der Betrag muss zwischen 0 und { $max } liegen
+bills-name-required = Name fehlt
+bills-paid-name = { $name } bezahlt
+bills-tui-upcoming = Anstehend
+bills-tui-categories = Kategorien
+bills-tui-new = Neue Rechnung
+bills-tui-edit = { $name } bearbeiten
+bills-tui-details = Details
+bills-tui-keys = up/down wählen  a hinzufügen  e bearbeiten  p bezahlen  q beenden
+bills-tui-form-keys = tab/up/down wechseln  enter speichern  esc abbrechen
+bills-aging-due-soon = Bald fällig
+bills-aging-overdue = 1-{ $days } Tage überfällig
+bills-aging-long-overdue = { $days }+ Tage überfällig
@@ -0,0 +1,29 @@
+contacts-card-title = Kontakt #{ $id }
+contacts-card-name = Name:
+contacts-card-email = E-Mail:
+contacts-card-birthday = Geburtstag:
+contacts-column-id = ID
+contacts-column-name = Name
+contacts-column-email = E-Mail
+contacts-column-birthday = Geburtstag
+contacts-none-found = keine Kontakte gefunden!
+contacts-not-found = Kontakt nicht gefunden
+contacts-deleted = Kontakt gelöscht
+contacts-undone = letzte Änderung rückgängig gemacht
+contacts-redone = letzte Änderung wiederhergestellt
+contacts-nothing-to-undo = nichts rückgängig zu machen
+contacts-nothing-to-redo = nichts wiederherzustellen
+contacts-ask-name = Name:
+contacts-ask-name-help = Der vollständige Name des Kontakts.
+contacts-ask-email = E-Mail (leer für keine):
+contacts-ask-email-help = Wo der Kontakt erreichbar ist.
+contacts-ask-birthday = Geburtstag als JJJJ-MM-TT (leer für keinen):
+contacts-ask-birthday-help = Geburtstage werden als jährliche Kalendertermine exportiert.
+contacts-invalid-email = Bitte eine Adresse wie ann@example.com eingeben
+contacts-invalid-birthday = Bitte ein Datum wie 1990-02-03 eingeben
+contacts-confirm-add = { $name } hinzufügen?
+contacts-birthday-event = Geburtstag: { $name }
+contacts-no-such-subcommand = unbekannter Unterbefehl: { $name }
+contacts-migration = { $file }: Formatversion { $from } zu { $to }
+contacts-migration-current = { $file } hat Formatversion { $version }, nichts zu migrieren
+contacts-migration-dry-run = Probelauf, nichts wurde geschrieben
@@ -0,0 +1,5 @@
+encryption-passphrase = Passphrase:
+encryption-repeat = Passphrase wiederholen:
+encryption-empty = Die Passphrase darf nicht leer sein
+encryption-mismatch = Die Passphrasen unterscheiden sich, bitte erneut eingeben
+encryption-no-terminal = kein Terminal, um nach der Passphrase zu fragen, { $variable } setzen
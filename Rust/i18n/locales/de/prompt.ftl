@@ -0,0 +1,5 @@
+prompt-back-help = (b oder Esc geht zurück, ? zeigt diese Hilfe)
+prompt-enter-number = Bitte eine Zahl eingeben
+prompt-pick-number = Bitte eine Zahl von 1 bis { $count } wählen
+prompt-confirm-help = y bestätigt, alles andere bricht ab.
+prompt-enter-again = Bitte die Daten erneut eingeben
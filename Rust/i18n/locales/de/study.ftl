@@ -0,0 +1,2 @@
+study-wrote-contacts = { $count } Kontakte nach { $file } geschrieben
+study-wrote-bills = { $count } Rechnungen nach { $file } geschrieben
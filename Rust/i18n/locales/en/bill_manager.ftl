@@ -0,0 +1,381 @@
+bills-title = Manage Bills
+bills-selection = Enter selection:
+bills-selection-help = Press the key shown before an action; Enter saves and quits.
+
+## Main menu, in the order of its keys
+bills-menu-add-bill = Add bill
+bills-menu-view-bills = View bills
+bills-menu-remove-bill = Remove bill
+bills-menu-update-bill = Update bill
+bills-menu-bill-summary = Bill summary
+bills-menu-export-bills-to-csv = Export bills to CSV
+bills-menu-import-bills-from-csv = Import bills from CSV
+bills-menu-mark-bill-as-paid = Mark bill as paid
+bills-menu-view-bills-by-category = View bills by category
+bills-menu-edit-exchange-rates = Edit exchange rates
+bills-menu-search-bills = Search bills
+bills-menu-undo-last-change = Undo last change
+bills-menu-rename-bill = Rename bill
+bills-menu-record-payment = Record payment
+bills-menu-payment-history = Payment history
+bills-menu-set-category-budget = Set category budget
+bills-menu-budget-status = Budget status
+bills-menu-monthly-report = Monthly report
+bills-menu-archive-paid-bills = Archive paid bills
+bills-menu-view-archive = View archive
+bills-menu-unarchive-bill = Unarchive bill
+bills-menu-show-bill-details = Show bill details
+bills-menu-spending-chart = Spending chart
+bills-menu-profiles = Profiles
+bills-menu-settings = Settings
+bills-menu-split-bill = Split bill
+bills-menu-settle-up = Settle up
+bills-menu-export-html-report = Export HTML report
+bills-menu-reconcile-bank-statement = Reconcile bank statement
+bills-menu-totals-by-payment-method = Totals by payment method
+bills-menu-attach-receipt = Attach receipt
+bills-menu-calendar = Calendar
+bills-menu-postpone-bill = Postpone bill
+bills-menu-yearly-summary-and-tax-export = Yearly summary and tax export
+bills-menu-suggest-recurring-bills = Suggest recurring bills
+bills-menu-print-bills-as-json = Print bills as JSON
+bills-menu-rotate-archive = Rotate archive
+bills-menu-action-history = Action history
+bills-menu-redo-last-change = Redo last undone change
+
+bills-invalid-profile = invalid profile name: { $name }
+bills-invalid-date-format = invalid date format: { $format }
+bills-in-use = { $file } is in use by process { $pid }
+bills-open-read-only = Open it read-only?
+bills-recovered = recovered { $count } unsaved changes from the journal
+bills-new-recurring = New recurring bills:
+bills-imported = { $count } bills imported
+bills-exported = bills exported
+bills-no-such-plugin = no such plugin: bill_manager-{ $name } is not on the PATH
+bills-migration = { $file }: format version { $from } to { $to }
+bills-migration-current = { $file } is at format version { $version }, nothing to migrate
+bills-migration-dry-run = dry run, nothing was written
+bills-name-taken = A bill with this name already exists:
+bills-name-ambiguous = several bills have this name, use Update bill instead
+bills-added = Bill added
+bills-no-unpaid = no unpaid bills
+bills-bucket-heading = == { $bucket } (subtotal { $subtotal }) ==
+bills-not-found = bill not found
+bills-removed = Bill removed
+bills-updated = updated bill
+bills-total-owed = Total owed: { $amount }
+bills-late-charges = Late charges: { $amount }
+bills-paid-this-month = Paid this month: { $amount }
+bills-count = Number of bills: { $count }
+bills-largest = Largest bill: { $bill }
+bills-largest-none = Largest bill: -
+bills-none-found = no bills found
+bills-renamed = Bill renamed
+bills-receipt-open-failed = could not open receipt: { $error }
+bills-no-such-receipt = no such receipt
+bills-already-paid = bill already paid
+bills-no-due-date = bill has no due date
+bills-enter-days = Please enter a whole number of days
+bills-now-due = Bill now due { $date }
+bills-postpone-failed = could not postpone bill
+bills-not-a-file = { $path } is not a file
+bills-file-name-semicolon = File names cannot contain ';'
+bills-receipt-copy-failed = could not copy receipt: { $error }
+bills-receipt-attached = Receipt attached
+bills-nothing-to-archive = no paid bills to archive
+bills-archived = { $count } paid bills archived
+bills-archive-large = the archive holds { $count } bills, Rotate archive moves past years into yearly files
+bills-archive-empty = archive is empty
+bills-rotated-years = bills from { $years } are in yearly files and count in the yearly summary
+bills-history-read-failed = could not read history: { $error }
+bills-no-matching-history = no matching history
+bills-no-past-years = no archived bills from past years
+bills-year-count = { $year }: { $count } bills
+bills-rotated = { $count } bills moved to { $file }
+bills-rotate-failed = could not rotate { $year }: { $error }
+bills-unarchived = Bill unarchived
+bills-not-in-archive = bill not found in archive
+bills-undone = Undid changes to { $names }
+bills-nothing-to-undo = nothing to undo
+bills-redone = Redid changes to { $names }
+bills-nothing-to-redo = nothing to redo
+bills-enter-other-currency = Please enter a three-letter currency code other than { $base }
+bills-rate-saved = rate saved
+bills-rate-removed = rate removed
+bills-profiles-list-failed = could not list profiles: { $error }
+bills-profile-in-use = cannot delete the active or default profile
+bills-profile-not-found = profile not found
+bills-profile-deleted = profile deleted
+bills-profile-delete-failed = could not delete profile: { $error }
+bills-profile-name-chars = Please use only letters, digits, '-' and '_'
+bills-profile-created = profile created
+bills-setting-currency = 1. Currency: { $value }
+bills-setting-default-category = 2. Default category: { $value }
+bills-setting-data-file = 3. Data file: { $value }
+bills-setting-date-format = 4. Date format: { $value }
+bills-setting-date-format-default = 4. Date format: { $value } (from language)
+bills-setting-reminder-days = 5. Reminder days: { $value }
+bills-setting-rounding = 6. Rounding: { $value }
+bills-setting-language = 7. Language: { $value }
+bills-setting-language-default = 7. Language: { $value } (from LANG)
+bills-setting-tax-categories-none = 8. Tax categories: -
+bills-setting-tax-categories = 8. Tax categories: { $value }
+bills-setting-theme = 9. Theme: { $value }
+bills-enter-date-format = Please enter a format with day, month and year like %d.%m.%Y
+bills-enter-setting = Please enter a number from 1 to 9
+bills-split-name-chars = Names cannot contain ':', ',' or '%'
+bills-split-removed = split removed
+bills-enter-split-names = Please enter names without ':' or '%'
+bills-enter-percent = Please enter a number between 0 and 100
+bills-shares-total = shares add up to { $percent }%, not 100%
+bills-split = Bill split
+bills-balances = Balances in { $currency }:
+bills-settled = everyone is settled up
+bills-owes = { $from } owes { $to } { $amount }
+bills-transfers-recorded = Transfers recorded, everyone is settled up
+bills-paid-next = Bill paid, next instance: { $next }
+bills-paid = Bill paid
+bills-remaining = Remaining: { $amount }
+bills-payment-settled-next = Payment recorded, bill is fully paid, next instance: { $next }
+bills-payment-remaining = Payment recorded, remaining: { $amount }
+bills-payment-settled = Payment recorded, bill is fully paid
+bills-no-payments = no payments recorded
+bills-paid-of = Paid { $paid } of { $amount }, remaining { $remaining }
+bills-budget-saved = Budget saved
+bills-budget-removed = Budget removed
+bills-no-budgets = no budgets set
+bills-budgets-for = Budgets for { $month }:
+bills-budget-line = { $category }: { $spent } of { $limit } ({ $percent }%){ $over }
+bills-nothing-to-report = no bills to report on
+bills-report-months = Months with data: { $months }
+bills-report-billed = Billed: { $amount }
+bills-report-paid = Paid: { $amount }
+bills-report-change = Change from last month: { $sign }{ $amount } ({ $percent }%)
+bills-report-change-new = Change from last month: +{ $amount }
+bills-report-change-none = Change from last month: n/a
+bills-report-unconverted = ({ $count } amounts without an exchange rate to { $base } left out)
+bills-report-exported = Report exported
+bills-report-export-failed = could not export report: { $error }
+bills-statement-read-failed = could not read statement: { $error }
+bills-statement-skipped = skipped unreadable lines { $lines }
+bills-unmatched = Unmatched transactions:
+bills-no-matches = no transactions match an unpaid bill
+bills-reconciled = Bills reconciled
+bills-none-due-this-month = no bills due this month
+bills-enter-next-or-previous = Please enter n or p
+bills-nothing-to-chart = no spending to chart
+bills-enter-year = Please enter a year like { $example }
+bills-read-failed = could not read { $file }: { $error }
+bills-no-payments-in = no payments in { $year }
+bills-tax-category = { $category }: { $amount } (tax)
+bills-total-paid = Total paid: { $amount }
+bills-tax-relevant = Tax relevant: { $amount }
+bills-tax-unconverted = { $count } payments without an exchange rate to { $base } left out
+bills-no-tax-categories = no tax categories set, choose them in Settings to export
+bills-payments-exported = { $count } payments exported
+bills-payments-export-failed = could not export payments: { $error }
+bills-no-repeating = no repeating bills found
+bills-suggestion = #{ $id } { $name }: { $amount } seen { $count } times, looks { $recurrence }
+bills-repeats = { $name } repeats { $recurrence }
+bills-exported-to-file = Bills exported
+bills-export-failed = could not export bills: { $error }
+bills-import-failed = could not import bills: { $error }
+bills-using = using { $name }
+bills-matching = matching bills: { $names }
+bills-enter-id = several bills are named { $name }, please enter an id:
+bills-amount-negative = Amount cannot be negative
+bills-amount-too-large = Amount cannot be more than { $max }
+bills-enter-number = Please enter a number ({ $error })
+bills-enter-date = Please enter a date like { $example }, tomorrow or in 2 weeks
+bills-no-rate-for = { $amount } (no rate for { $currencies })
+bills-enter-method = { $error }, use direct debit, card, transfer or cash
+bills-enter-priority = { $error }, use high, normal or low
+bills-contact-not-found = contact not found
+bills-matching-contacts = matching contacts: { $names }
+bills-over-budget = { $warning }: { $category } is over budget ({ $spent } of { $limit })
+bills-overdue = Overdue:
+bills-due-within = Due within { $days } days:
+bills-archive-save-failed = could not save archive: { $error }
+bills-history-write-failed = could not write history: { $error }
+bills-journal-clear-failed = could not clear journal: { $error }
+bills-save-failed = could not save bills: { $error }
+bills-opened-read-only = { $file } is in use by process { $pid }, opened read-only
+bills-read-only-action = { $file } is opened read-only, this action is not available
+bills-view-save-failed = could not save view preferences: { $error }
+bills-rates-save-failed = could not save rates: { $error }
+bills-back-to-menu = back to main menu
+bills-budgets-save-failed = could not save budgets: { $error }
+bills-switched-profile = switched to profile { $name }
+bills-transfers-save-failed = could not save transfers: { $error }
+bills-settings-save-failed = could not save settings: { $error }
+bills-no-config-dir = no config directory, settings only last this session
+bills-using-data-file = using data file { $file }
+bills-journal-write-failed = could not write journal: { $error }
+bills-prompt-name = Bill name:
+bills-help-name = The name the bill is shown and searched by.
+bills-prompt-name-taken = (a)dd another bill with this name, (r)eplace it or (c)ancel? [c]
+bills-help-name-taken = a keeps both bills, r replaces the existing bill, c cancels.
+bills-prompt-amount = Amount:
+bills-prompt-filter = Show (a)ll, (u)npaid, (p)aid or (o)verdue aging of bills? [{ $current }]
+bills-help-filter = a shows every bill, u only unpaid and p only paid bills; o groups unpaid bills by how long they are overdue.
+bills-prompt-sort = Sort by (n)ame, (a)mount, (d)ue date or (c)ategory? [{ $current }]
+bills-help-sort = Bills with the same value are ordered by name.
+bills-help-descending = y lists the largest, latest or last values first.
+bills-prompt-group = Group by (n)othing or (c)ategory? [{ $current }]
+bills-help-group = c lists the bills under a heading per category.
+bills-confirm-remove = Remove this bill?
+bills-prompt-update = Enter bill to update:
+bills-prompt-search = Search (name text, >100, 50..200, =39.99, cat:utilities; terms are combined):
+bills-help-search = Words match bill names, cat:text matches categories; >, >=, <, <= and = compare amounts and min..max keeps amounts in a range.
+bills-prompt-rename = Enter bill to rename:
+bills-prompt-new-name = New name:
+bills-help-new-name = Bills keep their id when renamed.
+bills-field-name = Name:
+bills-field-amount = Amount:
+bills-field-category = Category:
+bills-field-due = Due:
+bills-field-postponed = Postponed:
+bills-postponed-times = { $count } times, originally due { $date }
+bills-field-repeats = Repeats:
+bills-field-paid = Paid:
+bills-field-remaining = Remaining:
+bills-field-late = Late:
+bills-field-payments = Payments:
+bills-field-notes = Notes:
+bills-field-method = Method:
+bills-field-priority = Priority:
+bills-field-split = Split:
+bills-payee-missing = #{ $id } (not in contacts)
+bills-field-payee = Payee:
+bills-field-receipts = Receipts:
+bills-prompt-open-receipt = Open receipt (number, empty to skip):
+bills-help-open-receipt = Opens the file with the default program of your system.
+bills-prompt-postpone = Enter bill to postpone:
+bills-prompt-postpone-days = Days to postpone [7]:
+bills-help-postpone-days = The due date moves forward by this many days; the original date is kept.
+bills-prompt-attach = Enter bill to attach a receipt to:
+bills-prompt-receipt-file = Receipt file:
+bills-help-receipt-file = The path of a scan, photo or PDF of the receipt.
+bills-confirm-copy-receipt = Copy it into { $dir }?
+bills-confirm-archive = Archive { $count } paid bills?
+bills-prompt-history = Bill (#id or name, empty for all):
+bills-help-history = Shows when bills were added, removed, changed or paid.
+bills-confirm-rotate = Move them into compressed yearly files?
+bills-prompt-unarchive = Enter bill to unarchive:
+bills-prompt-rate-currency = Currency to convert into { $base } (empty to finish):
+bills-help-rate-currency = Enter a currency code to set its rate against { $base }.
+bills-prompt-profile = Profile to switch to or create (-name to delete):
+bills-help-profile = An existing name switches to it, a new name creates it and -name deletes it.
+bills-confirm-delete-profile = Delete profile { $name } and all its files?
+bills-prompt-setting = Setting to change (empty to finish):
+bills-help-setting = Pick a number from the list above; settings are saved when you finish.
+bills-prompt-default-category = Default category for new bills (- for none):
+bills-help-default-category = Used when the category prompt of a new bill is left empty.
+bills-prompt-data-file = Data file:
+bills-help-data-file = Path of the CSV file bills are kept in; other profiles are stored next to it.
+bills-prompt-date-format = Date format (like %d.%m.%Y, - for the language default):
+bills-help-date-format = A strftime format with day, month and year used to show and enter dates.
+bills-prompt-reminder-days = Days ahead to remind about due bills:
+bills-help-reminder-days = Bills due within this many days are listed when the program starts.
+bills-prompt-rounding = Rounding (half-up, half-even, down or up):
+bills-help-rounding = How totals and converted amounts are rounded to the decimals of their currency.
+bills-prompt-language = Language (en or de, - to follow LANG):
+bills-help-language = Sets the menu language and how numbers and default dates are written.
+bills-prompt-tax-categories = Tax categories (separated by commas, - for none):
+bills-help-tax-categories = Payments in these categories are listed in the tax export.
+bills-prompt-theme = Theme (default, light or mono):
+bills-help-theme = The colors used for overdue and paid bills, warnings and headings.
+bills-prompt-split = Enter bill to split:
+bills-prompt-payer = Paid by (empty to remove the split):
+bills-help-payer = The person who pays the bill; everyone else owes them their share.
+bills-prompt-people = Shared between (names separated by commas):
+bills-help-people = Everyone who pays a part of the bill, usually including the payer.
+bills-prompt-shares = (e)qual or (c)ustom shares? [e]
+bills-help-shares = e splits the bill evenly, c asks for a percentage per person.
+bills-prompt-percent = Percent for { $name }:
+bills-help-percent = The part of the bill this person pays, e.g. 40.
+bills-confirm-transfers = Record these transfers as paid?
+bills-method-not-set = not set
+bills-prompt-pay = Enter bill to mark as paid:
+bills-prompt-payment = Enter bill to record a payment for:
+bills-prompt-payment-date = Payment date (like { $example }, empty for today):
+bills-help-payment-date = The day the money was paid.
+bills-prompt-budget = Monthly limit in { $currency } (empty to remove):
+bills-prompt-month = Month (YYYY-MM, empty for all):
+bills-help-month = Pick one of the months listed above.
+bills-prompt-report-file = Export report to file (e.g. report.html):
+bills-help-report-file = Path of the HTML file to write; an existing file is overwritten.
+bills-prompt-statement = Bank statement file:
+bills-help-statement = A CSV export from your bank with date, description and amount columns.
+bills-confirm-reconcile = Mark { $count } bills as paid?
+bills-prompt-calendar = (n)ext or (p)revious month, empty to finish:
+bills-help-calendar = Days marked with * have bills due.
+bills-prompt-chart = Chart by (c)ategory or (m)onth? [c]
+bills-help-chart = c totals spending per category, m per month.
+bills-help-tax-year = Payments made in this year are summed, including archived bills.
+bills-prompt-tax-file = Export tax relevant payments to file (empty to skip):
+bills-help-csv-file = Path of the CSV file to write; an existing file is overwritten.
+bills-prompt-mark-recurring = Mark as recurring? (y)es, (n)o or (q)uit [n]
+bills-help-mark-recurring = y makes the latest bill repeat so the next one is created when it is paid.
+bills-prompt-export-file = Export to file:
+bills-prompt-import-file = Import from file:
+bills-help-import-file = Path of a CSV file in the export format; bills with the same name are replaced.
+bills-help-bill = Type the bill id like #3, its name or a unique prefix; Tab completes names.
+bills-did-you-mean = bill not found, did you mean '{ $name }'?
+bills-help-amount = Enter an amount like 12.50 or a sum like 12.50+3.20*2, between 0 and { $max }.
+bills-prompt-due = Due date (like { $example }, empty for none):
+bills-prompt-due-current = Due date [{ $due }] (empty to keep, - to clear):
+bills-help-due = The day the bill has to be paid; bills past it are shown as overdue. Also takes today, tomorrow, next friday, in 2 weeks or 15.03.
+bills-prompt-recurrence = Repeats (weekly, monthly, yearly, every N weeks/months/years; empty for never):
+bills-prompt-recurrence-current = Repeats [{ $recurrence }] (empty to keep, - to clear):
+bills-help-recurrence = Paying a repeating bill creates the next one, e.g. monthly or every 2 weeks.
+bills-prompt-category-default = Category [{ $default }] (- for none):
+bills-prompt-category = Category (empty for none):
+bills-help-category = Pick a number from the list or type a new category name.
+bills-prompt-rate = Value of 1 { $code } in { $base } (empty to remove):
+bills-help-rate = How many { $base } one { $code } is worth, e.g. 0.92.
+bills-enter-positive = Please enter a positive number
+bills-prompt-currency = Currency [{ $default }]:
+bills-help-currency = A three-letter code like EUR, USD or GBP.
+bills-enter-currency = Please enter a three-letter currency code like EUR
+bills-prompt-notes-current = Notes [{ $notes }] (empty to keep, - to clear):
+bills-prompt-notes = Notes (empty for none):
+bills-help-notes = Free text shown in the bill details.
+bills-prompt-method-current = Payment method [{ $method }] (empty to keep, - to clear):
+bills-prompt-method = Payment method (empty for none):
+bills-help-method = How the bill is paid: direct debit, card, transfer or cash.
+bills-prompt-priority = Priority (h)igh, (n)ormal or (l)ow? [{ $current }]
+bills-help-priority = High priority bills are listed first among unpaid bills and stand out in reminders.
+bills-prompt-payee-current = Payee contact [#{ $id }] (id or name, empty to keep, - to clear):
+bills-prompt-payee = Payee contact (id or name, empty for none):
+bills-help-payee = A contact id or part of a name from the contact manager.
+bills-prompt-year = Year [{ $default }]:
+bills-card-title = Bill #{ $id }
+bills-over-budget-marker = OVER BUDGET
+bills-repeats-never = never
+bills-prompt-descending = Descending? (y/n) [{ $current }]
+bills-line-due = (due { $date })
+bills-line-postponed = postponed { $count }x
+bills-line-priority = { $priority } priority
+bills-line-paid = paid { $date }
+bills-line-remaining = (remaining { $amount })
+bills-line-overdue = OVERDUE
+bills-line-late = { $bill } + { $late } late
+bills-warning = Warning
+bills-reminders = == Reminders ==
+bills-none-selected = no bill selected
+bills-amount-required = amount is required
+bills-amount-not-a-number = please enter a number as amount
+bills-amount-range = amount must be between 0 and { $max }
+bills-name-required = name is required
+bills-paid-name = { $name } paid
+bills-tui-upcoming = Upcoming
+bills-tui-categories = Categories
+bills-tui-new = New bill
+bills-tui-edit = Edit { $name }
+bills-tui-details = Details
+bills-tui-keys = up/down select  a add  e edit  p pay  q quit
+bills-tui-form-keys = tab/up/down move  enter save  esc cancel
+bills-aging-due-soon = Due soon
+bills-aging-overdue = 1-{ $days } days overdue
+bills-aging-long-overdue = { $days }+ days overdue
@@ -0,0 +1,29 @@
+contacts-card-title = Contact #{ $id }
+contacts-card-name = Name:
+contacts-card-email = Email:
+contacts-card-birthday = Birthday:
+contacts-column-id = ID
+contacts-column-name = Name
+contacts-column-email = Email
+contacts-column-birthday = Birthday
+contacts-none-found = no records found!
+contacts-not-found = record not found
+contacts-deleted = record deleted
+contacts-undone = undid the last change
+contacts-redone = redid the last change
+contacts-nothing-to-undo = nothing to undo
+contacts-nothing-to-redo = nothing to redo
+contacts-ask-name = Name:
+contacts-ask-name-help = The contact's full name.
+contacts-ask-email = Email (empty for none):
+contacts-ask-email-help = Where to reach the contact.
+contacts-ask-birthday = Birthday as YYYY-MM-DD (empty for none):
+contacts-ask-birthday-help = Birthdays are exported as yearly calendar events.
+contacts-invalid-email = Please enter an address like ann@example.com
+contacts-invalid-birthday = Please enter a date like 1990-02-03
+contacts-confirm-add = Add { $name }?
+contacts-birthday-event = Birthday: { $name }
+contacts-no-such-subcommand = no such subcommand: { $name }
+contacts-migration = { $file }: format version { $from } to { $to }
+contacts-migration-current = { $file } is at format version { $version }, nothing to migrate
+contacts-migration-dry-run = dry run, nothing was written
@@ -0,0 +1,5 @@
+encryption-passphrase = Passphrase:
+encryption-repeat = Repeat the passphrase:
+encryption-empty = The passphrase cannot be empty
+encryption-mismatch = The passphrases differ, please try again
+encryption-no-terminal = no terminal to ask for the passphrase, set { $variable }
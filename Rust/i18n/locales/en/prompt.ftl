@@ -0,0 +1,5 @@
+prompt-back-help = (b or Esc goes back, ? shows this help)
+prompt-enter-number = Please enter a number
+prompt-pick-number = Please pick a number from 1 to { $count }
+prompt-confirm-help = y confirms, anything else cancels.
+prompt-enter-again = Please enter your data again
@@ -0,0 +1,2 @@
+study-wrote-contacts = wrote { $count } contacts to { $file }
+study-wrote-bills = wrote { $count } bills to { $file }
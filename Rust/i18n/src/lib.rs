@@ -0,0 +1,305 @@
+use fluent_bundle::{concurrent::FluentBundle, FluentResource};
+use std::{
+    env,
+    sync::{LazyLock, RwLock},
+};
+use unic_langid::LanguageIdentifier;
+
+pub use fluent_bundle::FluentArgs;
+
+/// The messages of every binary and the shared crates, per language.
+const CATALOGS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            include_str!("../locales/en/bill_manager.ftl"),
+            include_str!("../locales/en/contact_manager.ftl"),
+            include_str!("../locales/en/encryption.ftl"),
+            include_str!("../locales/en/prompt.ftl"),
+            include_str!("../locales/en/study.ftl"),
+        ],
+    ),
+    (
+        "de",
+        &[
+            include_str!("../locales/de/bill_manager.ftl"),
+            include_str!("../locales/de/contact_manager.ftl"),
+            include_str!("../locales/de/encryption.ftl"),
+            include_str!("../locales/de/prompt.ftl"),
+            include_str!("../locales/de/study.ftl"),
+        ],
+    ),
+];
+/// The language every lookup ends with, since it has every message.
+const FALLBACK: &str = "en";
+
+static CATALOG: LazyLock<RwLock<Catalog>> = LazyLock::new(|| RwLock::new(Catalog::new("")));
+
+/// Bundles in lookup order, so a message missing from a translation falls
+/// back to the next language instead of showing its id.
+pub struct Catalog {
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+impl Catalog {
+    /// Looks messages up in `requested`, like `de-AT` or `de_AT.UTF-8`, then
+    /// its base language, then English.
+    pub fn new(requested: &str) -> Catalog {
+        let tag = requested
+            .split(['.', '@'])
+            .next()
+            .unwrap_or_default()
+            .replace('_', "-");
+        let mut chain = vec![];
+        if let Ok(language) = tag.parse::<LanguageIdentifier>() {
+            chain.push(language.to_string());
+            chain.push(language.language.to_string());
+        }
+        chain.push(FALLBACK.to_string());
+
+        let mut bundles: Vec<FluentBundle<FluentResource>> = vec![];
+        for candidate in chain {
+            let Some((name, sources)) = CATALOGS
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(&candidate))
+            else {
+                continue;
+            };
+            let language: LanguageIdentifier = name.parse().expect("catalog names are valid");
+            if bundles.iter().any(|bundle| bundle.locales[0] == language) {
+                continue;
+            }
+            let mut bundle = FluentBundle::new_concurrent(vec![language]);
+            bundle.set_use_isolating(false);
+            for source in *sources {
+                let resource = FluentResource::try_new(source.to_string())
+                    .unwrap_or_else(|(resource, _)| resource);
+                bundle.add_resource_overriding(resource);
+            }
+            bundles.push(bundle);
+        }
+        Catalog { bundles }
+    }
+    /// The language messages are looked up in first.
+    pub fn language(&self) -> String {
+        self.bundles
+            .first()
+            .map_or(FALLBACK.to_string(), |bundle| bundle.locales[0].to_string())
+    }
+    /// Formats message `id`, or returns the id when no language has it.
+    pub fn format(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        for bundle in &self.bundles {
+            if let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) {
+                let mut errors = vec![];
+                return bundle
+                    .format_pattern(pattern, args, &mut errors)
+                    .into_owned();
+            }
+        }
+        id.to_string()
+    }
+}
+
+/// The language from `LC_ALL`, `LC_MESSAGES` or `LANG`, whichever is set first.
+pub fn from_env() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty())
+}
+
+/// Switches every later message to `requested`, or to the environment's
+/// language without one. Until then messages are English.
+pub fn set_language(requested: Option<&str>) {
+    let requested = requested.map(String::from).or_else(from_env);
+    let catalog = Catalog::new(requested.as_deref().unwrap_or_default());
+    if let Ok(mut current) = CATALOG.write() {
+        *current = catalog;
+    }
+}
+
+pub fn language() -> String {
+    CATALOG
+        .read()
+        .map_or(FALLBACK.to_string(), |catalog| catalog.language())
+}
+
+pub fn text(id: &str, args: Option<&FluentArgs>) -> String {
+    match CATALOG.read() {
+        Ok(catalog) => catalog.format(id, args),
+        Err(_) => id.to_string(),
+    }
+}
+
+/// Formats a message in the current language, like
+/// `t!("contacts-confirm-add", name = record.name)`.
+#[macro_export]
+macro_rules! t {
+    ($id:expr) => {
+        $crate::text($id, None)
+    };
+    ($id:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = $crate::FluentArgs::new();
+        $(args.set(stringify!($name), $value.to_string());)+
+        $crate::text($id, Some(&args))
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(source: &str) -> Vec<&str> {
+        source
+            .lines()
+            .filter_map(|line| line.split_once(" ="))
+            .map(|(id, _)| id)
+            .filter(|id| !id.starts_with([' ', '#']))
+            .collect()
+    }
+
+    #[test]
+    fn every_language_has_every_message() {
+        let (_, english) = CATALOGS[0];
+        for (language, sources) in &CATALOGS[1..] {
+            for (source, translated) in english.iter().zip(*sources) {
+                let missing: Vec<_> = ids(source)
+                    .into_iter()
+                    .filter(|id| !ids(translated).contains(id))
+                    .collect();
+                assert!(missing.is_empty(), "{} lacks {:?}", language, missing);
+            }
+        }
+    }
+
+    /// The Rust sources of the crates that print through the catalogs.
+    fn sources() -> Vec<(std::path::PathBuf, String)> {
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        let mut sources = vec![];
+        let mut dirs: Vec<_> = [
+            "bill_manager",
+            "contact_manager",
+            "encryption",
+            "prompt",
+            "study",
+        ]
+        .iter()
+        .map(|name| root.join(name).join("src"))
+        .collect();
+        while let Some(dir) = dirs.pop() {
+            for entry in std::fs::read_dir(&dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else if path.extension().is_some_and(|extension| extension == "rs") {
+                    let source = std::fs::read_to_string(&path).unwrap();
+                    sources.push((path, source));
+                }
+            }
+        }
+        sources
+    }
+
+    /// The string literal a call starts with, also after a `ui,` or `f,`.
+    fn first_literal(args: &str) -> Option<&str> {
+        let args = args.trim_start();
+        let args = match args.split_once(',') {
+            Some((first, rest))
+                if !first.is_empty()
+                    && first
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || c == '_' || c == '.') =>
+            {
+                rest.trim_start()
+            }
+            _ => args,
+        };
+        args.trim_start_matches('&')
+            .strip_prefix('"')?
+            .split('"')
+            .next()
+    }
+
+    fn has_words(format: &str) -> bool {
+        let mut text = String::new();
+        let mut placeholder = false;
+        for c in format.chars() {
+            match c {
+                '{' => placeholder = true,
+                '}' => placeholder = false,
+                c if !placeholder => text.push(c),
+                _ => {}
+            }
+        }
+        text.split(|c: char| !c.is_ascii_alphabetic())
+            .any(|word| word.len() >= 3)
+    }
+
+    #[test]
+    fn sources_take_their_messages_from_the_catalogs() {
+        let (_, english) = CATALOGS[0];
+        let known: Vec<&str> = english.iter().flat_map(|source| ids(source)).collect();
+        // The script protocol is read by programs and the HTML report is a
+        // file, not terminal output.
+        let exempt = ["script.rs", "html.rs"];
+        for (path, source) in sources() {
+            let code = source.split("#[cfg(test)]").next().unwrap_or_default();
+            for (at, _) in code.match_indices("t!(\"") {
+                if code[..at].ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+                    continue;
+                }
+                let id = code[at + 4..].split('"').next().unwrap_or_default();
+                assert!(
+                    known.contains(&id),
+                    "{} uses unknown {}",
+                    path.display(),
+                    id
+                );
+            }
+            if path
+                .file_name()
+                .is_some_and(|name| exempt.iter().any(|exempt| name == *exempt))
+            {
+                continue;
+            }
+            for call in [
+                "writeln!(",
+                "write!(",
+                "println!(",
+                "eprintln!(",
+                ".prompt(",
+                ".title(",
+                "paint(",
+            ] {
+                for (at, _) in code.match_indices(call) {
+                    if let Some(literal) = first_literal(&code[at + call.len()..]) {
+                        assert!(
+                            !has_words(literal),
+                            "{} prints {:?} instead of a catalog message",
+                            path.display(),
+                            literal
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn falls_back_to_base_language_then_english() {
+        let mut args = FluentArgs::new();
+        args.set("name", "Ann");
+        let austrian = Catalog::new("de_AT.UTF-8");
+        assert_eq!(austrian.language(), "de");
+        assert_eq!(
+            austrian.format("contacts-confirm-add", Some(&args)),
+            "Ann hinzufügen?"
+        );
+        let klingon = Catalog::new("tlh");
+        assert_eq!(klingon.language(), "en");
+        assert_eq!(
+            klingon.format("contacts-confirm-add", Some(&args)),
+            "Add Ann?"
+        );
+        assert_eq!(klingon.format("no-such-message", None), "no-such-message");
+    }
+}
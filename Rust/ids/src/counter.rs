@@ -0,0 +1,81 @@
+#[cfg(feature = "std")]
+use std::{io, path::Path};
+#[cfg(feature = "std")]
+use vfs::Vfs;
+
+/// Hands out increasing ids from the last one issued. Kept in a file, it
+/// never hands out an id again even after its record is deleted.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Counter {
+    pub last: i64,
+}
+impl Counter {
+    pub fn after(last: i64) -> Self {
+        Self { last }
+    }
+    /// The next id that is not `taken`. Past `i64::MAX` it fills the gaps
+    /// from 1, and gives 0 only when every positive id is taken.
+    pub fn next(&mut self, taken: impl Fn(i64) -> bool) -> i64 {
+        let after = match self.last.max(0).checked_add(1) {
+            Some(first) => (first..=i64::MAX).find(|id| !taken(*id)),
+            None => None,
+        };
+        let id = after.unwrap_or_else(|| (1..=i64::MAX).find(|id| !taken(*id)).unwrap_or(0));
+        self.last = self.last.max(id);
+        id
+    }
+
+    /// Reads the counter kept at `path`, starting from 0 without one.
+    #[cfg(feature = "std")]
+    pub fn load(vfs: &dyn Vfs, path: &Path) -> io::Result<Self> {
+        match vfs.read_to_string(path) {
+            Ok(text) => text
+                .trim()
+                .parse()
+                .map(Self::after)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+    #[cfg(feature = "std")]
+    pub fn save(&self, vfs: &dyn Vfs, path: &Path) -> io::Result<()> {
+        vfs.write_atomic(path, format!("{}\n", self.last).as_bytes())
+    }
+}
+
+/// The id after the `largest` one in use, for data that keeps no counter.
+pub fn sequential(largest: Option<i64>, taken: impl Fn(i64) -> bool) -> i64 {
+    Counter::after(largest.unwrap_or(0)).next(taken)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_taken_ids_and_fills_gaps_at_the_end() {
+        let mut counter = Counter::after(2);
+        assert_eq!(counter.next(|id| id == 3), 4);
+        assert_eq!(counter.last, 4);
+        assert_eq!(sequential(None, |_| false), 1);
+        assert_eq!(
+            sequential(Some(i64::MAX), |id| id <= 2 || id == i64::MAX),
+            3
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn keeps_its_place_in_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("next_id");
+        let vfs = vfs::Disk;
+        let mut counter = Counter::load(&vfs, &path).unwrap();
+        assert_eq!(counter.next(|_| false), 1);
+        counter.save(&vfs, &path).unwrap();
+        // The record with id 1 is gone, but its id stays used.
+        assert_eq!(Counter::load(&vfs, &path).unwrap().next(|_| false), 2);
+        assert!(!vfs::temporary(&path).exists());
+    }
+}
@@ -0,0 +1,52 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod counter;
+mod random;
+
+pub use counter::{sequential, Counter};
+pub use random::{Ulid, Uuid7};
+
+use alloc::string::String;
+use thiserror::Error;
+
+/// How often a random id is drawn again when it collides before giving up.
+pub const ATTEMPTS: usize = 16;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    #[error("no free id after {0} attempts")]
+    Exhausted(usize),
+    #[error("not a valid {kind}: {text}")]
+    Parse { kind: &'static str, text: String },
+    #[error("no random bits: {0}")]
+    Random(String),
+}
+
+/// Draws ids from `generate` until one is not `taken`.
+pub fn unique<T>(
+    mut generate: impl FnMut() -> Result<T, Error>,
+    taken: impl Fn(&T) -> bool,
+) -> Result<T, Error> {
+    for _ in 0..ATTEMPTS {
+        let id = generate()?;
+        if !taken(&id) {
+            return Ok(id);
+        }
+    }
+    Err(Error::Exhausted(ATTEMPTS))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_collisions() {
+        let mut drawn = [3, 2, 1].into_iter();
+        let id = unique(|| Ok(drawn.next().unwrap()), |id| *id > 1);
+        assert_eq!(id, Ok(1));
+        assert_eq!(unique(|| Ok(7), |_| true), Err(Error::Exhausted(ATTEMPTS)));
+    }
+}
@@ -0,0 +1,166 @@
+use crate::Error;
+use alloc::string::ToString;
+use core::{fmt, str::FromStr};
+
+const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const MILLIS: u128 = (1 << 48) - 1;
+
+/// A UUID version 7: 48 bits of Unix milliseconds then random bits, so ids
+/// sort by when they were made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Uuid7(pub u128);
+impl Uuid7 {
+    pub fn from_parts(millis: u64, random: u128) -> Self {
+        let rand_a = (random >> 62) & 0xfff;
+        let rand_b = random & ((1 << 62) - 1);
+        Self((u128::from(millis) & MILLIS) << 80 | 0x7 << 76 | rand_a << 64 | 0b10 << 62 | rand_b)
+    }
+    #[cfg(feature = "std")]
+    pub fn now() -> Result<Self, Error> {
+        let (millis, random) = now()?;
+        Ok(Self::from_parts(millis, random))
+    }
+    pub fn millis(&self) -> u64 {
+        (self.0 >> 80) as u64
+    }
+}
+impl fmt::Display for Uuid7 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let hex = self.0;
+        write!(
+            f,
+            "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+            hex >> 96,
+            (hex >> 80) & 0xffff,
+            (hex >> 64) & 0xffff,
+            (hex >> 48) & 0xffff,
+            hex & 0xffff_ffff_ffff
+        )
+    }
+}
+impl FromStr for Uuid7 {
+    type Err = Error;
+    fn from_str(text: &str) -> Result<Self, Error> {
+        let invalid = || Error::Parse {
+            kind: "UUIDv7",
+            text: text.to_string(),
+        };
+        let groups: [usize; 5] = [8, 4, 4, 4, 12];
+        let mut parts = text.split('-');
+        let mut value = 0u128;
+        for length in groups {
+            let part = parts.next().filter(|part| part.len() == length);
+            let part = part.ok_or_else(invalid)?;
+            if !part.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+                return Err(invalid());
+            }
+            value =
+                value << (4 * length) | u128::from_str_radix(part, 16).map_err(|_| invalid())?;
+        }
+        let version = (value >> 76) & 0xf;
+        let variant = (value >> 62) & 0b11;
+        if parts.next().is_some() || version != 7 || variant != 0b10 {
+            return Err(invalid());
+        }
+        Ok(Self(value))
+    }
+}
+
+/// A ULID: 48 bits of Unix milliseconds then 80 random bits, written as 26
+/// Crockford base32 characters that sort by when they were made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ulid(pub u128);
+impl Ulid {
+    pub fn from_parts(millis: u64, random: u128) -> Self {
+        Self((u128::from(millis) & MILLIS) << 80 | random & ((1 << 80) - 1))
+    }
+    #[cfg(feature = "std")]
+    pub fn now() -> Result<Self, Error> {
+        let (millis, random) = now()?;
+        Ok(Self::from_parts(millis, random))
+    }
+    pub fn millis(&self) -> u64 {
+        (self.0 >> 80) as u64
+    }
+}
+impl fmt::Display for Ulid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for index in (0..26).rev() {
+            let digit = (self.0 >> (5 * index)) & 0x1f;
+            write!(f, "{}", CROCKFORD[digit as usize] as char)?;
+        }
+        Ok(())
+    }
+}
+impl FromStr for Ulid {
+    type Err = Error;
+    fn from_str(text: &str) -> Result<Self, Error> {
+        let invalid = || Error::Parse {
+            kind: "ULID",
+            text: text.to_string(),
+        };
+        // The first character only holds 3 bits, since 26 characters carry 130.
+        if text.len() != 26 || !matches!(text.as_bytes()[0], b'0'..=b'7') {
+            return Err(invalid());
+        }
+        let mut value = 0u128;
+        for byte in text.bytes() {
+            let digit = CROCKFORD
+                .iter()
+                .position(|&c| c == byte.to_ascii_uppercase())
+                .ok_or_else(invalid)?;
+            value = value << 5 | digit as u128;
+        }
+        Ok(Self(value))
+    }
+}
+
+#[cfg(feature = "std")]
+fn now() -> Result<(u64, u128), Error> {
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |since| since.as_millis() as u64);
+    let mut random = [0; 16];
+    getrandom::fill(&mut random).map_err(|e| Error::Random(e.to_string()))?;
+    Ok((millis, u128::from_le_bytes(random)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uuid7_layout_and_text() {
+        let id = Uuid7::from_parts(0x0189_7a2b_3c4d, u128::MAX);
+        assert_eq!(id.to_string(), "01897a2b-3c4d-7fff-bfff-ffffffffffff");
+        assert_eq!(id.millis(), 0x0189_7a2b_3c4d);
+        assert_eq!("01897A2B-3C4D-7FFF-BFFF-FFFFFFFFFFFF".parse(), Ok(id));
+        // Version 4, not 7.
+        assert!("01897a2b-3c4d-4fff-bfff-ffffffffffff"
+            .parse::<Uuid7>()
+            .is_err());
+        assert!("01897a2b3c4d7fffbfffffffffffffff".parse::<Uuid7>().is_err());
+    }
+
+    #[test]
+    fn ulid_layout_and_text() {
+        let id = Ulid::from_parts(1, 0);
+        assert_eq!(id.to_string(), "00000000010000000000000000");
+        assert_eq!(
+            Ulid::from_parts(u64::MAX, u128::MAX).to_string(),
+            "7ZZZZZZZZZZZZZZZZZZZZZZZZZ"
+        );
+        assert_eq!("0000000001000000000000000a".parse(), Ok(Ulid(1 << 80 | 10)));
+        assert!("80000000000000000000000000".parse::<Ulid>().is_err());
+        assert!("0000000001000000000000000U".parse::<Ulid>().is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn made_later_sorts_later() {
+        let first = Ulid::now().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        assert!(Ulid::now().unwrap() > first);
+        assert!(Uuid7::now().unwrap().millis() >= first.millis());
+    }
+}
@@ -0,0 +1,35 @@
+use std::{fs::OpenOptions, io, path::Path, sync::Mutex};
+use tracing_subscriber::{fmt, prelude::*, EnvFilter};
+
+/// Sends log events to stderr and, if given, appends them to `log_file`.
+///
+/// Warnings are shown by default and `verbose` adds debug output. Setting
+/// `<BINARY>_LOG`, like `BILL_MANAGER_LOG=debug`, overrides both with an
+/// env-filter directive.
+pub fn init(binary: &str, verbose: bool, log_file: Option<&Path>) -> io::Result<()> {
+    let variable = format!("{}_LOG", binary.to_uppercase());
+    let filter = match std::env::var(&variable) {
+        Ok(directives) => EnvFilter::try_new(directives).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidInput, format!("{}: {}", variable, e))
+        })?,
+        Err(_) => EnvFilter::new(if verbose { "debug" } else { "warn" }),
+    };
+    let stderr = fmt::layer()
+        .with_writer(io::stderr)
+        .without_time()
+        .with_level(false)
+        .with_target(false);
+    let file = match log_file {
+        Some(path) => {
+            let file = OpenOptions::new().create(true).append(true).open(path)?;
+            Some(fmt::layer().with_writer(Mutex::new(file)).with_ansi(false))
+        }
+        None => None,
+    };
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(stderr)
+        .with(file)
+        .try_init()
+        .map_err(|e| io::Error::other(e.to_string()))
+}
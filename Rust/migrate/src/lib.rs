@@ -0,0 +1,190 @@
+#![no_std]
+
+extern crate alloc;
+
+mod table;
+
+pub use table::Table;
+
+use alloc::vec::Vec;
+use core::fmt;
+use thiserror::Error;
+
+/// One numbered change to how data is stored. Migrations are numbered from
+/// 1 without gaps, and data that never went through one is at version 0.
+pub struct Migration<T> {
+    pub version: u32,
+    pub description: &'static str,
+    pub up: fn(&mut T),
+    /// Takes the change back, for handing data to an older build.
+    pub down: fn(&mut T),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+}
+
+/// A migration to run and which way.
+pub struct Step<'a, T> {
+    pub migration: &'a Migration<T>,
+    pub direction: Direction,
+}
+impl<T> Step<'_, T> {
+    pub fn run(&self, data: &mut T) {
+        match self.direction {
+            Direction::Up => (self.migration.up)(data),
+            Direction::Down => (self.migration.down)(data),
+        }
+    }
+}
+impl<T> fmt::Display for Step<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let arrow = match self.direction {
+            Direction::Up => "up",
+            Direction::Down => "down",
+        };
+        write!(
+            f,
+            "{} {}: {}",
+            arrow, self.migration.version, self.migration.description
+        )
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    #[error("stored at version {version}, newer than version {latest} this build knows")]
+    Newer { version: u32, latest: u32 },
+    #[error("there is no version {version}, this build knows up to version {latest}")]
+    Unknown { version: u32, latest: u32 },
+    #[error("migration {version} is listed where migration {expected} belongs")]
+    Misnumbered { version: u32, expected: u32 },
+}
+
+/// The version data is at after every migration ran.
+pub fn latest<T>(migrations: &[Migration<T>]) -> u32 {
+    migrations.len() as u32
+}
+
+/// The steps that bring data at version `from` to version `to`, without
+/// running them, which is all a dry run needs.
+pub fn plan<T>(migrations: &[Migration<T>], from: u32, to: u32) -> Result<Vec<Step<'_, T>>, Error> {
+    for (index, migration) in migrations.iter().enumerate() {
+        let expected = index as u32 + 1;
+        if migration.version != expected {
+            return Err(Error::Misnumbered {
+                version: migration.version,
+                expected,
+            });
+        }
+    }
+    let latest = latest(migrations);
+    if from > latest {
+        return Err(Error::Newer {
+            version: from,
+            latest,
+        });
+    }
+    if to > latest {
+        return Err(Error::Unknown {
+            version: to,
+            latest,
+        });
+    }
+    let steps = if from <= to {
+        migrations[from as usize..to as usize]
+            .iter()
+            .map(|migration| Step {
+                migration,
+                direction: Direction::Up,
+            })
+            .collect()
+    } else {
+        migrations[to as usize..from as usize]
+            .iter()
+            .rev()
+            .map(|migration| Step {
+                migration,
+                direction: Direction::Down,
+            })
+            .collect()
+    };
+    Ok(steps)
+}
+
+/// Runs the steps from version `from` to `to` on `data` and returns them.
+pub fn migrate<'a, T>(
+    migrations: &'a [Migration<T>],
+    data: &mut T,
+    from: u32,
+    to: u32,
+) -> Result<Vec<Step<'a, T>>, Error> {
+    let steps = plan(migrations, from, to)?;
+    for step in &steps {
+        step.run(data);
+    }
+    Ok(steps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{string::ToString, vec};
+
+    const MIGRATIONS: &[Migration<Table>] = &[
+        Migration {
+            version: 1,
+            description: "add a currency column",
+            up: |table| table.add_column("currency", "EUR"),
+            down: |table| table.remove_column("currency"),
+        },
+        Migration {
+            version: 2,
+            description: "rename sum to amount",
+            up: |table| table.rename_column("sum", "amount"),
+            down: |table| table.rename_column("amount", "sum"),
+        },
+    ];
+
+    fn table(columns: &[&str], row: &[&str]) -> Table {
+        Table::new(
+            columns.iter().map(|column| column.to_string()).collect(),
+            vec![row.iter().map(|field| field.to_string()).collect()],
+        )
+    }
+
+    #[test]
+    fn migrates_both_ways() {
+        let old = table(&["name", "sum"], &["Rent", "500"]);
+        let mut data = old.clone();
+        let steps = migrate(MIGRATIONS, &mut data, 0, latest(MIGRATIONS)).unwrap();
+        assert_eq!(steps[1].to_string(), "up 2: rename sum to amount");
+        assert_eq!(
+            data,
+            table(&["name", "amount", "currency"], &["Rent", "500", "EUR"])
+        );
+        assert_eq!(data.select(&["amount", "due"]), [["500", ""]]);
+
+        let steps = migrate(MIGRATIONS, &mut data, 2, 0).unwrap();
+        assert_eq!(steps[0].to_string(), "down 2: rename sum to amount");
+        assert_eq!(data, old);
+
+        assert_eq!(plan(MIGRATIONS, 2, 2).unwrap().len(), 0);
+        assert!(matches!(
+            plan(MIGRATIONS, 3, 2),
+            Err(Error::Newer {
+                version: 3,
+                latest: 2
+            })
+        ));
+        assert!(matches!(
+            plan(&MIGRATIONS[1..], 0, 1),
+            Err(Error::Misnumbered {
+                version: 2,
+                expected: 1
+            })
+        ));
+    }
+}
@@ -0,0 +1,77 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Stored rows under their column names, the shape migrations work on.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Table {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+impl Table {
+    pub fn new(columns: Vec<String>, rows: Vec<Vec<String>>) -> Self {
+        Self { columns, rows }
+    }
+    pub fn column(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|column| column == name)
+    }
+    /// Adds `name` at the end, filled with `default`, unless it is there already.
+    pub fn add_column(&mut self, name: &str, default: &str) {
+        if self.column(name).is_some() {
+            return;
+        }
+        let width = self.columns.len();
+        self.columns.push(name.to_string());
+        for row in &mut self.rows {
+            row.resize(width, String::new());
+            row.push(default.to_string());
+        }
+    }
+    pub fn remove_column(&mut self, name: &str) {
+        if let Some(index) = self.column(name) {
+            self.columns.remove(index);
+            for row in self.rows.iter_mut().filter(|row| index < row.len()) {
+                row.remove(index);
+            }
+        }
+    }
+    pub fn rename_column(&mut self, from: &str, to: &str) {
+        if let Some(index) = self.column(from) {
+            self.columns[index] = to.to_string();
+        }
+    }
+    /// The value of `column` in row `row`, empty if either is missing.
+    pub fn get(&self, row: usize, column: &str) -> &str {
+        self.column(column)
+            .and_then(|index| self.rows.get(row)?.get(index))
+            .map_or("", String::as_str)
+    }
+    /// Sets `column` in row `row`, adding the column if it is missing.
+    pub fn set(&mut self, row: usize, column: &str, value: String) {
+        self.add_column(column, "");
+        let (index, width) = (self.column(column).unwrap_or(0), self.columns.len());
+        if let Some(row) = self.rows.get_mut(row) {
+            row.resize(width, String::new());
+            row[index] = value;
+        }
+    }
+    /// The rows with just `columns` in that order, empty where a column is missing.
+    pub fn select(&self, columns: &[&str]) -> Vec<Vec<String>> {
+        let indices: Vec<Option<usize>> = columns.iter().map(|name| self.column(name)).collect();
+        self.rows
+            .iter()
+            .map(|row| {
+                indices
+                    .iter()
+                    .map(|index| {
+                        index
+                            .and_then(|index| row.get(index))
+                            .cloned()
+                            .unwrap_or_default()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
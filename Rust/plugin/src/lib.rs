@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    env,
+    ffi::OsStr,
+    io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+use storage::Backend;
+
+/// Bumped when plugins would misread the handshake of a newer tool. Version 1
+/// wrote the handshake to stdin.
+pub const PROTOCOL: u32 = 2;
+
+/// What a plugin is told about the tool that started it. It is set as JSON
+/// in `<TOOL>_HANDSHAKE`, and the main fields also as `<TOOL>_DATA_FILE`,
+/// `<TOOL>_STORAGE` and `<TOOL>_VERBOSE`. The plugin shares the tool's
+/// terminal, so it may prompt the user.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Handshake {
+    pub protocol: u32,
+    pub tool: String,
+    pub version: String,
+    pub command: String,
+    pub args: Vec<String>,
+    pub data_file: PathBuf,
+    pub storage: Backend,
+    pub verbose: bool,
+}
+
+/// An executable named `<tool>-<command>` on the PATH, run for subcommands
+/// the tool does not know itself.
+#[derive(Debug)]
+pub struct Plugin {
+    command: String,
+    path: PathBuf,
+}
+impl Plugin {
+    pub fn find(tool: &str, command: &str) -> Option<Self> {
+        Self::find_in(tool, command, &env::var_os("PATH")?)
+    }
+    /// Looks through `paths`, a list like PATH.
+    pub fn find_in(tool: &str, command: &str, paths: &OsStr) -> Option<Self> {
+        let file_name = format!("{}-{}{}", tool, command, env::consts::EXE_SUFFIX);
+        let path = env::split_paths(paths)
+            .map(|dir| dir.join(&file_name))
+            .find(|candidate| is_executable(candidate))?;
+        Some(Self {
+            command: command.to_string(),
+            path,
+        })
+    }
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    /// Runs the plugin with `args` and waits for it, returning its exit code.
+    pub fn run(
+        &self,
+        tool: &str,
+        version: &str,
+        context: Context,
+        args: &[String],
+    ) -> io::Result<i32> {
+        let handshake = Handshake {
+            protocol: PROTOCOL,
+            tool: tool.to_string(),
+            version: version.to_string(),
+            command: self.command.clone(),
+            args: args.to_vec(),
+            data_file: context.data_file.to_path_buf(),
+            storage: context.storage,
+            verbose: context.verbose,
+        };
+        let prefix = tool.to_uppercase();
+        tracing::debug!("running plugin {}", self.path.display());
+        let status = Command::new(&self.path)
+            .args(args)
+            .env(
+                format!("{}_HANDSHAKE", prefix),
+                serde_json::to_string(&handshake)?,
+            )
+            .env(format!("{}_DATA_FILE", prefix), context.data_file)
+            .env(format!("{}_STORAGE", prefix), context.storage.to_string())
+            .env(
+                format!("{}_VERBOSE", prefix),
+                if context.verbose { "1" } else { "0" },
+            )
+            .status()?;
+        Ok(status.code().unwrap_or(1))
+    }
+}
+
+/// Whether `path` is a file this user may run; files without an execute bit
+/// are skipped, like the shell skips them.
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// The parts of a tool's configuration plugins get to see.
+#[derive(Debug, Clone, Copy)]
+pub struct Context<'a> {
+    pub data_file: &'a Path,
+    pub storage: Backend,
+    pub verbose: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[cfg(unix)]
+    #[test]
+    fn skips_files_that_are_not_executable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        fs::write(first.path().join("tool-greet"), "notes").unwrap();
+        let script = second.path().join("tool-greet");
+        fs::write(&script, "#!/bin/sh\n").unwrap();
+
+        let paths = env::join_paths([first.path(), second.path()]).unwrap();
+        assert!(Plugin::find_in("tool", "greet", &paths).is_none());
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        let plugin = Plugin::find_in("tool", "greet", &paths).unwrap();
+        assert_eq!(plugin.path(), script);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn passes_context_in_env() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("tool-greet");
+        fs::write(
+            &script,
+            "#!/bin/sh\nprintf '%s' \"$TOOL_HANDSHAKE\" > \"$(dirname \"$0\")/handshake.json\"\n\
+             echo \"$TOOL_DATA_FILE $TOOL_STORAGE $TOOL_VERBOSE $*\" > \"$(dirname \"$0\")/env.txt\"\n\
+             exit 3\n",
+        )
+        .unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+
+        assert!(Plugin::find_in("tool", "missing", dir.path().as_os_str()).is_none());
+        let plugin = Plugin::find_in("tool", "greet", dir.path().as_os_str()).unwrap();
+        let context = Context {
+            data_file: Path::new("data.csv"),
+            storage: Backend::Csv,
+            verbose: true,
+        };
+        let args = ["--loud".to_string(), "Ann".to_string()];
+        assert_eq!(plugin.run("tool", "1.2.3", context, &args).unwrap(), 3);
+
+        assert_eq!(
+            fs::read_to_string(dir.path().join("env.txt")).unwrap(),
+            "data.csv csv 1 --loud Ann\n"
+        );
+        let handshake: Handshake =
+            serde_json::from_str(&fs::read_to_string(dir.path().join("handshake.json")).unwrap())
+                .unwrap();
+        assert_eq!(
+            handshake,
+            Handshake {
+                protocol: PROTOCOL,
+                tool: "tool".to_string(),
+                version: "1.2.3".to_string(),
+                command: "greet".to_string(),
+                args: args.to_vec(),
+                data_file: PathBuf::from("data.csv"),
+                storage: Backend::Csv,
+                verbose: true,
+            }
+        );
+    }
+}
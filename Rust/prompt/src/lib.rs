@@ -0,0 +1,187 @@
+use std::{fmt, str::FromStr};
+
+mod script;
+mod terminal;
+
+pub use script::Script;
+pub use terminal::Terminal;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Back;
+
+pub enum Line {
+    Text(String),
+    Empty,
+    Back,
+}
+impl From<&str> for Line {
+    fn from(line: &str) -> Line {
+        match line.trim() {
+            "" => Line::Empty,
+            input if input.eq_ignore_ascii_case("b") => Line::Back,
+            input => Line::Text(input.to_owned()),
+        }
+    }
+}
+
+pub trait UserInterface {
+    fn read_line(&mut self, completions: &[&str]) -> Line;
+    fn write_line(&mut self, line: &str);
+
+    fn read_key(&mut self) -> Line {
+        self.read_line(&[])
+    }
+
+    fn write_fmt(&mut self, args: fmt::Arguments) {
+        let text = args.to_string();
+        self.write_line(text.strip_suffix('\n').unwrap_or(&text));
+    }
+    fn prompt(&mut self, text: &str, help: &str) -> Result<Option<String>, Back> {
+        self.prompt_completing(text, help, &[])
+    }
+    fn prompt_completing(
+        &mut self,
+        text: &str,
+        help: &str,
+        completions: &[&str],
+    ) -> Result<Option<String>, Back> {
+        self.write_line(text);
+        loop {
+            match self.read_line(completions) {
+                Line::Text(input) if input == "?" => {
+                    self.write_line(help);
+                    self.write_line(&i18n::t!("prompt-back-help"));
+                    self.write_line(text);
+                }
+                Line::Text(input) => return Ok(Some(input)),
+                Line::Empty => return Ok(None),
+                Line::Back => return Err(Back),
+            }
+        }
+    }
+    fn prompt_key(&mut self, text: &str, help: &str) -> Result<Option<String>, Back> {
+        self.write_line(text);
+        loop {
+            match self.read_key() {
+                Line::Text(input) if input == "?" => {
+                    self.write_line(help);
+                    self.write_line(&i18n::t!("prompt-back-help"));
+                    self.write_line(text);
+                }
+                Line::Text(input) => return Ok(Some(input)),
+                Line::Empty => return Ok(None),
+                Line::Back => return Err(Back),
+            }
+        }
+    }
+}
+
+/// Asks until `parse` accepts the input, printing its error each time. Empty input gives `None`.
+pub fn parsed<T>(
+    ui: &mut dyn UserInterface,
+    text: &str,
+    help: &str,
+    mut parse: impl FnMut(&str) -> Result<T, String>,
+) -> Result<Option<T>, Back> {
+    loop {
+        let input = match ui.prompt(text, help)? {
+            Some(input) => input,
+            None => return Ok(None),
+        };
+        match parse(&input) {
+            Ok(value) => return Ok(Some(value)),
+            Err(e) => writeln!(ui, "{}", e),
+        }
+    }
+}
+
+pub fn number<T: FromStr>(
+    ui: &mut dyn UserInterface,
+    text: &str,
+    help: &str,
+) -> Result<Option<T>, Back> {
+    parsed(ui, text, help, |input| {
+        input.parse().map_err(|_| i18n::t!("prompt-enter-number"))
+    })
+}
+
+/// Lists the options and takes either a number from the list or an option's name.
+pub fn choice(
+    ui: &mut dyn UserInterface,
+    text: &str,
+    help: &str,
+    options: &[&str],
+) -> Result<Option<usize>, Back> {
+    for (num, option) in options.iter().enumerate() {
+        writeln!(ui, "{}. {}", num + 1, option);
+    }
+    loop {
+        let input = match ui.prompt_completing(text, help, options)? {
+            Some(input) => input,
+            None => return Ok(None),
+        };
+        let index = match input.parse::<usize>() {
+            Ok(num) => num.checked_sub(1).filter(|index| *index < options.len()),
+            Err(_) => options
+                .iter()
+                .position(|option| option.eq_ignore_ascii_case(&input)),
+        };
+        match index {
+            Some(index) => return Ok(Some(index)),
+            None => writeln!(
+                ui,
+                "{}",
+                i18n::t!("prompt-pick-number", count = options.len())
+            ),
+        }
+    }
+}
+
+pub fn confirm(ui: &mut dyn UserInterface, text: &str) -> Result<bool, Back> {
+    let answer = ui.prompt_key(&format!("{} (y/N)", text), &i18n::t!("prompt-confirm-help"))?;
+    Ok(matches!(
+        answer.map(|input| input.to_lowercase()).as_deref(),
+        Some("y" | "yes")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prompts_until_the_input_parses() {
+        let mut ui = Script::new(&["?", "ten", "10"]);
+        assert_eq!(number::<u32>(&mut ui, "Count:", "How many."), Ok(Some(10)));
+        assert_eq!(
+            ui.output,
+            [
+                "Count:",
+                "How many.",
+                "(b or Esc goes back, ? shows this help)",
+                "Count:",
+                "Please enter a number",
+                "Count:"
+            ]
+        );
+    }
+
+    #[test]
+    fn choices_take_numbers_or_names() {
+        let options = ["weekly", "monthly"];
+        let mut ui = Script::new(&["3", "Monthly", "1", "", "b"]);
+        assert_eq!(choice(&mut ui, "Repeats:", "", &options), Ok(Some(1)));
+        assert!(ui.printed("Please pick a number from 1 to 2"));
+        assert_eq!(choice(&mut ui, "Repeats:", "", &options), Ok(Some(0)));
+        assert_eq!(choice(&mut ui, "Repeats:", "", &options), Ok(None));
+        assert_eq!(choice(&mut ui, "Repeats:", "", &options), Err(Back));
+    }
+
+    #[test]
+    fn confirm_needs_a_yes() {
+        let mut ui = Script::new(&["Y", "n", ""]);
+        assert_eq!(confirm(&mut ui, "Delete?"), Ok(true));
+        assert_eq!(confirm(&mut ui, "Delete?"), Ok(false));
+        assert_eq!(confirm(&mut ui, "Delete?"), Ok(false));
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{Line, UserInterface};
+use std::collections::VecDeque;
+
+/// Reads prepared lines and records everything written, for tests.
+pub struct Script {
+    input: VecDeque<String>,
+    pub output: Vec<String>,
+}
+impl Script {
+    pub fn new(input: &[&str]) -> Script {
+        Script {
+            input: input.iter().map(|line| line.to_string()).collect(),
+            output: vec![],
+        }
+    }
+    pub fn printed(&self, text: &str) -> bool {
+        self.output.iter().any(|line| line.contains(text))
+    }
+}
+impl UserInterface for Script {
+    fn read_line(&mut self, _: &[&str]) -> Line {
+        match self.input.pop_front() {
+            Some(line) => Line::from(line.as_str()),
+            None => Line::Empty,
+        }
+    }
+    fn write_line(&mut self, line: &str) {
+        self.output.push(line.to_string());
+    }
+}
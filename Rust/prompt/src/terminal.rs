@@ -0,0 +1,135 @@
+use crate::Line;
+use crossterm::{
+    event::{self, Event, KeyCode as TermKey, KeyEventKind, KeyModifiers},
+    terminal,
+};
+use rustyline::{
+    completion::Completer, error::ReadlineError, highlight::Highlighter, hint::Hinter,
+    history::DefaultHistory, validate::Validator, Cmd, Config, Context, Editor, Helper, KeyCode,
+    KeyEvent, Modifiers,
+};
+use std::io::{self, IsTerminal};
+
+#[derive(Default)]
+struct NameHelper {
+    names: Vec<String>,
+}
+impl Completer for NameHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let prefix = line[..pos].to_lowercase();
+        let candidates = self
+            .names
+            .iter()
+            .filter(|name| name.to_lowercase().starts_with(&prefix))
+            .cloned()
+            .collect();
+        Ok((0, candidates))
+    }
+}
+impl Hinter for NameHelper {
+    type Hint = String;
+}
+impl Highlighter for NameHelper {}
+impl Validator for NameHelper {}
+impl Helper for NameHelper {}
+
+type LineEditor = Editor<NameHelper, DefaultHistory>;
+
+const ESC_TIMEOUT_MS: u16 = 50;
+
+fn new_editor() -> Option<LineEditor> {
+    let config = Config::builder()
+        .keyseq_timeout(Some(ESC_TIMEOUT_MS))
+        .build();
+    let mut editor = LineEditor::with_config(config).ok()?;
+    editor.set_helper(Some(NameHelper::default()));
+    editor.bind_sequence(KeyEvent(KeyCode::Esc, Modifiers::NONE), Cmd::Interrupt);
+    Some(editor)
+}
+
+fn read_single_key() -> io::Result<Line> {
+    terminal::enable_raw_mode()?;
+    let key = loop {
+        match event::read() {
+            Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => break Ok(key),
+            Ok(_) => {}
+            Err(e) => break Err(e),
+        }
+    };
+    terminal::disable_raw_mode()?;
+
+    let key = key?;
+    let (line, echo) = match key.code {
+        TermKey::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            (Line::Back, String::new())
+        }
+        TermKey::Char(c) => (Line::from(c.to_string().as_str()), c.to_string()),
+        TermKey::Esc => (Line::Back, String::new()),
+        _ => (Line::Empty, String::new()),
+    };
+    println!("{}", echo);
+    Ok(line)
+}
+
+fn read_raw_line() -> io::Result<String> {
+    let mut buffer = String::new();
+    io::stdin().read_line(&mut buffer)?;
+    Ok(buffer)
+}
+
+pub struct Terminal {
+    editor: Option<LineEditor>,
+}
+impl Terminal {
+    pub fn new() -> Terminal {
+        Terminal {
+            editor: new_editor(),
+        }
+    }
+}
+impl Default for Terminal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl crate::UserInterface for Terminal {
+    fn read_line(&mut self, completions: &[&str]) -> Line {
+        let editor = match self.editor.as_mut() {
+            Some(editor) => editor,
+            None => return Line::from(read_raw_line().unwrap_or_default().as_str()),
+        };
+        if let Some(helper) = editor.helper_mut() {
+            helper.names = completions.iter().map(|name| name.to_string()).collect();
+        }
+        loop {
+            match editor.readline("") {
+                Ok(line) => {
+                    let _ = editor.add_history_entry(line.trim());
+                    return Line::from(line.as_str());
+                }
+                Err(ReadlineError::Eof) => return Line::Empty,
+                Err(ReadlineError::Interrupted) => return Line::Back,
+                Err(_) => println!("{}", i18n::t!("prompt-enter-again")),
+            }
+        }
+    }
+    fn write_line(&mut self, line: &str) {
+        println!("{}", line);
+    }
+    fn read_key(&mut self) -> Line {
+        if self.editor.is_none() || !io::stdin().is_terminal() {
+            return self.read_line(&[]);
+        }
+        match read_single_key() {
+            Ok(line) => line,
+            Err(_) => self.read_line(&[]),
+        }
+    }
+}
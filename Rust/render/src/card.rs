@@ -0,0 +1,26 @@
+use crate::{pad, Align, Style};
+
+/// A title over labelled values, with the values lined up:
+///
+/// ```text
+/// Contact #1
+///   Name:     Ann Lee
+///   Birthday: 1990-02-03
+/// ```
+pub fn card<L: AsRef<str>, V: AsRef<str>>(title: &str, fields: &[(L, V)]) -> Vec<String> {
+    let width = fields
+        .iter()
+        .map(|(label, _)| crate::width(label.as_ref()))
+        .max()
+        .unwrap_or(0);
+    let mut lines = vec![Style::Strong.paint(title)];
+    for (label, value) in fields {
+        let line = format!(
+            "  {} {}",
+            pad(label.as_ref(), width, Align::Left),
+            value.as_ref()
+        );
+        lines.push(line.trim_end().to_string());
+    }
+    lines
+}
@@ -0,0 +1,54 @@
+use crate::{pad, truncate, width, Align};
+use std::env;
+
+const DEFAULT_WIDTH: usize = 80;
+
+pub fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .filter(|columns| *columns > 0)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// One bar per `(label, value, shown value)` row, scaled so the largest fills
+/// the `columns` left over by labels, which are cut to half the width at most.
+pub fn bar_chart(rows: &[(String, f64, String)], columns: usize) -> Vec<String> {
+    let label_width = rows
+        .iter()
+        .map(|row| width(&row.0))
+        .max()
+        .unwrap_or(0)
+        .min(columns / 2);
+    let value_width = rows.iter().map(|row| width(&row.2)).max().unwrap_or(0);
+    let max = rows.iter().map(|row| row.1).fold(0.0, f64::max);
+    let bar_width = columns.saturating_sub(label_width + value_width + 4).max(1);
+
+    rows.iter()
+        .map(|(label, value, shown)| {
+            let length = if max > 0.0 {
+                (value / max * bar_width as f64).round() as usize
+            } else {
+                0
+            };
+            format!(
+                "{} | {:<bar_width$} {}",
+                pad(&truncate(label, label_width), label_width, Align::Left),
+                "#".repeat(length),
+                pad(shown, value_width, Align::Right)
+            )
+        })
+        .collect()
+}
+
+const SHARE_WIDTH: usize = 10;
+
+pub fn share_bar(share: f64) -> String {
+    let filled = (share.clamp(0.0, 1.0) * SHARE_WIDTH as f64).round() as usize;
+    format!(
+        "{:.1}% {}{}",
+        share * 100.0,
+        "#".repeat(filled),
+        ".".repeat(SHARE_WIDTH - filled)
+    )
+}
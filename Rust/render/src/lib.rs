@@ -0,0 +1,11 @@
+mod card;
+mod chart;
+mod style;
+mod table;
+mod width;
+
+pub use card::card;
+pub use chart::{bar_chart, share_bar, terminal_width};
+pub use style::{set_theme, Style, Theme};
+pub use table::Table;
+pub use width::{pad, truncate, width, Align};
@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    env, fmt,
+    io::{self, IsTerminal},
+    str::FromStr,
+    sync::{OnceLock, RwLock},
+};
+
+const RESET: &str = "\x1b[0m";
+
+static THEME: RwLock<Theme> = RwLock::new(Theme::Default);
+
+/// What a piece of text means; the theme decides how that looks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// Overdue bills and failures.
+    Error,
+    /// Paid bills and completed actions.
+    Success,
+    Warning,
+    /// Headings, keys and anything that should stand out.
+    Strong,
+}
+impl Style {
+    pub fn start(self) -> &'static str {
+        if enabled() {
+            theme().code(self)
+        } else {
+            ""
+        }
+    }
+    pub fn end(self) -> &'static str {
+        if enabled() && !theme().code(self).is_empty() {
+            RESET
+        } else {
+            ""
+        }
+    }
+    pub fn paint(self, text: &str) -> String {
+        format!("{}{}{}", self.start(), text, self.end())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    #[default]
+    Default,
+    /// Darker colors that stay readable on light backgrounds.
+    Light,
+    /// Bold and underline only, for terminals or eyes that struggle with colors.
+    Mono,
+}
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::Default, Theme::Light, Theme::Mono];
+
+    fn code(self, style: Style) -> &'static str {
+        match (self, style) {
+            (Theme::Default, Style::Error) => "\x1b[31m",
+            (Theme::Default, Style::Success) => "\x1b[32m",
+            (Theme::Default, Style::Warning) => "\x1b[33m",
+            (Theme::Light, Style::Error) => "\x1b[1;31m",
+            (Theme::Light, Style::Success) => "\x1b[34m",
+            (Theme::Light, Style::Warning) => "\x1b[35m",
+            (Theme::Mono, Style::Error) => "\x1b[1;4m",
+            (Theme::Mono, Style::Success) => "",
+            (Theme::Mono, Style::Warning) => "\x1b[4m",
+            (_, Style::Strong) => "\x1b[1m",
+        }
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Theme::Default => write!(f, "default"),
+            Theme::Light => write!(f, "light"),
+            Theme::Mono => write!(f, "mono"),
+        }
+    }
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(theme: &str) -> Result<Self, Self::Err> {
+        Theme::ALL
+            .into_iter()
+            .find(|candidate| candidate.to_string() == theme.trim().to_lowercase())
+            .ok_or_else(|| format!("invalid theme {}", theme))
+    }
+}
+
+pub fn set_theme(theme: Theme) {
+    if let Ok(mut current) = THEME.write() {
+        *current = theme;
+    }
+}
+
+fn theme() -> Theme {
+    THEME.read().map_or(Theme::Default, |theme| *theme)
+}
+
+fn enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| {
+        let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        !no_color && io::stdout().is_terminal()
+    })
+}
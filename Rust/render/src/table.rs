@@ -0,0 +1,100 @@
+use crate::{pad, truncate, width, Align, Style};
+
+const GAP: &str = "  ";
+
+/// Rows under a header, with columns as wide as their widest cell. When that
+/// is too wide for the screen, the widest columns are cut down first.
+#[derive(Debug, Default)]
+pub struct Table {
+    header: Vec<String>,
+    align: Vec<Align>,
+    rows: Vec<Vec<String>>,
+}
+impl Table {
+    pub fn new<S: AsRef<str>>(header: &[S]) -> Self {
+        Self {
+            header: header
+                .iter()
+                .map(|cell| cell.as_ref().to_string())
+                .collect(),
+            align: vec![Align::Left; header.len()],
+            rows: vec![],
+        }
+    }
+    pub fn align(mut self, column: usize, align: Align) -> Self {
+        if let Some(current) = self.align.get_mut(column) {
+            *current = align;
+        }
+        self
+    }
+    pub fn row<S: AsRef<str>>(&mut self, cells: &[S]) {
+        self.rows
+            .push(cells.iter().map(|cell| cell.as_ref().to_string()).collect());
+    }
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+    fn widths(&self, max: usize) -> Vec<usize> {
+        let mut widths: Vec<usize> = self.header.iter().map(|cell| width(cell)).collect();
+        for row in &self.rows {
+            for (column, cell) in row.iter().enumerate().take(widths.len()) {
+                widths[column] = widths[column].max(width(cell));
+            }
+        }
+        let gaps = GAP.len() * widths.len().saturating_sub(1);
+        while widths.iter().sum::<usize>() + gaps > max {
+            match widths.iter_mut().max() {
+                Some(widest) if *widest > 1 => *widest -= 1,
+                _ => break,
+            }
+        }
+        widths
+    }
+    /// The lines of the table, at most `max` columns wide where possible.
+    pub fn render(&self, max: usize) -> Vec<String> {
+        let widths = self.widths(max);
+        let line = |cells: &[String]| {
+            let cells: Vec<String> = widths
+                .iter()
+                .zip(&self.align)
+                .enumerate()
+                .map(|(column, (&width, &align))| {
+                    let cell = cells.get(column).map_or("", String::as_str);
+                    pad(&truncate(cell, width), width, align)
+                })
+                .collect();
+            cells.join(GAP).trim_end().to_string()
+        };
+        let mut lines = vec![Style::Strong.paint(&line(&self.header))];
+        lines.extend(self.rows.iter().map(|row| line(row)));
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fits_the_widest_column_to_the_screen() {
+        let mut table = Table::new(&["ID", "Name", "Amount"]).align(2, Align::Right);
+        table.row(&["1", "Rent, flat in town", "500.00"]);
+        table.row(&["12", "Gym", "30.00"]);
+        assert_eq!(
+            table.render(80),
+            [
+                "ID  Name                Amount",
+                "1   Rent, flat in town  500.00",
+                "12  Gym                  30.00"
+            ]
+        );
+        assert_eq!(
+            table.render(24),
+            [
+                "ID  Name          Amount",
+                "1   Rent, flat …  500.00",
+                "12  Gym            30.00"
+            ]
+        );
+    }
+}
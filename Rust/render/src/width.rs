@@ -0,0 +1,72 @@
+use unicode_width::UnicodeWidthChar;
+
+const ELLIPSIS: char = '…';
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+}
+
+/// Columns `text` takes up in a terminal, so wide characters count twice and
+/// color codes not at all.
+pub fn width(text: &str) -> usize {
+    let mut width = 0;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+            continue;
+        }
+        width += c.width().unwrap_or(0);
+    }
+    width
+}
+
+/// Shortens `text` to at most `max` columns, ending it in `…` when cut.
+pub fn truncate(text: &str, max: usize) -> String {
+    if width(text) <= max {
+        return text.to_string();
+    }
+    let mut cut = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let c_width = c.width().unwrap_or(0);
+        if used + c_width + 1 > max {
+            break;
+        }
+        cut.push(c);
+        used += c_width;
+    }
+    if max > 0 {
+        cut.push(ELLIPSIS);
+    }
+    cut
+}
+
+/// Fills `text` with spaces up to `columns`, which `format!` cannot do for
+/// wide characters or colored text.
+pub fn pad(text: &str, columns: usize, align: Align) -> String {
+    let fill = " ".repeat(columns.saturating_sub(width(text)));
+    match align {
+        Align::Left => format!("{}{}", text, fill),
+        Align::Right => format!("{}{}", fill, text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_terminal_columns() {
+        assert_eq!(width("Zoë"), 3);
+        assert_eq!(width("東京"), 4);
+        assert_eq!(width("\x1b[31mlate\x1b[0m"), 4);
+        assert_eq!(truncate("Rent, flat", 6), "Rent,…");
+        assert_eq!(truncate("東京都", 4), "東…");
+        assert_eq!(truncate("Gym", 3), "Gym");
+        assert_eq!(pad("東京", 6, Align::Right), "  東京");
+    }
+}
@@ -0,0 +1,392 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    env, fs, io,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+use toml::{Table, Value};
+
+mod paths;
+
+pub use paths::AppDirs;
+
+const CONFIG_FILE: &str = "config.toml";
+
+/// A tool's typed settings section. Keys missing from every layer keep their
+/// `Default` value.
+pub trait Settings: Serialize + DeserializeOwned + Default {
+    /// Names the config directory and, upper-cased, prefixes the environment variables.
+    const APP: &'static str;
+}
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("could not read config {}", path.display())]
+    Read { path: PathBuf, source: io::Error },
+    #[error("invalid config {}: {message}", path.display())]
+    Parse { path: PathBuf, message: String },
+    #[error("invalid {origin}: {message}")]
+    Invalid { origin: String, message: String },
+}
+impl From<Error> for io::Error {
+    fn from(error: Error) -> Self {
+        let kind = match &error {
+            Error::Read { source, .. } => source.kind(),
+            _ => io::ErrorKind::InvalidData,
+        };
+        io::Error::new(kind, error)
+    }
+}
+
+/// `config.toml` in the app's config directory.
+pub fn default_path(app: &str) -> Option<PathBuf> {
+    Some(AppDirs::new(app)?.config_dir().join(CONFIG_FILE))
+}
+
+/// Looks up the app's directories and creates the missing ones, which only
+/// happens on the first run. A home that cannot be written to gets a
+/// warning rather than an error, since everything still works without them.
+pub fn first_run(app: &str) -> Option<AppDirs> {
+    let dirs = AppDirs::new(app)?;
+    if let Err(e) = dirs.init() {
+        tracing::warn!("could not create the {} directories: {}", app, e);
+    }
+    Some(dirs)
+}
+
+/// Resolves `T` from its defaults, the config file, `<APP>_*` environment
+/// variables and command line flags, each layer overriding the ones before.
+///
+/// Environment variables use `__` between nested sections, so
+/// `BILL_MANAGER_VIEW__SORT=due` sets `sort` in `[view]`. Values are read as
+/// TOML where that fits the setting and as plain text otherwise; lists also
+/// take comma-separated text.
+#[derive(Debug, Clone)]
+pub struct Layers<T> {
+    file: Option<PathBuf>,
+    env: Vec<(String, String)>,
+    flags: Vec<(String, Result<Value, String>)>,
+    settings: PhantomData<T>,
+}
+impl<T: Settings> Default for Layers<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: Settings> Layers<T> {
+    pub fn new() -> Self {
+        Self {
+            file: default_path(T::APP),
+            env: env::vars().collect(),
+            flags: vec![],
+            settings: PhantomData,
+        }
+    }
+
+    /// Reads `file` instead of the default config file, if given.
+    pub fn file(self, file: Option<PathBuf>) -> Self {
+        Self {
+            file: file.or(self.file),
+            ..self
+        }
+    }
+
+    /// Replaces the process environment, mostly for tests.
+    pub fn env(self, vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        Self {
+            env: vars.into_iter().collect(),
+            ..self
+        }
+    }
+
+    /// Sets `key`, with `.` between nested sections, unless `value` is `None`.
+    pub fn flag<V: Serialize>(mut self, key: &str, value: Option<V>) -> Self {
+        if let Some(value) = value {
+            let value = Value::try_from(value).map_err(|e| e.to_string());
+            self.flags.push((key.to_string(), value));
+        }
+        self
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    /// The defaults overlaid with the config file only, which is what a
+    /// settings editor should show and save.
+    pub fn stored(&self) -> Result<T, Error> {
+        let defaults = to_table(&T::default(), "defaults")?;
+        let Some(path) = &self.file else {
+            return deserialize(defaults, "defaults");
+        };
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return deserialize(defaults, "defaults")
+            }
+            Err(source) => {
+                return Err(Error::Read {
+                    path: path.clone(),
+                    source,
+                })
+            }
+        };
+        let parse_error = |message: String| Error::Parse {
+            path: path.clone(),
+            message,
+        };
+        let file: Table = toml::from_str(&text).map_err(|e| parse_error(e.to_string()))?;
+        let mut table = defaults;
+        merge(&mut table, file);
+        Value::Table(table)
+            .try_into()
+            .map_err(|e: toml::de::Error| parse_error(e.to_string()))
+    }
+
+    /// Overlays the environment and flags on `stored`.
+    pub fn resolve(&self, stored: &T) -> Result<T, Error> {
+        let mut table = to_table(stored, "settings")?;
+        let prefix = format!("{}_", T::APP.to_uppercase());
+        for (name, raw) in &self.env {
+            let Some(key) = name.strip_prefix(&prefix).filter(|key| !key.is_empty()) else {
+                continue;
+            };
+            let path: Vec<String> = key.to_lowercase().split("__").map(String::from).collect();
+            let candidates = candidates(raw, lookup(&table, &path));
+            table = overlay::<T>(&table, &path, candidates, name)?;
+        }
+        for (key, value) in &self.flags {
+            let path: Vec<String> = key.split('.').map(String::from).collect();
+            let origin = format!("setting {}", key);
+            let value = value.clone().map_err(|message| Error::Invalid {
+                origin: origin.clone(),
+                message,
+            })?;
+            table = overlay::<T>(&table, &path, vec![value], &origin)?;
+        }
+        deserialize(table, "settings")
+    }
+
+    pub fn load(&self) -> Result<T, Error> {
+        self.resolve(&self.stored()?)
+    }
+
+    pub fn save(&self, settings: &T) -> io::Result<()> {
+        let path = self
+            .file
+            .as_deref()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text =
+            toml::to_string(settings).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, text)
+    }
+}
+
+fn to_table<T: Serialize>(settings: &T, origin: &str) -> Result<Table, Error> {
+    match Value::try_from(settings) {
+        Ok(Value::Table(table)) => Ok(table),
+        Ok(_) => Err(Error::Invalid {
+            origin: origin.to_string(),
+            message: "settings must be a table".to_string(),
+        }),
+        Err(e) => Err(Error::Invalid {
+            origin: origin.to_string(),
+            message: e.to_string(),
+        }),
+    }
+}
+
+fn deserialize<T: DeserializeOwned>(table: Table, origin: &str) -> Result<T, Error> {
+    Value::Table(table)
+        .try_into()
+        .map_err(|e: toml::de::Error| Error::Invalid {
+            origin: origin.to_string(),
+            message: e.to_string(),
+        })
+}
+
+fn merge(base: &mut Table, layer: Table) {
+    for (key, value) in layer {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(layer)) => merge(base, layer),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn lookup<'a>(table: &'a Table, path: &[String]) -> Option<&'a Value> {
+    let (first, rest) = path.split_first()?;
+    let value = table.get(first)?;
+    if rest.is_empty() {
+        Some(value)
+    } else {
+        lookup(value.as_table()?, rest)
+    }
+}
+
+/// The readings of an environment value to try, best first.
+fn candidates(raw: &str, current: Option<&Value>) -> Vec<Value> {
+    let mut candidates = vec![];
+    if let Ok(mut parsed) = toml::from_str::<Table>(&format!("value = {}", raw)) {
+        candidates.extend(parsed.remove("value"));
+    }
+    if let Some(Value::Array(_)) = current {
+        let items = raw
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| Value::String(item.to_string()));
+        candidates.push(Value::Array(items.collect()));
+    }
+    candidates.push(Value::String(raw.to_string()));
+    candidates
+}
+
+/// Sets `path` to the first candidate that still deserializes into `T`.
+fn overlay<T: DeserializeOwned>(
+    table: &Table,
+    path: &[String],
+    candidates: Vec<Value>,
+    origin: &str,
+) -> Result<Table, Error> {
+    let mut error = None;
+    for value in candidates {
+        let mut layered = table.clone();
+        merge(&mut layered, nested(path, value));
+        match deserialize::<T>(layered.clone(), origin) {
+            Ok(_) => return Ok(layered),
+            Err(e) => error = Some(e),
+        }
+    }
+    Err(error.unwrap_or_else(|| Error::Invalid {
+        origin: origin.to_string(),
+        message: "no value".to_string(),
+    }))
+}
+
+fn nested(path: &[String], value: Value) -> Table {
+    let mut value = value;
+    for key in path.iter().rev() {
+        let mut table = Table::new();
+        table.insert(key.clone(), value);
+        value = Value::Table(table);
+    }
+    match value {
+        Value::Table(table) => table,
+        _ => Table::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    struct Tool {
+        name: String,
+        days: u32,
+        tags: Vec<String>,
+        view: View,
+    }
+    impl Default for Tool {
+        fn default() -> Self {
+            Self {
+                name: "tool".to_string(),
+                days: 7,
+                tags: vec![],
+                view: View::default(),
+            }
+        }
+    }
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    struct View {
+        descending: bool,
+        column: Option<String>,
+    }
+    impl Settings for Tool {
+        const APP: &'static str = "tool";
+    }
+
+    fn vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn later_layers_win() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("config.toml");
+        fs::write(
+            &file,
+            "name = \"file\"\ndays = 3\n[view]\ndescending = true\n",
+        )
+        .unwrap();
+        let layers = Layers::<Tool>::new()
+            .file(Some(file))
+            .env(vars(&[
+                ("TOOL_DAYS", "5"),
+                ("TOOL_TAGS", "rent, gym"),
+                ("TOOL_VIEW__COLUMN", "12"),
+                ("OTHER_DAYS", "9"),
+            ]))
+            .flag("name", Some("flag"))
+            .flag("days", None::<u32>);
+
+        let stored = layers.stored().unwrap();
+        assert_eq!((stored.name.as_str(), stored.days), ("file", 3));
+        assert_eq!(
+            layers.load().unwrap(),
+            Tool {
+                name: "flag".to_string(),
+                days: 5,
+                tags: vec!["rent".to_string(), "gym".to_string()],
+                view: View {
+                    descending: true,
+                    column: Some("12".to_string()),
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn errors_name_their_layer() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = Layers::<Tool>::new()
+            .file(Some(dir.path().join("missing.toml")))
+            .env(vars(&[("TOOL_DAYS", "soon")]));
+        assert_eq!(missing.stored().unwrap(), Tool::default());
+        assert!(matches!(
+            missing.load(),
+            Err(Error::Invalid { origin, .. }) if origin == "TOOL_DAYS"
+        ));
+
+        let file = dir.path().join("config.toml");
+        fs::write(&file, "days = [\n").unwrap();
+        let broken = Layers::<Tool>::new().file(Some(file)).env(vec![]);
+        assert!(matches!(broken.stored(), Err(Error::Parse { .. })));
+    }
+
+    #[test]
+    fn saved_settings_load_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let layers = Layers::<Tool>::new()
+            .file(Some(dir.path().join("tool").join("config.toml")))
+            .env(vec![]);
+        let tool = Tool {
+            days: 1,
+            ..Tool::default()
+        };
+        layers.save(&tool).unwrap();
+        assert_eq!(layers.stored().unwrap(), tool);
+    }
+}
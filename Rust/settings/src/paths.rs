@@ -0,0 +1,81 @@
+use directories::ProjectDirs;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Where an app keeps its files on this platform, for example for `bill_manager`:
+///
+/// | | Linux | macOS | Windows |
+/// |---|---|---|---|
+/// | config | `~/.config/bill_manager` | `~/Library/Application Support/bill_manager` | `%APPDATA%\bill_manager\config` |
+/// | data | `~/.local/share/bill_manager` | `~/Library/Application Support/bill_manager` | `%APPDATA%\bill_manager\data` |
+/// | cache | `~/.cache/bill_manager` | `~/Library/Caches/bill_manager` | `%LOCALAPPDATA%\bill_manager\cache` |
+///
+/// Backups go to `backups` in the data directory. The XDG variables are honoured on Linux.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppDirs {
+    config: PathBuf,
+    data: PathBuf,
+    cache: PathBuf,
+}
+impl AppDirs {
+    /// `None` if the platform has no home directory to put them in.
+    pub fn new(app: &str) -> Option<Self> {
+        let dirs = ProjectDirs::from("", "", app)?;
+        Some(Self {
+            config: dirs.config_dir().to_path_buf(),
+            data: dirs.data_dir().to_path_buf(),
+            cache: dirs.cache_dir().to_path_buf(),
+        })
+    }
+    pub fn config_dir(&self) -> &Path {
+        &self.config
+    }
+    pub fn data_dir(&self) -> &Path {
+        &self.data
+    }
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache
+    }
+    pub fn backup_dir(&self) -> PathBuf {
+        self.data.join("backups")
+    }
+
+    /// Creates whichever directories are missing and returns whether any
+    /// were, which means this is the app's first run.
+    pub fn init(&self) -> io::Result<bool> {
+        let mut created = false;
+        for dir in [
+            self.config.clone(),
+            self.data.clone(),
+            self.cache.clone(),
+            self.backup_dir(),
+        ] {
+            if !dir.try_exists()? {
+                fs::create_dir_all(&dir)?;
+                tracing::debug!("created {}", dir.display());
+                created = true;
+            }
+        }
+        Ok(created)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_run_creates_every_directory() {
+        let root = tempfile::tempdir().unwrap();
+        let dirs = AppDirs {
+            config: root.path().join("config"),
+            data: root.path().join("data"),
+            cache: root.path().join("cache"),
+        };
+        assert!(dirs.init().unwrap());
+        assert!(dirs.backup_dir().is_dir() && dirs.cache_dir().is_dir());
+        assert!(!dirs.init().unwrap());
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{
+    migration::{migrate_stored, Stored},
+    Error, Migrated, Rows, Storage,
+};
+use errors::Diagnostic;
+use flatfile::{Format, Writer};
+use migrate::Table;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use vfs::{Disk, Vfs};
+
+/// The first line of files written at a version above 0, like `#version=2`.
+const STAMP: &str = "#version=";
+
+/// Rows as comma-separated lines under a header line.
+pub struct Csv<T: Rows> {
+    path: PathBuf,
+    context: T::Context,
+    vfs: Arc<dyn Vfs>,
+}
+impl<T: Rows> Csv<T> {
+    pub fn new(path: impl Into<PathBuf>, context: T::Context) -> Self {
+        Self {
+            path: path.into(),
+            context,
+            vfs: Arc::new(Disk),
+        }
+    }
+    pub fn in_vfs(self, vfs: Arc<dyn Vfs>) -> Self {
+        Self { vfs, ..self }
+    }
+}
+impl<T: Rows> Storage<T> for Csv<T>
+where
+    T::Context: Send + Sync,
+{
+    fn path(&self) -> &Path {
+        &self.path
+    }
+    fn load(&self) -> Result<T, Error> {
+        let text = self.vfs.read_to_string(&self.path)?;
+        parse_csv(&text, &self.path, &self.context)
+    }
+    fn save(&self, value: &T) -> Result<(), Error> {
+        let text = write_csv(value);
+        Ok(self.vfs.write_atomic(&self.path, text.as_bytes())?)
+    }
+    fn migrate(&self, to: Option<u32>, dry_run: bool) -> Result<Migrated, Error> {
+        let text = self.vfs.read_to_string(&self.path)?;
+        let stored = read_stored(&text, &self.path);
+        migrate_stored::<T>(&self.path, stored, to, dry_run, |stored| {
+            let text = format_stored(stored);
+            Ok(self.vfs.write_atomic(&self.path, text.as_bytes())?)
+        })
+    }
+    fn vfs(&self) -> Arc<dyn Vfs> {
+        Arc::clone(&self.vfs)
+    }
+}
+
+/// Reads `text`, which came from `path`, warning about every line it skips.
+pub fn parse_csv<T: Rows>(text: &str, path: &Path, context: &T::Context) -> Result<T, Error> {
+    let (stored, mut skipped) = split_stored(text);
+    let (value, rejected) = stored.into_value::<T>(path, context)?;
+    skipped.extend(rejected);
+    warn(text, path, skipped);
+    Ok(value)
+}
+
+pub fn write_csv<T: Rows>(value: &T) -> String {
+    format_stored(&Stored::of(value))
+}
+
+pub(crate) fn read_stored(text: &str, path: &Path) -> Stored {
+    let (stored, skipped) = split_stored(text);
+    warn(text, path, skipped);
+    stored
+}
+
+fn split_stored(text: &str) -> (Stored, Vec<Diagnostic>) {
+    // Parse from the stamp's line break on, so line numbers stay those of the file.
+    let text = text.strip_prefix(flatfile::BOM).unwrap_or(text);
+    let (version, body) = match text.split_once('\n') {
+        Some((first, _)) => match first.trim_end().strip_prefix(STAMP).map(str::parse) {
+            Some(Ok(version)) => (version, &text[first.len()..]),
+            _ => (0, text),
+        },
+        None => (0, text),
+    };
+    let table = flatfile::parse(body, Format::default());
+    let mut rows = vec![];
+    let mut lines = vec![];
+    let mut spans = vec![];
+    let mut skipped = vec![];
+    for record in table.records {
+        match record {
+            Ok(record) => {
+                lines.push(record.line);
+                spans.push(record.spans);
+                rows.push(record.fields);
+            }
+            Err(e) => skipped.push(Diagnostic::from(e)),
+        }
+    }
+    let stored = Stored {
+        version,
+        table: Table::new(table.header, rows),
+        lines,
+        spans,
+    };
+    (stored, skipped)
+}
+
+pub(crate) fn format_stored(stored: &Stored) -> String {
+    let mut text = String::new();
+    if stored.version > 0 {
+        text.push_str(&format!("{}{}\n", STAMP, stored.version));
+    }
+    text.push_str(&Writer::default().table(&stored.table.columns, &stored.table.rows));
+    text
+}
+
+fn warn(text: &str, path: &Path, mut skipped: Vec<Diagnostic>) {
+    skipped.sort_by_key(|diagnostic| diagnostic.line);
+    for diagnostic in skipped {
+        tracing::warn!("{}", diagnostic.render(path.display(), text));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{names, Names};
+
+    #[test]
+    fn skips_broken_lines() {
+        let text = write_csv(&names());
+        assert_eq!(text, "name,number\n\"Rent, flat\",1\n\"\"\"Gym\"\"\",2\n");
+        let broken = format!("{}\"open,3\nPhone,x\n", text);
+        assert_eq!(
+            parse_csv::<Names>(&broken, Path::new("names.csv"), &()).unwrap(),
+            names()
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{
+    csv::{format_stored, read_stored},
+    migration::migrate_stored,
+    parse_csv, write_csv, Error, Migrated, Rows, Storage,
+};
+use encryption::Passphrase;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use vfs::{Disk, Vfs};
+
+/// CSV rows encrypted with a passphrase. Plaintext files still load, so
+/// turning encryption on needs no conversion: the next save encrypts them.
+pub struct Encrypted<T: Rows> {
+    path: PathBuf,
+    context: T::Context,
+    passphrase: Passphrase,
+    vfs: Arc<dyn Vfs>,
+}
+impl<T: Rows> Encrypted<T> {
+    pub fn new(path: impl Into<PathBuf>, context: T::Context, passphrase: Passphrase) -> Self {
+        Self {
+            path: path.into(),
+            context,
+            passphrase,
+            vfs: Arc::new(Disk),
+        }
+    }
+    pub fn in_vfs(self, vfs: Arc<dyn Vfs>) -> Self {
+        Self { vfs, ..self }
+    }
+    fn error(&self, source: encryption::Error) -> Error {
+        Error::Encryption {
+            path: self.path.clone(),
+            source,
+        }
+    }
+    fn read(&self) -> Result<String, Error> {
+        let data = self.vfs.read(&self.path)?;
+        if !encryption::is_encrypted(&data) {
+            return Ok(String::from_utf8_lossy(&data).into_owned());
+        }
+        let plain = encryption::decrypt(&data, &self.passphrase).map_err(|e| self.error(e))?;
+        Ok(String::from_utf8_lossy(&plain).into_owned())
+    }
+    fn write(&self, text: &str) -> Result<(), Error> {
+        let data =
+            encryption::encrypt(text.as_bytes(), &self.passphrase).map_err(|e| self.error(e))?;
+        Ok(self.vfs.write_atomic(&self.path, &data)?)
+    }
+}
+impl<T: Rows> Storage<T> for Encrypted<T>
+where
+    T::Context: Send + Sync,
+{
+    fn path(&self) -> &Path {
+        &self.path
+    }
+    fn load(&self) -> Result<T, Error> {
+        parse_csv(&self.read()?, &self.path, &self.context)
+    }
+    fn save(&self, value: &T) -> Result<(), Error> {
+        self.write(&write_csv(value))
+    }
+    fn migrate(&self, to: Option<u32>, dry_run: bool) -> Result<Migrated, Error> {
+        let stored = read_stored(&self.read()?, &self.path);
+        migrate_stored::<T>(&self.path, stored, to, dry_run, |stored| {
+            self.write(&format_stored(stored))
+        })
+    }
+    fn encrypted(&self) -> bool {
+        true
+    }
+    fn vfs(&self) -> Arc<dyn Vfs> {
+        Arc::clone(&self.vfs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{names, Names};
+    use vfs::Memory;
+
+    #[test]
+    fn encrypts_on_save_and_reads_plaintext() {
+        let memory = Arc::new(Memory::new());
+        let path = Path::new("names.csv");
+        memory.write(path, write_csv(&names()).as_bytes()).unwrap();
+        let open = |passphrase: &str| {
+            Encrypted::<Names>::new(path, (), Passphrase::from(passphrase.to_string()))
+                .in_vfs(memory.clone())
+        };
+        let store = open("secret");
+        assert_eq!(store.load().unwrap(), names());
+
+        store.save(&names()).unwrap();
+        assert!(encryption::is_encrypted(&memory.read(path).unwrap()));
+        assert_eq!(store.load().unwrap(), names());
+        assert!(matches!(
+            open("wrong").load(),
+            Err(Error::Encryption { .. })
+        ));
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{
+    migration::{migrate_stored, Stored},
+    warn_skipped, Error, Migrated, Rows, Storage,
+};
+use migrate::Table;
+use serde_json::{json, Map, Value};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use vfs::{Disk, Vfs};
+
+/// Rows as a JSON list of objects keyed by column, leaving out empty fields.
+/// From version 1 on the list is the `rows` of an object with a `version`.
+pub struct Json<T: Rows> {
+    path: PathBuf,
+    context: T::Context,
+    vfs: Arc<dyn Vfs>,
+}
+impl<T: Rows> Json<T> {
+    pub fn new(path: impl Into<PathBuf>, context: T::Context) -> Self {
+        Self {
+            path: path.into(),
+            context,
+            vfs: Arc::new(Disk),
+        }
+    }
+    pub fn in_vfs(self, vfs: Arc<dyn Vfs>) -> Self {
+        Self { vfs, ..self }
+    }
+    fn error(&self, source: serde_json::Error) -> Error {
+        Error::Json {
+            path: self.path.clone(),
+            source,
+        }
+    }
+    fn read(&self) -> Result<Stored, Error> {
+        let text = self.vfs.read_to_string(&self.path)?;
+        let (version, objects): (u32, Vec<Map<String, Value>>) =
+            match serde_json::from_str(&text).map_err(|e| self.error(e))? {
+                Value::Object(mut stamped) => (
+                    serde_json::from_value(stamped.remove("version").unwrap_or_default())
+                        .map_err(|e| self.error(e))?,
+                    serde_json::from_value(stamped.remove("rows").unwrap_or_default())
+                        .map_err(|e| self.error(e))?,
+                ),
+                list => (0, serde_json::from_value(list).map_err(|e| self.error(e))?),
+            };
+        let mut columns: Vec<String> = vec![];
+        for object in &objects {
+            for key in object.keys() {
+                if !columns.contains(key) {
+                    columns.push(key.clone());
+                }
+            }
+        }
+        let rows = objects
+            .iter()
+            .map(|object| {
+                columns
+                    .iter()
+                    .map(|column| match object.get(column) {
+                        Some(Value::String(text)) => text.clone(),
+                        Some(Value::Null) | None => String::new(),
+                        Some(value) => value.to_string(),
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(Stored {
+            version,
+            spans: vec![],
+            lines: (1..=objects.len()).collect(),
+            table: Table::new(columns, rows),
+        })
+    }
+    fn write(&self, stored: &Stored) -> Result<(), Error> {
+        let columns = &stored.table.columns;
+        let objects: Vec<Map<String, Value>> = stored
+            .table
+            .rows
+            .iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .zip(row)
+                    .filter(|(_, field)| !field.is_empty())
+                    .map(|(column, field)| (column.clone(), Value::String(field.clone())))
+                    .collect()
+            })
+            .collect();
+        let value = match stored.version {
+            0 => json!(objects),
+            version => json!({ "version": version, "rows": objects }),
+        };
+        let text = serde_json::to_string_pretty(&value).map_err(|e| self.error(e))?;
+        Ok(self
+            .vfs
+            .write_atomic(&self.path, (text + "\n").as_bytes())?)
+    }
+}
+impl<T: Rows> Storage<T> for Json<T>
+where
+    T::Context: Send + Sync,
+{
+    fn path(&self) -> &Path {
+        &self.path
+    }
+    fn load(&self) -> Result<T, Error> {
+        let (value, skipped) = self.read()?.into_value::<T>(&self.path, &self.context)?;
+        warn_skipped(&self.path, skipped);
+        Ok(value)
+    }
+    fn save(&self, value: &T) -> Result<(), Error> {
+        self.write(&Stored::of(value))
+    }
+    fn migrate(&self, to: Option<u32>, dry_run: bool) -> Result<Migrated, Error> {
+        migrate_stored::<T>(&self.path, self.read()?, to, dry_run, |stored| {
+            self.write(stored)
+        })
+    }
+    fn vfs(&self) -> Arc<dyn Vfs> {
+        Arc::clone(&self.vfs)
+    }
+}
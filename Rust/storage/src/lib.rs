@@ -0,0 +1,357 @@
+use errors::Diagnostic;
+use migrate::{Migration, Table};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt, io,
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+use thiserror::Error;
+use vfs::{Disk, Vfs};
+
+mod csv;
+#[cfg(feature = "encryption")]
+mod encrypted;
+#[cfg(feature = "json")]
+mod json;
+mod migration;
+#[cfg(feature = "async")]
+mod nonblocking;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+pub use csv::{parse_csv, write_csv, Csv};
+#[cfg(feature = "encryption")]
+pub use encrypted::Encrypted;
+#[cfg(feature = "json")]
+pub use json::Json;
+pub use migration::Migrated;
+#[cfg(feature = "async")]
+pub use nonblocking::{AsyncStorage, AsyncWatch};
+#[cfg(feature = "sqlite")]
+pub use sqlite::Sqlite;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    /// Where the row came from: the line in CSV files and the position otherwise.
+    pub line: usize,
+    pub fields: Vec<String>,
+    /// The byte range of each field within its line, where there is one.
+    pub spans: Vec<Option<Range<usize>>>,
+}
+impl Row {
+    /// An error about field `index`, pointing at it when its place is known.
+    pub fn diagnostic(&self, message: impl fmt::Display, index: usize) -> Diagnostic {
+        let diagnostic = Diagnostic::new(message, self.line);
+        match self.spans.get(index).cloned().flatten() {
+            Some(span) => diagnostic.at(span),
+            None => diagnostic,
+        }
+    }
+}
+
+/// A collection kept as rows of text fields under fixed column names, which
+/// every backend knows how to store.
+pub trait Rows: Sized {
+    /// What reading rows needs besides the rows, like a default currency.
+    type Context;
+    const COLUMNS: &'static [&'static str];
+    /// How to bring rows written by older builds up to date, numbered from 1.
+    /// Files are stamped with the number of the last one, so adding one
+    /// upgrades older files on load and keeps newer ones from being misread.
+    const MIGRATIONS: &'static [Migration<Table>] = &[];
+    fn to_rows(&self) -> Vec<Vec<String>>;
+    /// Rebuilds the collection and returns the rows it had to skip.
+    fn from_rows(rows: Vec<Row>, context: &Self::Context) -> (Self, Vec<Diagnostic>);
+}
+
+pub trait Storage<T>: Send + Sync {
+    fn path(&self) -> &Path;
+    /// Fails with `NotFound` if nothing was saved yet.
+    fn load(&self) -> Result<T, Error>;
+    fn save(&self, value: &T) -> Result<(), Error>;
+    /// Rewrites the stored data at version `to`, the latest if `None`,
+    /// running the migrations in between. A dry run only reports them.
+    fn migrate(&self, to: Option<u32>, dry_run: bool) -> Result<Migrated, Error>;
+    /// Whether the data is kept encrypted, so callers know to leave plaintext
+    /// copies of it out of side files.
+    fn encrypted(&self) -> bool {
+        false
+    }
+    /// The filesystem the data is kept in.
+    fn vfs(&self) -> Arc<dyn Vfs> {
+        Arc::new(Disk)
+    }
+    fn watch(&self) -> Watch {
+        Watch::new(self.vfs(), self.path())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    #[default]
+    Csv,
+    Json,
+    Sqlite,
+}
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Backend::Csv => "csv",
+            Backend::Json => "json",
+            Backend::Sqlite => "sqlite",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[cfg(feature = "json")]
+    #[error("{} is not a JSON list of rows", path.display())]
+    Json {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[cfg(feature = "sqlite")]
+    #[error("could not use database {}", path.display())]
+    Sqlite {
+        path: PathBuf,
+        source: rusqlite::Error,
+    },
+    #[cfg(feature = "encryption")]
+    #[error("could not decrypt {}", path.display())]
+    Encryption {
+        path: PathBuf,
+        source: encryption::Error,
+    },
+    #[cfg(feature = "encryption")]
+    #[error("only csv storage can be encrypted, not {0}")]
+    NotEncryptable(Backend),
+    #[error("could not migrate {}", path.display())]
+    Migration {
+        path: PathBuf,
+        source: migrate::Error,
+    },
+    #[error("{0} storage is not built in, rebuild with the `{0}` feature")]
+    Disabled(Backend),
+}
+impl From<Error> for io::Error {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::Io(e) => e,
+            Error::Disabled(_) => io::Error::new(io::ErrorKind::Unsupported, error),
+            #[cfg(feature = "encryption")]
+            Error::NotEncryptable(_) => io::Error::new(io::ErrorKind::Unsupported, error),
+            #[allow(unreachable_patterns)]
+            error => io::Error::new(io::ErrorKind::InvalidData, error),
+        }
+    }
+}
+
+/// Opens `path` with the given backend, if it was compiled in.
+pub fn open<T>(
+    backend: Backend,
+    path: impl Into<PathBuf>,
+    context: T::Context,
+) -> Result<Box<dyn Storage<T>>, Error>
+where
+    T: Rows + 'static,
+    T::Context: Send + Sync + 'static,
+{
+    open_in(Arc::new(Disk), backend, path, context)
+}
+
+/// Opens `path` inside `vfs`. SQLite databases are always on disk.
+pub fn open_in<T>(
+    vfs: Arc<dyn Vfs>,
+    backend: Backend,
+    path: impl Into<PathBuf>,
+    context: T::Context,
+) -> Result<Box<dyn Storage<T>>, Error>
+where
+    T: Rows + 'static,
+    T::Context: Send + Sync + 'static,
+{
+    let path = path.into();
+    match backend {
+        Backend::Csv => Ok(Box::new(Csv::new(path, context).in_vfs(vfs))),
+        #[cfg(feature = "json")]
+        Backend::Json => Ok(Box::new(Json::new(path, context).in_vfs(vfs))),
+        #[cfg(feature = "sqlite")]
+        Backend::Sqlite => Ok(Box::new(Sqlite::new(path, context))),
+        #[allow(unreachable_patterns)]
+        backend => Err(Error::Disabled(backend)),
+    }
+}
+
+/// Opens `path` encrypted with `passphrase`, which works for CSV storage only.
+#[cfg(feature = "encryption")]
+pub fn open_encrypted<T>(
+    backend: Backend,
+    path: impl Into<PathBuf>,
+    context: T::Context,
+    passphrase: encryption::Passphrase,
+) -> Result<Box<dyn Storage<T>>, Error>
+where
+    T: Rows + 'static,
+    T::Context: Send + Sync + 'static,
+{
+    open_encrypted_in(Arc::new(Disk), backend, path, context, passphrase)
+}
+
+#[cfg(feature = "encryption")]
+pub fn open_encrypted_in<T>(
+    vfs: Arc<dyn Vfs>,
+    backend: Backend,
+    path: impl Into<PathBuf>,
+    context: T::Context,
+    passphrase: encryption::Passphrase,
+) -> Result<Box<dyn Storage<T>>, Error>
+where
+    T: Rows + 'static,
+    T::Context: Send + Sync + 'static,
+{
+    match backend {
+        Backend::Csv => Ok(Box::new(
+            Encrypted::new(path, context, passphrase).in_vfs(vfs),
+        )),
+        backend => Err(Error::NotEncryptable(backend)),
+    }
+}
+
+/// Tells when the stored data changed, for example because another process
+/// saved it. Saving through the storage counts as a change too.
+#[derive(Debug)]
+pub struct Watch {
+    vfs: Arc<dyn Vfs>,
+    path: PathBuf,
+    seen: Option<SystemTime>,
+}
+impl Watch {
+    pub fn new(vfs: Arc<dyn Vfs>, path: &Path) -> Self {
+        let seen = vfs.modified(path).ok();
+        Self {
+            vfs,
+            path: path.to_path_buf(),
+            seen,
+        }
+    }
+    /// Whether the data changed since the last call or since watching started.
+    pub fn changed(&mut self) -> bool {
+        let now = self.vfs.modified(&self.path).ok();
+        let changed = now != self.seen;
+        self.seen = now;
+        changed
+    }
+}
+
+#[cfg(any(feature = "json", feature = "sqlite"))]
+fn warn_skipped(path: &Path, skipped: Vec<Diagnostic>) {
+    for diagnostic in skipped {
+        tracing::warn!(
+            "error: {}\n --> {} row {}",
+            diagnostic.message,
+            path.display(),
+            diagnostic.line
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Names with a number, skipping rows whose number does not parse.
+    #[derive(Debug, PartialEq)]
+    pub struct Names(pub Vec<(String, u32)>);
+    impl Rows for Names {
+        type Context = ();
+        const COLUMNS: &'static [&'static str] = &["name", "number"];
+        fn to_rows(&self) -> Vec<Vec<String>> {
+            self.0
+                .iter()
+                .map(|(name, number)| vec![name.clone(), number.to_string()])
+                .collect()
+        }
+        fn from_rows(rows: Vec<Row>, _: &()) -> (Self, Vec<Diagnostic>) {
+            let mut names = vec![];
+            let mut skipped = vec![];
+            for row in rows {
+                match row.fields[1].parse() {
+                    Ok(number) => names.push((row.fields[0].clone(), number)),
+                    Err(e) => skipped.push(row.diagnostic(e, 1)),
+                }
+            }
+            (Names(names), skipped)
+        }
+    }
+
+    pub fn names() -> Names {
+        Names(vec![
+            ("Rent, flat".to_string(), 1),
+            ("\"Gym\"".to_string(), 2),
+        ])
+    }
+
+    fn round_trip(backend: Backend) {
+        let dir = tempfile::tempdir().unwrap();
+        check_round_trip(open::<Names>(backend, dir.path().join("names"), ()).unwrap());
+    }
+
+    fn check_round_trip(store: Box<dyn Storage<Names>>) {
+        assert!(matches!(store.load(), Err(Error::Io(e)) if e.kind() == io::ErrorKind::NotFound));
+        let mut watch = store.watch();
+        store.save(&names()).unwrap();
+        assert!(watch.changed());
+        assert!(!watch.changed());
+        assert_eq!(store.load().unwrap(), names());
+        store.save(&Names(vec![])).unwrap();
+        assert_eq!(store.load().unwrap(), Names(vec![]));
+    }
+
+    #[test]
+    fn csv_round_trips() {
+        round_trip(Backend::Csv);
+    }
+
+    #[test]
+    fn saves_in_memory_by_renaming_into_place() {
+        let memory = Arc::new(vfs::Memory::new());
+        for backend in [Backend::Csv, Backend::Json] {
+            let path = PathBuf::from(format!("names.{}", backend));
+            match open_in::<Names>(memory.clone(), backend, &path, ()) {
+                Ok(store) => check_round_trip(store),
+                Err(Error::Disabled(_)) => continue,
+                Err(e) => panic!("{}", e),
+            }
+            assert!(!memory.exists(&vfs::temporary(&path)).unwrap());
+        }
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_round_trips() {
+        round_trip(Backend::Json);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn sqlite_round_trips() {
+        round_trip(Backend::Sqlite);
+    }
+
+    #[cfg(not(feature = "sqlite"))]
+    #[test]
+    fn missing_backends_are_reported() {
+        assert!(matches!(
+            open::<Names>(Backend::Sqlite, "names.db", ()),
+            Err(Error::Disabled(Backend::Sqlite))
+        ));
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{Error, Row, Rows};
+use errors::Diagnostic;
+use migrate::Table;
+use std::{ops::Range, path::Path};
+
+/// What a backend keeps on disk before it becomes a `T`: the rows under
+/// their column names and the version they were written at.
+pub(crate) struct Stored {
+    pub version: u32,
+    pub table: Table,
+    /// Where each row came from, see [`Row::line`].
+    pub lines: Vec<usize>,
+    /// Where each field of a row sits in its line, if the format has lines.
+    pub spans: Vec<Vec<Range<usize>>>,
+}
+impl Stored {
+    pub fn of<T: Rows>(value: &T) -> Self {
+        let rows = value.to_rows();
+        Self {
+            version: migrate::latest(T::MIGRATIONS),
+            lines: (1..=rows.len()).collect(),
+            spans: vec![],
+            table: Table::new(T::COLUMNS.iter().map(|c| c.to_string()).collect(), rows),
+        }
+    }
+    /// Brings the rows up to date and rebuilds the collection from them.
+    pub fn into_value<T: Rows>(
+        mut self,
+        path: &Path,
+        context: &T::Context,
+    ) -> Result<(T, Vec<Diagnostic>), Error> {
+        let stored_columns = self.table.columns.clone();
+        let latest = migrate::latest(T::MIGRATIONS);
+        migrate::migrate(T::MIGRATIONS, &mut self.table, self.version, latest)
+            .map_err(|source| migration_error(path, source))?;
+        // Spans belong to the columns as stored, before migrations moved them.
+        let indices: Vec<Option<usize>> = T::COLUMNS
+            .iter()
+            .map(|name| stored_columns.iter().position(|column| column == name))
+            .collect();
+        let mut spans = self.spans.into_iter();
+        let rows = self
+            .table
+            .select(T::COLUMNS)
+            .into_iter()
+            .zip(self.lines)
+            .map(|(fields, line)| {
+                let stored = spans.next().unwrap_or_default();
+                let spans = indices
+                    .iter()
+                    .map(|index| index.and_then(|index| stored.get(index).cloned()))
+                    .collect();
+                Row {
+                    line,
+                    fields,
+                    spans,
+                }
+            })
+            .collect();
+        Ok(T::from_rows(rows, context))
+    }
+}
+
+/// The outcome of [`Storage::migrate`](crate::Storage::migrate).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Migrated {
+    pub from: u32,
+    pub to: u32,
+    /// The migrations run, like `up 1: record paid dates as payments`.
+    pub steps: Vec<String>,
+}
+
+/// Moves `stored` to version `to`, or the latest one, and passes it to
+/// `write` unless this is a dry run or nothing changes.
+pub(crate) fn migrate_stored<T: Rows>(
+    path: &Path,
+    mut stored: Stored,
+    to: Option<u32>,
+    dry_run: bool,
+    write: impl FnOnce(&Stored) -> Result<(), Error>,
+) -> Result<Migrated, Error> {
+    let from = stored.version;
+    let to = to.unwrap_or_else(|| migrate::latest(T::MIGRATIONS));
+    let plan =
+        migrate::plan(T::MIGRATIONS, from, to).map_err(|source| migration_error(path, source))?;
+    let steps = plan.iter().map(|step| step.to_string()).collect();
+    if !dry_run && from != to {
+        for step in &plan {
+            step.run(&mut stored.table);
+        }
+        stored.version = to;
+        write(&stored)?;
+    }
+    Ok(Migrated { from, to, steps })
+}
+
+fn migration_error(path: &Path, source: migrate::Error) -> Error {
+    Error::Migration {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{open, tests::names, Backend};
+    use migrate::Migration;
+    use std::fs;
+
+    /// Names whose numbers were first stored as `count`.
+    #[derive(Debug, PartialEq)]
+    struct Renamed(crate::tests::Names);
+    impl Rows for Renamed {
+        type Context = ();
+        const COLUMNS: &'static [&'static str] = &["name", "number"];
+        const MIGRATIONS: &'static [Migration<Table>] = &[Migration {
+            version: 1,
+            description: "rename count to number",
+            up: |table| table.rename_column("count", "number"),
+            down: |table| table.rename_column("number", "count"),
+        }];
+        fn to_rows(&self) -> Vec<Vec<String>> {
+            self.0.to_rows()
+        }
+        fn from_rows(rows: Vec<Row>, context: &()) -> (Self, Vec<Diagnostic>) {
+            let (names, skipped) = crate::tests::Names::from_rows(rows, context);
+            (Renamed(names), skipped)
+        }
+    }
+
+    #[test]
+    fn upgrades_on_load_and_migrates_both_ways() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("names.csv");
+        let old = "name,count\n\"Rent, flat\",1\n\"\"\"Gym\"\"\",2\n";
+        fs::write(&path, old).unwrap();
+        let store = open::<Renamed>(Backend::Csv, &path, ()).unwrap();
+        assert_eq!(store.load().unwrap(), Renamed(names()));
+
+        let dry = store.migrate(None, true).unwrap();
+        assert_eq!(dry.steps, ["up 1: rename count to number"]);
+        assert_eq!(fs::read_to_string(&path).unwrap(), old);
+        store.migrate(None, false).unwrap();
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .starts_with("#version=1\nname,number\n"));
+        let down = store.migrate(Some(0), false).unwrap();
+        assert_eq!((down.from, down.to), (1, 0));
+        assert_eq!(fs::read_to_string(&path).unwrap(), old);
+
+        fs::write(&path, format!("#version=2\n{}", old)).unwrap();
+        assert!(matches!(store.load(), Err(Error::Migration { .. })));
+    }
+}
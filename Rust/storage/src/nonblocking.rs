@@ -0,0 +1,118 @@
+use crate::{Error, Storage};
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+use tokio::time::{self, Interval, MissedTickBehavior};
+
+/// Runs a storage on tokio's blocking pool, so async code serving several
+/// requests never stalls on file IO. Clones share the same storage.
+pub struct AsyncStorage<T> {
+    inner: Arc<dyn Storage<T>>,
+}
+impl<T> Clone for AsyncStorage<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+impl<T> From<Box<dyn Storage<T>>> for AsyncStorage<T> {
+    fn from(storage: Box<dyn Storage<T>>) -> Self {
+        Self {
+            inner: Arc::from(storage),
+        }
+    }
+}
+impl<T: Send + Sync + 'static> AsyncStorage<T> {
+    pub fn path(&self) -> &Path {
+        self.inner.path()
+    }
+    pub async fn load(&self) -> Result<T, Error> {
+        let inner = Arc::clone(&self.inner);
+        blocking(move || inner.load()).await
+    }
+    pub async fn save(&self, value: Arc<T>) -> Result<(), Error> {
+        let inner = Arc::clone(&self.inner);
+        blocking(move || inner.save(&value)).await
+    }
+    /// Checks for changes every `period`.
+    pub fn watch(&self, period: Duration) -> AsyncWatch {
+        AsyncWatch::new(self.path(), period)
+    }
+}
+
+async fn blocking<R: Send + 'static>(
+    task: impl FnOnce() -> Result<R, Error> + Send + 'static,
+) -> Result<R, Error> {
+    tokio::task::spawn_blocking(task)
+        .await
+        .map_err(|e| Error::Io(io::Error::other(e)))?
+}
+
+/// The async counterpart of [`Watch`](crate::Watch).
+#[derive(Debug)]
+pub struct AsyncWatch {
+    path: PathBuf,
+    seen: Option<Option<SystemTime>>,
+    interval: Interval,
+}
+impl AsyncWatch {
+    fn new(path: &Path, period: Duration) -> Self {
+        let mut interval = time::interval(period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Self {
+            path: path.to_path_buf(),
+            seen: None,
+            interval,
+        }
+    }
+    /// Waits until the data differs from when this was first awaited or last returned.
+    pub async fn changed(&mut self) {
+        loop {
+            self.interval.tick().await;
+            let now = tokio::fs::metadata(&self.path)
+                .await
+                .and_then(|meta| meta.modified())
+                .ok();
+            match self.seen {
+                Some(seen) if seen != now => {
+                    self.seen = Some(now);
+                    return;
+                }
+                Some(_) => {}
+                None => self.seen = Some(now),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        open,
+        tests::{names, Names},
+        Backend,
+    };
+
+    #[tokio::test]
+    async fn loads_and_saves_off_the_runtime() {
+        let dir = tempfile::tempdir().unwrap();
+        let store: AsyncStorage<Names> = open(Backend::Csv, dir.path().join("names.csv"), ())
+            .unwrap()
+            .into();
+        let mut watch = store.watch(Duration::from_millis(10));
+        let saved = Arc::new(names());
+        let writer = store.clone();
+        let (_, saved_result) = tokio::join!(watch.changed(), async {
+            // Give the watch its first look before the file appears.
+            time::sleep(Duration::from_millis(50)).await;
+            writer.save(Arc::clone(&saved)).await
+        });
+        saved_result.unwrap();
+        assert_eq!(store.load().await.unwrap(), *saved);
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{
+    migration::{migrate_stored, Stored},
+    warn_skipped, Error, Migrated, Rows, Storage,
+};
+use migrate::Table;
+use rusqlite::{params_from_iter, Connection, OptionalExtension};
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+const TABLE: &str = "rows";
+
+/// Rows in a table of text columns inside an SQLite database file, with the
+/// version kept in its `user_version`.
+pub struct Sqlite<T: Rows> {
+    path: PathBuf,
+    context: T::Context,
+}
+impl<T: Rows> Sqlite<T> {
+    pub fn new(path: impl Into<PathBuf>, context: T::Context) -> Self {
+        Self {
+            path: path.into(),
+            context,
+        }
+    }
+    fn error(&self, source: rusqlite::Error) -> Error {
+        Error::Sqlite {
+            path: self.path.clone(),
+            source,
+        }
+    }
+    fn read(&self) -> Result<Stored, Error> {
+        // Opening creates missing databases, so check first like the file backends.
+        if !self.path.try_exists()? {
+            return Err(io::Error::new(io::ErrorKind::NotFound, "no database").into());
+        }
+        let connection = Connection::open(&self.path).map_err(|e| self.error(e))?;
+        let version = connection
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(|e| self.error(e))?;
+        let exists = connection
+            .query_row(
+                "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+                [TABLE],
+                |_| Ok(()),
+            )
+            .optional()
+            .map_err(|e| self.error(e))?;
+        let mut table = Table::default();
+        if exists.is_some() {
+            let query = format!("SELECT * FROM {} ORDER BY rowid", TABLE);
+            let mut statement = connection.prepare(&query).map_err(|e| self.error(e))?;
+            table.columns = statement
+                .column_names()
+                .into_iter()
+                .map(String::from)
+                .collect();
+            let width = table.columns.len();
+            let mut result = statement.query([]).map_err(|e| self.error(e))?;
+            while let Some(row) = result.next().map_err(|e| self.error(e))? {
+                let fields = (0..width)
+                    .map(|index| row.get::<_, Option<String>>(index))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| self.error(e))?;
+                table
+                    .rows
+                    .push(fields.into_iter().map(Option::unwrap_or_default).collect());
+            }
+        }
+        Ok(Stored {
+            version,
+            spans: vec![],
+            lines: (1..=table.rows.len()).collect(),
+            table,
+        })
+    }
+    fn write(&self, stored: &Stored) -> Result<(), Error> {
+        let mut connection = Connection::open(&self.path).map_err(|e| self.error(e))?;
+        let transaction = connection.transaction().map_err(|e| self.error(e))?;
+        let columns = quoted(&stored.table.columns);
+        let placeholders = vec!["?"; stored.table.columns.len()].join(", ");
+        transaction
+            .execute_batch(&format!(
+                "DROP TABLE IF EXISTS {table}; CREATE TABLE {table} ({columns}); \
+                 PRAGMA user_version = {version};",
+                table = TABLE,
+                columns = columns,
+                version = stored.version
+            ))
+            .map_err(|e| self.error(e))?;
+        {
+            let insert = format!(
+                "INSERT INTO {} ({}) VALUES ({})",
+                TABLE, columns, placeholders
+            );
+            let mut statement = transaction.prepare(&insert).map_err(|e| self.error(e))?;
+            for row in &stored.table.rows {
+                statement
+                    .execute(params_from_iter(row))
+                    .map_err(|e| self.error(e))?;
+            }
+        }
+        transaction.commit().map_err(|e| self.error(e))
+    }
+}
+
+fn quoted(columns: &[String]) -> String {
+    columns
+        .iter()
+        .map(|column| format!("\"{}\"", column.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl<T: Rows> Storage<T> for Sqlite<T>
+where
+    T::Context: Send + Sync,
+{
+    fn path(&self) -> &Path {
+        &self.path
+    }
+    fn load(&self) -> Result<T, Error> {
+        let (value, skipped) = self.read()?.into_value::<T>(&self.path, &self.context)?;
+        warn_skipped(&self.path, skipped);
+        Ok(value)
+    }
+    fn save(&self, value: &T) -> Result<(), Error> {
+        self.write(&Stored::of(value))
+    }
+    fn migrate(&self, to: Option<u32>, dry_run: bool) -> Result<Migrated, Error> {
+        migrate_stored::<T>(&self.path, self.read()?, to, dry_run, |stored| {
+            self.write(stored)
+        })
+    }
+}
@@ -0,0 +1,357 @@
+use chrono::{Days, NaiveDate};
+use clap::{Args, ValueEnum, ValueHint};
+use serde_json::{Map, Value};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+const CONTACT_COLUMNS: &[&str] = &["id", "name", "email", "birthday"];
+const BILL_COLUMNS: &[&str] = &[
+    "id",
+    "name",
+    "amount",
+    "due",
+    "recurrence",
+    "category",
+    "currency",
+    "payments",
+    "notes",
+    "priority",
+];
+
+const FIRST_NAMES: &[&str] = &[
+    "Ann", "Ben", "Clara", "David", "Emma", "Felix", "Greta", "Hannah", "Ivan", "Jana", "Karl",
+    "Lena", "Mia", "Noah", "Olga", "Paul", "Rosa", "Sven", "Tom", "Ute",
+];
+const LAST_NAMES: &[&str] = &[
+    "Bauer",
+    "Fischer",
+    "Hoffmann",
+    "Koch",
+    "Meyer",
+    "Richter",
+    "Schmidt",
+    "Schulz",
+    "Wagner",
+    "Weber",
+    "Wolf",
+    "Zimmermann",
+];
+const DOMAINS: &[&str] = &["example.com", "mail.example.de", "post.example.org"];
+/// Names that are valid but trip up naive parsers: delimiters, quotes,
+/// accents, other scripts and emoji.
+const ODD_NAMES: &[&str] = &[
+    "Müller, Jürgen",
+    "Anna \"Annie\" Schmidt",
+    "O'Brien; Sean",
+    "李小龙",
+    "Zoë 🙂",
+    "Åsa Ñúñez-Øberg",
+];
+/// Payees with the usual range of their amounts in cents.
+const PAYEES: &[(&str, &str, u64, u64)] = &[
+    ("Rent", "Housing", 40_000, 150_000),
+    ("Electricity", "Utilities", 3_000, 15_000),
+    ("Water", "Utilities", 1_500, 6_000),
+    ("Internet", "Utilities", 2_000, 8_000),
+    ("Phone", "Utilities", 1_000, 5_000),
+    ("Gym", "Health", 1_500, 9_000),
+    ("Insurance", "Insurance", 5_000, 40_000),
+    ("Streaming", "Leisure", 500, 2_000),
+    ("Car loan", "Transport", 15_000, 60_000),
+];
+const RECURRENCES: &[&str] = &["", "", "monthly", "weekly", "yearly", "every 3 months"];
+const CURRENCIES: &[&str] = &["", "EUR", "EUR", "USD", "GBP", "CHF"];
+const PRIORITIES: &[&str] = &["", "", "", "high", "low"];
+const ODD_NOTES: &[&str] = &[
+    "paid, but \"late\"",
+    "split; ask Tom",
+    "Überweisung 🙂",
+    "   ",
+    "=SUM(A1:A2)",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    #[default]
+    Csv,
+    Json,
+}
+
+/// Writes random contacts and bills for benchmarks, fuzz corpora and trying
+/// out large files. The same seed always gives the same files.
+#[derive(Args, Debug)]
+pub struct GenData {
+    #[arg(long, default_value_t = 1000, help = "how many contacts to write")]
+    contacts: usize,
+    #[arg(long, default_value_t = 100, help = "how many bills to write")]
+    bills: usize,
+    #[arg(long, default_value_t = 0, help = "seed for the random data")]
+    seed: u64,
+    #[arg(long, value_enum, default_value_t, help = "file format")]
+    format: Format,
+    #[arg(
+        long,
+        value_hint = ValueHint::DirPath,
+        default_value = ".",
+        help = "directory for contacts.<format> and bills.<format>"
+    )]
+    out: PathBuf,
+    #[arg(
+        long,
+        default_value_t = 0,
+        value_parser = clap::value_parser!(u8).range(0..=100),
+        help = "percentage of rows with a field the tools reject"
+    )]
+    broken: u8,
+}
+
+/// SplitMix64, small and fixed so a seed gives the same data on every
+/// platform and in every version.
+struct Random(u64);
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+    fn between(&mut self, low: u64, high: u64) -> u64 {
+        low + self.below(high - low + 1)
+    }
+    fn percent(&mut self, percent: u8) -> bool {
+        self.below(100) < u64::from(percent)
+    }
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len() as u64) as usize]
+    }
+    fn date(&mut self, from: NaiveDate, days: u64) -> NaiveDate {
+        from + Days::new(self.below(days))
+    }
+}
+
+fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("valid date")
+}
+
+fn contact(random: &mut Random, id: usize, broken: bool) -> Vec<String> {
+    let name = if random.percent(5) {
+        random.pick(ODD_NAMES).to_string()
+    } else if random.percent(1) {
+        "Long".repeat(50)
+    } else {
+        format!("{} {}", random.pick(FIRST_NAMES), random.pick(LAST_NAMES))
+    };
+    let email = match random.below(10) {
+        0 | 1 => String::new(),
+        2 => format!(
+            "{}+bills{}@{}",
+            local_part(&name, id),
+            id,
+            random.pick(DOMAINS)
+        ),
+        3 => format!("{}@{}", local_part(&name, id), random.pick(DOMAINS)).to_uppercase(),
+        _ => format!("{}{}@{}", local_part(&name, id), id, random.pick(DOMAINS)),
+    };
+    let birthday = match random.below(20) {
+        0..=5 => String::new(),
+        6 => "2000-02-29".to_string(),
+        _ => random.date(date(1940, 1, 1), 70 * 365).to_string(),
+    };
+    let mut row = vec![id.to_string(), name, email, birthday];
+    if broken {
+        let (index, value) = match random.below(3) {
+            0 => (0, "x"),
+            1 => (1, ""),
+            _ => (3, "2023-02-30"),
+        };
+        row[index] = value.to_string();
+    }
+    row
+}
+
+fn local_part(name: &str, id: usize) -> String {
+    let local: String = name
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    match local.is_empty() {
+        true => format!("user{}", id),
+        false => local,
+    }
+}
+
+fn bill(random: &mut Random, id: usize, broken: bool) -> Vec<String> {
+    let (payee, category, low, high) = PAYEES[random.below(PAYEES.len() as u64) as usize];
+    let name = match random.below(20) {
+        0 => random.pick(ODD_NAMES).to_string(),
+        1 | 2 => format!("{} {}", payee, random.pick(LAST_NAMES)),
+        _ => payee.to_string(),
+    };
+    let cents = match random.below(50) {
+        0 => 0,
+        1 => 1,
+        2 => 9_999_999,
+        _ => random.between(low, high),
+    };
+    let amount = match cents % 100 {
+        0 if random.percent(50) => (cents / 100).to_string(),
+        _ => format!("{}.{:02}", cents / 100, cents % 100),
+    };
+    let due = random.date(date(2024, 1, 1), 3 * 365);
+    let payments = match random.below(4) {
+        0 => format!("{}@{}", amount, due),
+        1 if cents > 1 => format!(
+            "{}.{:02}@{}",
+            cents / 200,
+            cents / 2 % 100,
+            due - Days::new(random.below(30))
+        ),
+        _ => String::new(),
+    };
+    let notes = match random.below(10) {
+        0 => random.pick(ODD_NOTES).to_string(),
+        1 => format!("contract {}", random.below(1_000_000)),
+        _ => String::new(),
+    };
+    let category = match random.percent(10) {
+        true => String::new(),
+        false => category.to_string(),
+    };
+    let mut row = vec![
+        id.to_string(),
+        name,
+        amount,
+        due.to_string(),
+        random.pick(RECURRENCES).to_string(),
+        category,
+        random.pick(CURRENCIES).to_string(),
+        payments,
+        notes,
+        random.pick(PRIORITIES).to_string(),
+    ];
+    if broken {
+        let (index, value) = match random.below(4) {
+            0 => (2, "abc"),
+            1 => (2, "-5"),
+            2 => (3, "2023-13-01"),
+            _ => (4, "fortnightly"),
+        };
+        row[index] = value.to_string();
+    }
+    row
+}
+
+fn text(format: Format, columns: &[&str], rows: &[Vec<String>]) -> String {
+    match format {
+        Format::Csv => flatfile::Writer::default().table(columns, rows),
+        // Laid out like the JSON storage backend: one object per row
+        // without its empty fields.
+        Format::Json => {
+            let objects: Vec<Value> = rows
+                .iter()
+                .map(|row| {
+                    let object: Map<String, Value> = columns
+                        .iter()
+                        .zip(row)
+                        .filter(|(_, field)| !field.is_empty())
+                        .map(|(column, field)| (column.to_string(), Value::String(field.clone())))
+                        .collect();
+                    Value::Object(object)
+                })
+                .collect();
+            let text = serde_json::to_string_pretty(&objects).expect("strings always serialize");
+            text + "\n"
+        }
+    }
+}
+
+fn generate(
+    random: &mut Random,
+    count: usize,
+    broken: u8,
+    row: fn(&mut Random, usize, bool) -> Vec<String>,
+) -> Vec<Vec<String>> {
+    (1..=count)
+        .map(|id| {
+            let broken = random.percent(broken);
+            row(random, id, broken)
+        })
+        .collect()
+}
+
+fn write(
+    out: &Path,
+    name: &str,
+    message: &str,
+    format: Format,
+    text: String,
+    count: usize,
+) -> io::Result<()> {
+    let extension = match format {
+        Format::Csv => "csv",
+        Format::Json => "json",
+    };
+    let path = out.join(format!("{}.{}", name, extension));
+    fs::write(&path, text)?;
+    println!(
+        "{}",
+        i18n::t!(message, count = count, file = path.display())
+    );
+    Ok(())
+}
+
+pub fn run(args: &GenData) -> io::Result<()> {
+    let mut random = Random(args.seed);
+    let contacts = generate(&mut random, args.contacts, args.broken, contact);
+    let bills = generate(&mut random, args.bills, args.broken, bill);
+    fs::create_dir_all(&args.out)?;
+    let text_of = |columns, rows| text(args.format, columns, rows);
+    write(
+        &args.out,
+        "contacts",
+        "study-wrote-contacts",
+        args.format,
+        text_of(CONTACT_COLUMNS, &contacts),
+        args.contacts,
+    )?;
+    write(
+        &args.out,
+        "bills",
+        "study-wrote-bills",
+        args.format,
+        text_of(BILL_COLUMNS, &bills),
+        args.bills,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_rows() {
+        let rows = |seed| generate(&mut Random(seed), 200, 10, contact);
+        assert_eq!(rows(42), rows(42));
+        assert_ne!(rows(42), rows(43));
+    }
+
+    #[test]
+    fn contacts_load_unless_broken() {
+        let rows = generate(&mut Random(1), 500, 0, contact);
+        let file = text(Format::Csv, CONTACT_COLUMNS, &rows);
+        let records = contact_manager::parse_records(&file, Path::new("contacts.csv")).unwrap();
+        assert_eq!(records.len(), 500);
+
+        let rows = generate(&mut Random(1), 500, 20, contact);
+        let file = text(Format::Csv, CONTACT_COLUMNS, &rows);
+        let records = contact_manager::parse_records(&file, Path::new("contacts.csv")).unwrap();
+        assert!(records.len() < 450);
+    }
+}
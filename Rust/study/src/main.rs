@@ -0,0 +1,151 @@
+use clap::{builder::Styles, Parser, Subcommand, ValueHint};
+use gen_data::GenData;
+use serde::{Deserialize, Serialize};
+use settings::{Layers, Settings};
+use std::{ffi::OsString, path::PathBuf, process};
+
+mod gen_data;
+
+/// Flags every tool understands, kept in the study config file so they
+/// only need setting once.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct Config {
+    lang: Option<String>,
+    log_file: Option<PathBuf>,
+}
+impl Settings for Config {
+    const APP: &'static str = "study";
+}
+
+#[derive(Parser, Debug)]
+#[command(
+    about = "Every study project in one program",
+    version,
+    styles = Styles::styled()
+)]
+struct Opt {
+    #[arg(
+        long,
+        value_hint = ValueHint::FilePath,
+        help = "config file, defaults to config.toml in the study config directory"
+    )]
+    config: Option<PathBuf>,
+    #[arg(short, long, help = "log debug details")]
+    verbose: bool,
+    #[arg(
+        long,
+        value_hint = ValueHint::FilePath,
+        help = "also append log output to this file"
+    )]
+    log_file: Option<PathBuf>,
+    #[arg(
+        long,
+        help = "language for messages, like de or de-AT, defaults to LANG"
+    )]
+    lang: Option<String>,
+    #[command(subcommand)]
+    tool: Tool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Tool {
+    /// The contact manager; see `study contacts --help`
+    #[command(disable_help_flag = true, disable_version_flag = true)]
+    Contacts {
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<OsString>,
+    },
+    /// The bill manager; see `study bills --help`
+    #[command(disable_help_flag = true, disable_version_flag = true)]
+    Bills {
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<OsString>,
+    },
+    /// Writes random contacts and bills for benchmarks and testing large files
+    GenData(GenData),
+}
+
+/// The shared flags as a tool's own, in front of the arguments meant for it.
+fn tool_args(
+    name: &str,
+    opt: &Opt,
+    config: &Config,
+    lang: Option<&str>,
+    rest: &[OsString],
+) -> Vec<OsString> {
+    let mut args = vec![OsString::from(format!("study {}", name))];
+    // Given again after the tool name, it would be a duplicate flag there,
+    // so the tool's own one wins.
+    if opt.verbose && !given(rest, "--verbose") && !rest.iter().any(|arg| arg == "-v") {
+        args.push("--verbose".into());
+    }
+    if let Some(log_file) = config
+        .log_file
+        .as_ref()
+        .filter(|_| !given(rest, "--log-file"))
+    {
+        args.push("--log-file".into());
+        args.push(log_file.into());
+    }
+    if let Some(lang) = lang.filter(|_| !given(rest, "--lang")) {
+        args.push("--lang".into());
+        args.push(lang.into());
+    }
+    args.extend(rest.iter().cloned());
+    args
+}
+
+/// Whether `flag` is among the arguments, also in its `--flag=value` form.
+fn given(rest: &[OsString], flag: &str) -> bool {
+    rest.iter().any(|arg| {
+        let arg = arg.to_string_lossy();
+        arg == flag
+            || arg
+                .strip_prefix(flag)
+                .is_some_and(|value| value.starts_with('='))
+    })
+}
+
+fn main() {
+    // The tools' self-update must replace this binary with a newer `study`,
+    // not with a build of just one tool.
+    #[cfg(feature = "self-update")]
+    update::set_host("study", env!("CARGO_PKG_VERSION"));
+    let opt = Opt::parse();
+    let config: Config = match Layers::new()
+        .file(opt.config.clone())
+        .flag("lang", opt.lang.as_ref())
+        .flag("log_file", opt.log_file.as_ref())
+        .load()
+    {
+        Ok(config) => config,
+        Err(e) => {
+            errors::report(&e);
+            process::exit(1);
+        }
+    };
+    match &opt.tool {
+        Tool::Contacts { args } => {
+            let all = tool_args("contacts", &opt, &config, config.lang.as_deref(), args);
+            contact_manager::cli::main(all);
+        }
+        Tool::Bills { args } => {
+            // The bill manager only takes the languages it has messages in,
+            // so others fall back the way the message catalogs do.
+            let lang = config
+                .lang
+                .as_deref()
+                .map(|lang| i18n::Catalog::new(lang).language());
+            let all = tool_args("bills", &opt, &config, lang.as_deref(), args);
+            bill_manager::main(all);
+        }
+        Tool::GenData(args) => {
+            i18n::set_language(config.lang.as_deref());
+            if let Err(e) = gen_data::run(args) {
+                errors::report(&e);
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+fn study(dir: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("study").unwrap();
+    cmd.current_dir(dir.path())
+        .env("PATH", "")
+        .env("XDG_CONFIG_HOME", dir.path())
+        .env("HOME", dir.path())
+        .env_remove("XDG_DATA_HOME")
+        .env_remove("XDG_CACHE_HOME")
+        .env_remove("CONTACT_MANAGER_DATA_FILE")
+        .env_remove("CONTACT_MANAGER_STORAGE")
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .env_remove("LANG");
+    cmd
+}
+
+#[test]
+fn runs_each_tool() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("p2_data.csv"), "id,name,email,birthday\n").unwrap();
+    study(&dir)
+        .args(["contacts", "add", "Ann", "-e", "ann@example.com"])
+        .assert()
+        .success();
+    study(&dir)
+        .args(["contacts", "show", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Email:    ann@example.com"));
+    study(&dir)
+        .args(["bills", "-d", "bills.csv"])
+        .write_stdin("add;Rent;500\nlist\n")
+        .assert()
+        .success()
+        .stdout("ok;add;1\nbill;1;Rent;500.00;EUR;;\nok;list;1\n");
+    study(&dir)
+        .args(["contacts", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Usage: study contacts"));
+}
+
+#[test]
+fn shares_flags_and_config() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("p2_data.csv"), "id,name,email,birthday\n").unwrap();
+    study(&dir)
+        .args(["--lang", "de", "contacts", "show", "1"])
+        .assert()
+        .success()
+        .stdout("Kontakt nicht gefunden\n");
+
+    fs::create_dir_all(dir.path().join("study")).unwrap();
+    fs::write(dir.path().join("study/config.toml"), "lang = \"de-AT\"\n").unwrap();
+    study(&dir)
+        .args(["contacts", "show", "1"])
+        .assert()
+        .success()
+        .stdout("Kontakt nicht gefunden\n");
+    study(&dir)
+        .args(["bills", "-d", "bills.csv", "--interactive"])
+        .write_stdin("")
+        .assert()
+        .stdout(predicate::str::contains("Rechnung hinzufügen"));
+}
+
+#[test]
+fn forwards_flags_each_tool_accepts() {
+    let dir = tempfile::tempdir().unwrap();
+    study(&dir)
+        .args(["--lang", "fr", "bills", "-d", "bills.csv", "--interactive"])
+        .write_stdin("")
+        .assert()
+        .stdout(predicate::str::contains("Add bill"));
+    study(&dir)
+        .args(["-v", "bills", "-v", "-d", "bills.csv"])
+        .write_stdin("list\n")
+        .assert()
+        .success()
+        .stdout("ok;list;0\n");
+}
+
+#[test]
+fn lets_the_tool_flags_win() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("p2_data.csv"), "id,name,email,birthday\n").unwrap();
+    fs::create_dir_all(dir.path().join("study")).unwrap();
+    fs::write(dir.path().join("study/config.toml"), "lang = \"de\"\n").unwrap();
+    study(&dir)
+        .args(["contacts", "--lang", "en", "show", "1"])
+        .assert()
+        .success()
+        .stdout("record not found\n");
+    study(&dir)
+        .args([
+            "--lang",
+            "de",
+            "bills",
+            "--lang=en",
+            "-d",
+            "bills.csv",
+            "--interactive",
+        ])
+        .write_stdin("")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Add bill"));
+}
+
+#[test]
+fn lets_the_tool_log_file_win() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join("p2_data.csv"), "id,name,email,birthday\n").unwrap();
+    study(&dir)
+        .args([
+            "--log-file",
+            "study.log",
+            "bills",
+            "--log-file",
+            "bills.log",
+        ])
+        .args(["-v", "-d", "bills.csv"])
+        .write_stdin("list\n")
+        .assert()
+        .success()
+        .stdout("ok;list;0\n");
+    assert!(dir.path().join("bills.log").exists());
+    study(&dir)
+        .args([
+            "--log-file",
+            "study.log",
+            "contacts",
+            "--log-file=contacts.log",
+        ])
+        .args(["-v", "show", "1"])
+        .assert()
+        .success();
+    assert!(dir.path().join("contacts.log").exists());
+}
+
+#[test]
+fn generated_data_loads_in_each_tool() {
+    let dir = tempfile::tempdir().unwrap();
+    study(&dir)
+        .args([
+            "gen-data",
+            "--contacts",
+            "300",
+            "--bills",
+            "50",
+            "--seed",
+            "42",
+        ])
+        .args(["--out", "data"])
+        .assert()
+        .success()
+        .stdout("wrote 300 contacts to data/contacts.csv\nwrote 50 bills to data/bills.csv\n");
+    let contacts = fs::read(dir.path().join("data/contacts.csv")).unwrap();
+    study(&dir)
+        .args([
+            "gen-data",
+            "--contacts",
+            "300",
+            "--bills",
+            "0",
+            "--seed",
+            "42",
+        ])
+        .args(["--out", "again"])
+        .assert()
+        .success();
+    assert_eq!(
+        fs::read(dir.path().join("again/contacts.csv")).unwrap(),
+        contacts
+    );
+
+    study(&dir)
+        .args(["contacts", "-d", "data/contacts.csv", "show", "300"])
+        .assert()
+        .success()
+        .stderr("");
+    study(&dir)
+        .args(["bills", "-d", "data/bills.csv"])
+        .write_stdin("list\n")
+        .assert()
+        .success()
+        .stderr("");
+}
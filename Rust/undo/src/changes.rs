@@ -0,0 +1,123 @@
+use crate::Command;
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::{collections::HashMap, hash::Hash};
+
+/// Values kept by id, the shape of everything the programs edit.
+pub trait Table {
+    type Key: Ord + Clone;
+    type Value: Clone + PartialEq;
+    fn get(&self, key: &Self::Key) -> Option<&Self::Value>;
+    fn keys(&self) -> Vec<Self::Key>;
+    fn insert(&mut self, key: Self::Key, value: Self::Value);
+    fn remove(&mut self, key: &Self::Key);
+}
+impl<K: Ord + Clone, V: Clone + PartialEq> Table for BTreeMap<K, V> {
+    type Key = K;
+    type Value = V;
+    fn get(&self, key: &K) -> Option<&V> {
+        BTreeMap::get(self, key)
+    }
+    fn keys(&self) -> Vec<K> {
+        BTreeMap::keys(self).cloned().collect()
+    }
+    fn insert(&mut self, key: K, value: V) {
+        BTreeMap::insert(self, key, value);
+    }
+    fn remove(&mut self, key: &K) {
+        BTreeMap::remove(self, key);
+    }
+}
+#[cfg(feature = "std")]
+impl<K: Ord + Hash + Clone, V: Clone + PartialEq> Table for HashMap<K, V> {
+    type Key = K;
+    type Value = V;
+    fn get(&self, key: &K) -> Option<&V> {
+        HashMap::get(self, key)
+    }
+    fn keys(&self) -> Vec<K> {
+        HashMap::keys(self).cloned().collect()
+    }
+    fn insert(&mut self, key: K, value: V) {
+        HashMap::insert(self, key, value);
+    }
+    fn remove(&mut self, key: &K) {
+        HashMap::remove(self, key);
+    }
+}
+
+/// How a value is written to a journal and read back.
+pub trait Fields: Sized {
+    type Context;
+    fn to_fields(&self) -> Vec<String>;
+    fn from_fields(fields: &[String], context: &Self::Context) -> Option<Self>;
+}
+
+/// Sets each key to a value, or removes it for `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Changes<K, V> {
+    entries: Vec<(K, Option<V>)>,
+}
+impl<K, V> Default for Changes<K, V> {
+    fn default() -> Self {
+        Self { entries: vec![] }
+    }
+}
+impl<K, V> Changes<K, V> {
+    pub fn set(mut self, key: K, value: Option<V>) -> Self {
+        self.entries.push((key, value));
+        self
+    }
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (&K, Option<&V>)> {
+        self.entries
+            .iter()
+            .map(|(key, value)| (key, value.as_ref()))
+    }
+}
+impl<K: Ord + Clone, V: Clone + PartialEq> Changes<K, V> {
+    /// What turns `before` into `after`, in key order.
+    pub fn between<A, B>(before: &A, after: &B) -> Self
+    where
+        A: Table<Key = K, Value = V>,
+        B: Table<Key = K, Value = V>,
+    {
+        let mut keys = before.keys();
+        keys.extend(after.keys());
+        keys.sort();
+        keys.dedup();
+        let entries = keys
+            .into_iter()
+            .filter(|key| before.get(key) != after.get(key))
+            .map(|key| {
+                let value = after.get(&key).cloned();
+                (key, value)
+            })
+            .collect();
+        Self { entries }
+    }
+}
+impl<T: Table> Command<T> for Changes<T::Key, T::Value> {
+    fn apply(&self, target: &mut T) {
+        for (key, value) in &self.entries {
+            match value {
+                Some(value) => target.insert(key.clone(), value.clone()),
+                None => target.remove(key),
+            }
+        }
+    }
+    fn invert(&self, target: &T) -> Self {
+        Self {
+            entries: self
+                .entries
+                .iter()
+                .map(|(key, _)| (key.clone(), target.get(key).cloned()))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::Command;
+use alloc::{collections::VecDeque, vec, vec::Vec};
+
+/// The commands that take back recent changes, dropping the oldest past
+/// `limit`, and the ones that redo what was taken back.
+#[derive(Debug, Clone)]
+pub struct History<C> {
+    pub(crate) undo: VecDeque<C>,
+    pub(crate) redo: Vec<C>,
+    limit: usize,
+}
+impl<C> History<C> {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: vec![],
+            limit,
+        }
+    }
+    /// Remembers `inverse`, which takes back a change that was just made.
+    /// A new change can't be redone over, so this forgets what was undone.
+    pub fn record(&mut self, inverse: C) {
+        self.redo.clear();
+        self.push_undo(inverse);
+    }
+    pub(crate) fn push_undo(&mut self, inverse: C) {
+        if self.limit == 0 {
+            return;
+        }
+        if self.undo.len() == self.limit {
+            self.undo.pop_front();
+        }
+        self.undo.push_back(inverse);
+    }
+    pub fn execute<T>(&mut self, command: C, target: &mut T)
+    where
+        C: Command<T>,
+    {
+        self.record(command.invert(target));
+        command.apply(target);
+    }
+    /// Takes back the last change and returns the command that redoes it.
+    pub fn undo<T>(&mut self, target: &mut T) -> Option<&C>
+    where
+        C: Command<T>,
+    {
+        let inverse = self.undo.pop_back()?;
+        self.redo.push(inverse.invert(target));
+        inverse.apply(target);
+        self.redo.last()
+    }
+    /// Makes the last undone change again and returns the command that takes it back.
+    pub fn redo<T>(&mut self, target: &mut T) -> Option<&C>
+    where
+        C: Command<T>,
+    {
+        let command = self.redo.pop()?;
+        self.push_undo(command.invert(target));
+        command.apply(target);
+        self.undo.back()
+    }
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Changes;
+    use alloc::collections::BTreeMap;
+
+    #[test]
+    fn undoes_and_redoes_within_the_limit() {
+        let mut names = BTreeMap::from([(1, "Rent")]);
+        let mut history = History::new(2);
+        for (id, name) in [(2, "Gym"), (3, "Phone"), (1, "Flat")] {
+            history.execute(Changes::default().set(id, Some(name)), &mut names);
+        }
+        history.execute(Changes::default().set(2, None), &mut names);
+        assert_eq!(names, BTreeMap::from([(1, "Flat"), (3, "Phone")]));
+
+        assert!(history.undo(&mut names).is_some());
+        assert!(history.undo(&mut names).is_some());
+        assert!(history.undo(&mut names).is_none());
+        assert_eq!(
+            names,
+            BTreeMap::from([(1, "Rent"), (2, "Gym"), (3, "Phone")])
+        );
+
+        let redone = history.redo(&mut names).unwrap();
+        assert_eq!(redone.iter().collect::<Vec<_>>(), [(&1, Some(&"Rent"))]);
+        assert_eq!(names[&1], "Flat");
+        history.execute(Changes::default().set(4, Some("Water")), &mut names);
+        assert!(history.redo(&mut names).is_none());
+    }
+}
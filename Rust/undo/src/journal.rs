@@ -0,0 +1,189 @@
+use crate::{Changes, Fields, History};
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+use vfs::{Disk, Vfs};
+
+/// Changes written to a file as they are made: `put,<key>,<fields>` and
+/// `del,<key>` lines, each group closed by a line with its mark. A group
+/// cut off by a crash has no mark and is left out when reading.
+#[derive(Debug, Clone)]
+pub struct Journal {
+    path: PathBuf,
+    vfs: Arc<dyn Vfs>,
+}
+impl Journal {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            vfs: Arc::new(Disk),
+        }
+    }
+    pub fn in_vfs(self, vfs: Arc<dyn Vfs>) -> Self {
+        Self { vfs, ..self }
+    }
+    /// The journal next to `data_file`, named after it with `.<extension>` added.
+    pub fn beside(data_file: &Path, extension: &str) -> Self {
+        let mut file_name = data_file.file_name().unwrap_or_default().to_os_string();
+        file_name.push(".");
+        file_name.push(extension);
+        Self::new(data_file.with_file_name(file_name))
+    }
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    pub fn append<K: fmt::Display, V: Fields>(
+        &self,
+        mark: &str,
+        changes: &Changes<K, V>,
+    ) -> io::Result<()> {
+        self.vfs.append(&self.path, group(mark, changes).as_bytes())
+    }
+    pub fn read<K: FromStr, V: Fields>(
+        &self,
+        context: &V::Context,
+    ) -> io::Result<Vec<(String, Changes<K, V>)>> {
+        let text = match self.vfs.read_to_string(&self.path) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            result => result?,
+        };
+        let mut groups = vec![];
+        let mut changes = Changes::default();
+        let format = flatfile::Format {
+            delimiter: ',',
+            header: false,
+        };
+        for record in flatfile::parse(&text, format).records {
+            let fields = match record {
+                Ok(record) => record.fields,
+                Err(_) => continue,
+            };
+            changes = match fields.as_slice() {
+                [kind, key, row @ ..] if kind == "put" => {
+                    match (key.parse(), V::from_fields(row, context)) {
+                        (Ok(key), Some(value)) => changes.set(key, Some(value)),
+                        _ => changes,
+                    }
+                }
+                [kind, key] if kind == "del" => match key.parse() {
+                    Ok(key) => changes.set(key, None),
+                    Err(_) => changes,
+                },
+                [mark] => {
+                    groups.push((mark.clone(), changes));
+                    Changes::default()
+                }
+                _ => changes,
+            };
+        }
+        Ok(groups)
+    }
+    pub fn clear(&self) -> io::Result<()> {
+        match self.vfs.remove_file(&self.path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Keeps a history for the next run, replacing what was saved before.
+    pub fn save_history<K: fmt::Display, V: Fields>(
+        &self,
+        history: &History<Changes<K, V>>,
+    ) -> io::Result<()> {
+        let mut text = String::new();
+        for changes in &history.undo {
+            text.push_str(&group("undo", changes));
+        }
+        for changes in &history.redo {
+            text.push_str(&group("redo", changes));
+        }
+        self.vfs.write_atomic(&self.path, text.as_bytes())
+    }
+    pub fn load_history<K: FromStr, V: Fields>(
+        &self,
+        limit: usize,
+        context: &V::Context,
+    ) -> io::Result<History<Changes<K, V>>> {
+        let mut history = History::new(limit);
+        for (mark, changes) in self.read(context)? {
+            match mark.as_str() {
+                "undo" => history.push_undo(changes),
+                "redo" => history.redo.push(changes),
+                _ => {}
+            }
+        }
+        Ok(history)
+    }
+}
+
+fn group<K: fmt::Display, V: Fields>(mark: &str, changes: &Changes<K, V>) -> String {
+    let mut text = String::new();
+    for (key, value) in changes.iter() {
+        let mut fields = vec![key.to_string()];
+        match value {
+            Some(value) => {
+                fields.insert(0, "put".to_string());
+                fields.extend(value.to_fields());
+            }
+            None => fields.insert(0, "del".to_string()),
+        }
+        text.push_str(&flatfile::write_record(&fields, ','));
+    }
+    text.push_str(&flatfile::write_record(&[mark], ','));
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Command;
+    use std::collections::BTreeMap;
+    use vfs::Memory;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Name(String);
+    impl Fields for Name {
+        type Context = ();
+        fn to_fields(&self) -> Vec<String> {
+            vec![self.0.clone()]
+        }
+        fn from_fields(fields: &[String], _: &()) -> Option<Self> {
+            Some(Name(fields.first()?.clone()))
+        }
+    }
+
+    #[test]
+    fn keeps_history_and_skips_cut_off_groups() {
+        let memory = Arc::new(Memory::new());
+        let journal = Journal::beside(Path::new("names.csv"), "undo").in_vfs(memory.clone());
+        assert_eq!(journal.path(), Path::new("names.csv.undo"));
+
+        let mut names = BTreeMap::from([(1, Name("Rent".to_string()))]);
+        let mut history = History::new(5);
+        history.execute(
+            Changes::default().set(2, Some(Name("Gym, monthly".to_string()))),
+            &mut names,
+        );
+        history.execute(Changes::default().set(1, None), &mut names);
+        history.undo(&mut names);
+        journal.save_history(&history).unwrap();
+
+        let mut loaded = journal.load_history::<i64, Name>(5, &()).unwrap();
+        assert!(loaded.redo(&mut names).is_some());
+        assert!(loaded.undo(&mut names).is_some());
+        assert!(loaded.undo(&mut names).is_some());
+        assert_eq!(names, BTreeMap::from([(1, Name("Rent".to_string()))]));
+
+        journal.clear().unwrap();
+        let changes = Changes::default().set(3, Some(Name("Phone".to_string())));
+        journal.append("commit", &changes).unwrap();
+        memory.append(journal.path(), b"del,1\n").unwrap();
+        let groups = journal.read::<i64, Name>(&()).unwrap();
+        assert_eq!(groups, [("commit".to_string(), changes.clone())]);
+        changes.apply(&mut names);
+        assert_eq!(names.len(), 2);
+    }
+}
@@ -0,0 +1,22 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod changes;
+mod history;
+#[cfg(feature = "std")]
+mod journal;
+
+pub use changes::{Changes, Fields, Table};
+pub use history::History;
+#[cfg(feature = "std")]
+pub use journal::Journal;
+
+/// An edit that knows how to take itself back.
+pub trait Command<T> {
+    fn apply(&self, target: &mut T);
+    /// The command that restores `target` to how it is now, before this one is applied.
+    fn invert(&self, target: &T) -> Self
+    where
+        Self: Sized;
+}
@@ -0,0 +1,298 @@
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::{
+    cmp::Ordering,
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+use thiserror::Error;
+
+/// Where releases are published. Each one carries a build of every binary
+/// per platform, named like `contact_manager-x86_64-linux`, and beside each
+/// build its SHA-256 in `sha256sum` format, named like
+/// `contact_manager-x86_64-linux.sha256`.
+pub const REPOSITORY: &str = "Srabo93/study-topics";
+const DOWNLOAD_LIMIT: u64 = 256 * 1024 * 1024;
+
+/// The program the tools are built into, with its version, if not their own.
+static HOST: OnceLock<(&'static str, &'static str)> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Asset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    pub assets: Vec<Asset>,
+}
+impl Release {
+    /// The tag without its `v`, like `0.2.0` for `v0.2.0`.
+    pub fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+    pub fn asset(&self, binary: &str) -> Option<&Asset> {
+        self.named(&asset_name(binary))
+    }
+    /// The published checksum of `binary`'s build.
+    pub fn checksum(&self, binary: &str) -> Option<&Asset> {
+        self.named(&checksum_name(binary))
+    }
+    fn named(&self, name: &str) -> Option<&Asset> {
+        self.assets.iter().find(|asset| asset.name == name)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("could not reach the release server")]
+    Http(#[from] ureq::Error),
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("{0} has not published any releases")]
+    NoRelease(String),
+    #[error("release {version} has no {name} build")]
+    NoAsset { name: String, version: String },
+    #[error("release {version} has no {name} checksum")]
+    NoChecksum { name: String, version: String },
+    #[error("the downloaded {0} does not match its published checksum")]
+    Checksum(String),
+}
+impl From<Error> for io::Error {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::Io(e) => e,
+            error => io::Error::other(error),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    UpToDate(String),
+    Available { current: String, latest: String },
+    Updated { from: String, to: String },
+}
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::UpToDate(version) => write!(f, "{} is the latest version", version),
+            Status::Available { current, latest } => {
+                write!(f, "{} is available, this is {}", latest, current)
+            }
+            Status::Updated { from, to } => write!(f, "updated from {} to {}", from, to),
+        }
+    }
+}
+
+/// The name of this platform's build of `binary`.
+pub fn asset_name(binary: &str) -> String {
+    format!(
+        "{}-{}-{}{}",
+        binary,
+        env::consts::ARCH,
+        env::consts::OS,
+        env::consts::EXE_SUFFIX
+    )
+}
+
+fn checksum_name(binary: &str) -> String {
+    format!("{}.sha256", asset_name(binary))
+}
+
+/// Whether `build` has the SHA-256 in `published`, a `sha256sum` line.
+fn matches_checksum(build: &[u8], published: &str) -> bool {
+    let digest: String = Sha256::digest(build)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    published
+        .split_whitespace()
+        .next()
+        .is_some_and(|expected| expected.eq_ignore_ascii_case(&digest))
+}
+
+/// Makes tools built into another program, like `study`, update that
+/// program instead of installing their own build over it.
+pub fn set_host(binary: &'static str, version: &'static str) {
+    let _ = HOST.set((binary, version));
+}
+
+/// The binary to update and its version: the host's if there is one.
+fn running<'a>(binary: &'a str, current: &'a str) -> (&'a str, &'a str) {
+    HOST.get().copied().unwrap_or((binary, current))
+}
+
+/// Compares dotted version numbers, treating anything unreadable as not newer.
+pub fn is_newer(latest: &str, current: &str) -> bool {
+    let parse = |version: &str| {
+        version
+            .split('.')
+            .map(str::parse::<u64>)
+            .collect::<Result<Vec<_>, _>>()
+    };
+    match (parse(latest), parse(current)) {
+        (Ok(latest), Ok(current)) => latest.cmp(&current) == Ordering::Greater,
+        _ => false,
+    }
+}
+
+fn get(url: &str) -> Result<ureq::http::Response<ureq::Body>, Error> {
+    Ok(ureq::get(url)
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", REPOSITORY)
+        .call()?)
+}
+
+pub fn latest(repository: &str) -> Result<Release, Error> {
+    let url = format!(
+        "https://api.github.com/repos/{}/releases/latest",
+        repository
+    );
+    match get(&url) {
+        Ok(mut response) => Ok(response.body_mut().read_json()?),
+        Err(Error::Http(ureq::Error::StatusCode(404))) => {
+            Err(Error::NoRelease(repository.to_string()))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Checks for a newer release of `binary`, currently at version `current`,
+/// and installs it over the running executable unless `check` is set.
+pub fn self_update(binary: &str, current: &str, check: bool) -> Result<Status, Error> {
+    let (binary, current) = running(binary, current);
+    let release = latest(REPOSITORY)?;
+    let latest = release.version().to_string();
+    if !is_newer(&latest, current) {
+        return Ok(Status::UpToDate(current.to_string()));
+    }
+    if check {
+        return Ok(Status::Available {
+            current: current.to_string(),
+            latest,
+        });
+    }
+    let asset = release.asset(binary).ok_or_else(|| Error::NoAsset {
+        name: asset_name(binary),
+        version: latest.clone(),
+    })?;
+    let checksum = release.checksum(binary).ok_or_else(|| Error::NoChecksum {
+        name: asset_name(binary),
+        version: latest.clone(),
+    })?;
+    tracing::debug!("downloading {}", asset.browser_download_url);
+    let build = get(&asset.browser_download_url)?
+        .body_mut()
+        .with_config()
+        .limit(DOWNLOAD_LIMIT)
+        .read_to_vec()?;
+    let published = get(&checksum.browser_download_url)?
+        .body_mut()
+        .read_to_string()?;
+    if !matches_checksum(&build, &published) {
+        return Err(Error::Checksum(asset.name.clone()));
+    }
+    replace(&env::current_exe()?, &build)?;
+    Ok(Status::Updated {
+        from: current.to_string(),
+        to: latest,
+    })
+}
+
+fn sibling(exe: &Path, suffix: &str) -> PathBuf {
+    let mut name = exe.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    exe.with_file_name(name)
+}
+
+/// Swaps `exe` for `build` by renaming, which works even while it runs. The
+/// old copy stays behind as `<exe>.old` where it cannot be deleted yet.
+fn replace(exe: &Path, build: &[u8]) -> io::Result<()> {
+    let new = sibling(exe, ".new");
+    let old = sibling(exe, ".old");
+    fs::write(&new, build)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&new, fs::Permissions::from_mode(0o755))?;
+    }
+    let _ = fs::remove_file(&old);
+    fs::rename(exe, &old)?;
+    if let Err(e) = fs::rename(&new, exe) {
+        fs::rename(&old, exe)?;
+        return Err(e);
+    }
+    let _ = fs::remove_file(&old);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_versions() {
+        assert!(is_newer("0.10.0", "0.9.1"));
+        assert!(is_newer("1.0.0.1", "1.0.0"));
+        assert!(!is_newer("0.1.0", "0.1.0"));
+        assert!(!is_newer("0.2.0-beta", "0.1.0"));
+    }
+
+    #[test]
+    fn finds_this_platforms_build() {
+        let release: Release = serde_json::from_value(serde_json::json!({
+            "tag_name": "v0.2.0",
+            "assets": [
+                {"name": "bill_manager-sparc-plan9", "browser_download_url": "https://example.com/1"},
+                {"name": asset_name("bill_manager"), "browser_download_url": "https://example.com/2"},
+                {"name": checksum_name("bill_manager"), "browser_download_url": "https://example.com/3"},
+            ],
+        }))
+        .unwrap();
+        assert_eq!(release.version(), "0.2.0");
+        assert_eq!(
+            release
+                .asset("bill_manager")
+                .map(|asset| asset.browser_download_url.as_str()),
+            Some("https://example.com/2")
+        );
+        assert_eq!(
+            release
+                .checksum("bill_manager")
+                .map(|asset| asset.browser_download_url.as_str()),
+            Some("https://example.com/3")
+        );
+        assert_eq!(release.asset("contact_manager"), None);
+        assert_eq!(release.checksum("contact_manager"), None);
+    }
+
+    #[test]
+    fn checks_builds_against_their_checksum() {
+        let published =
+            "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855  bill_manager\n";
+        assert!(matches_checksum(b"", published));
+        assert!(!matches_checksum(b"new build", published));
+        assert!(!matches_checksum(b"", ""));
+    }
+
+    #[test]
+    fn hosts_update_themselves() {
+        assert_eq!(running("bill_manager", "0.1.0"), ("bill_manager", "0.1.0"));
+        set_host("study", "0.3.0");
+        assert_eq!(running("bill_manager", "0.1.0"), ("study", "0.3.0"));
+    }
+
+    #[test]
+    fn replaces_the_executable() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join("contact_manager");
+        fs::write(&exe, "old build").unwrap();
+        replace(&exe, b"new build").unwrap();
+        assert_eq!(fs::read_to_string(&exe).unwrap(), "new build");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
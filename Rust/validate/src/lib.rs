@@ -0,0 +1,121 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use chrono::NaiveDate;
+use thiserror::Error;
+
+/// Why an input was rejected.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum Invalid {
+    #[error("invalid email address {0}, expected something like ann@example.com")]
+    Email(String),
+    #[error("invalid phone number {0}, expected 3 to 15 digits")]
+    Phone(String),
+    #[error("invalid date {input}, expected a date like {example}")]
+    Date { input: String, example: String },
+    #[error("invalid currency {0}, expected a three-letter code like EUR")]
+    Currency(String),
+    #[error("amount cannot be negative")]
+    Negative,
+}
+
+/// Checks the shape of an address: a local part, one `@` and a dotted domain.
+pub fn email(input: &str) -> Result<String, Invalid> {
+    let input = input.trim();
+    let valid = match input.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && !input.chars().any(char::is_whitespace)
+                && domain.split('.').count() > 1
+                && domain.split('.').all(|label| !label.is_empty())
+        }
+        None => false,
+    };
+    match valid {
+        true => Ok(input.to_string()),
+        false => Err(Invalid::Email(input.to_string())),
+    }
+}
+
+/// Strips spaces, dashes, dots and parentheses, keeping a leading `+`:
+/// `+49 (30) 123-456` becomes `+4930123456`.
+pub fn phone(input: &str) -> Result<String, Invalid> {
+    let input = input.trim();
+    let (plus, rest) = match input.strip_prefix('+') {
+        Some(rest) => ("+", rest),
+        None => ("", input),
+    };
+    let mut digits = String::from(plus);
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => digits.push(c),
+            ' ' | '-' | '.' | '(' | ')' => {}
+            _ => return Err(Invalid::Phone(input.to_string())),
+        }
+    }
+    match digits.len() - plus.len() {
+        3..=15 => Ok(digits),
+        _ => Err(Invalid::Phone(input.to_string())),
+    }
+}
+
+pub fn date(input: &str, format: &str) -> Result<NaiveDate, Invalid> {
+    NaiveDate::parse_from_str(input.trim(), format).map_err(|_| Invalid::Date {
+        input: input.to_string(),
+        example: NaiveDate::from_ymd_opt(1990, 2, 3)
+            .map(|date| date.format(format).to_string())
+            .unwrap_or_default(),
+    })
+}
+
+/// Accepts codes in any case and returns them in upper case.
+pub fn currency(input: &str) -> Result<String, Invalid> {
+    let code = input.trim().to_ascii_uppercase();
+    match code.len() == 3 && code.chars().all(|c| c.is_ascii_uppercase()) {
+        true => Ok(code),
+        false => Err(Invalid::Currency(input.to_string())),
+    }
+}
+
+pub fn non_negative<T: PartialOrd + Default>(amount: T) -> Result<T, Invalid> {
+    match amount < T::default() {
+        true => Err(Invalid::Negative),
+        false => Ok(amount),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_and_normalizes() {
+        assert_eq!(email(" ann@example.com ").unwrap(), "ann@example.com");
+        for bad in [
+            "ann",
+            "ann@",
+            "@example.com",
+            "ann@example",
+            "a b@x.org",
+            "a@x..org",
+        ] {
+            assert_eq!(email(bad), Err(Invalid::Email(bad.to_string())));
+        }
+        assert_eq!(phone("+49 (30) 123-456").unwrap(), "+4930123456");
+        assert_eq!(phone("030.123").unwrap(), "030123");
+        assert!(phone("12").is_err());
+        assert!(phone("call me").is_err());
+        assert_eq!(currency("chf").unwrap(), "CHF");
+        assert!(currency("EURO").is_err());
+        assert_eq!(
+            date("1990-02-03", "%Y-%m-%d").unwrap(),
+            NaiveDate::from_ymd_opt(1990, 2, 3).unwrap()
+        );
+        assert!(date("1990-02-30", "%Y-%m-%d").is_err());
+        assert_eq!(non_negative(0), Ok(0));
+        assert_eq!(non_negative(-1), Err(Invalid::Negative));
+    }
+}
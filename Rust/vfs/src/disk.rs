@@ -0,0 +1,66 @@
+use crate::Vfs;
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// The real filesystem.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Disk;
+impl Vfs for Disk {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        fs::read(path)
+    }
+    fn write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        fs::write(path, data)
+    }
+    fn append(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.write_all(data)?;
+        file.sync_data()
+    }
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        fs::rename(from, to)
+    }
+    fn hard_link(&self, original: &Path, link: &Path) -> io::Result<()> {
+        fs::hard_link(original, link)
+    }
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        fs::remove_file(path)
+    }
+    fn modified(&self, path: &Path) -> io::Result<SystemTime> {
+        fs::metadata(path)?.modified()
+    }
+    fn exists(&self, path: &Path) -> io::Result<bool> {
+        path.try_exists()
+    }
+    fn list(&self, dir: &Path) -> io::Result<Vec<PathBuf>> {
+        let read = match dir.as_os_str().is_empty() {
+            true => fs::read_dir(".")?,
+            false => fs::read_dir(dir)?,
+        };
+        let mut paths = vec![];
+        for entry in read {
+            let entry = entry?;
+            if entry.file_type()?.is_file() {
+                paths.push(dir.join(entry.file_name()));
+            }
+        }
+        paths.sort();
+        Ok(paths)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::behaves_like_a_filesystem;
+
+    #[test]
+    fn behaves_like_the_memory_filesystem() {
+        let dir = tempfile::tempdir().unwrap();
+        behaves_like_a_filesystem(&Disk, dir.path());
+    }
+}
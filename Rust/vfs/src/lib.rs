@@ -0,0 +1,88 @@
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+mod disk;
+mod memory;
+
+pub use disk::Disk;
+pub use memory::Memory;
+
+/// The file operations loading and saving are built from.
+pub trait Vfs: fmt::Debug + Send + Sync {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>>;
+    /// Creates or replaces the file at `path`.
+    fn write(&self, path: &Path, data: &[u8]) -> io::Result<()>;
+    /// Adds `data` to the end of the file, creating it if needed, and waits
+    /// until it is stored.
+    fn append(&self, path: &Path, data: &[u8]) -> io::Result<()>;
+    /// Moves `from` over `to` in one step, replacing what was there.
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()>;
+    /// Makes `link` name the file at `original`, failing with `AlreadyExists`
+    /// if it is taken. Lock files rely on this being atomic.
+    fn hard_link(&self, original: &Path, link: &Path) -> io::Result<()>;
+    fn remove_file(&self, path: &Path) -> io::Result<()>;
+    fn modified(&self, path: &Path) -> io::Result<SystemTime>;
+    fn exists(&self, path: &Path) -> io::Result<bool>;
+    /// The files directly in `dir`, in order; an empty path is the current
+    /// directory.
+    fn list(&self, dir: &Path) -> io::Result<Vec<PathBuf>>;
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        String::from_utf8(self.read(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+    /// Writes beside `path` first and renames the result into place, so a
+    /// crash leaves either the old file or the new one, never half of it.
+    fn write_atomic(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        let pending = temporary(path);
+        self.write(&pending, data)?;
+        self.rename(&pending, path)
+    }
+}
+
+/// Where `write_atomic` puts the new contents of `path` before renaming them
+/// into place, like `bills.csv.tmp`.
+pub fn temporary(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".tmp");
+    path.with_file_name(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The behaviour both implementations must share.
+    pub fn behaves_like_a_filesystem(vfs: &dyn Vfs, dir: &Path) {
+        let file = dir.join("bills.csv");
+        assert_eq!(vfs.read(&file).unwrap_err().kind(), io::ErrorKind::NotFound);
+        assert!(!vfs.exists(&file).unwrap());
+        vfs.write_atomic(&file, b"name\n").unwrap();
+        assert!(!vfs.exists(&temporary(&file)).unwrap());
+        vfs.append(&file, b"Rent\n").unwrap();
+        assert_eq!(vfs.read_to_string(&file).unwrap(), "name\nRent\n");
+
+        let link = dir.join("bills.csv.lock");
+        vfs.hard_link(&file, &link).unwrap();
+        assert_eq!(
+            vfs.hard_link(&file, &link).unwrap_err().kind(),
+            io::ErrorKind::AlreadyExists
+        );
+        vfs.remove_file(&file).unwrap();
+        assert_eq!(vfs.read_to_string(&link).unwrap(), "name\nRent\n");
+
+        let moved = dir.join("moved.csv");
+        let before = vfs.modified(&link).unwrap();
+        vfs.rename(&link, &moved).unwrap();
+        assert!(!vfs.exists(&link).unwrap());
+        assert_eq!(vfs.modified(&moved).unwrap(), before);
+        assert_eq!(vfs.list(dir).unwrap(), vec![moved.clone()]);
+        assert_eq!(
+            vfs.remove_file(&link).unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use crate::Vfs;
+use std::{
+    collections::BTreeMap,
+    io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, SystemTime},
+};
+
+#[derive(Debug, Clone)]
+struct File {
+    /// Shared between hard links, so writing through one shows in the others.
+    data: Arc<Mutex<Vec<u8>>>,
+    modified: SystemTime,
+}
+
+/// Files kept in memory, for tests. There are no directories, and the clock
+/// ticks one second per write, so modification times are predictable.
+#[derive(Debug, Default)]
+pub struct Memory {
+    files: Mutex<BTreeMap<PathBuf, File>>,
+    ticks: Mutex<u64>,
+}
+impl Memory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// The paths of every file, in order.
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.files().keys().cloned().collect()
+    }
+    fn files(&self) -> MutexGuard<'_, BTreeMap<PathBuf, File>> {
+        self.files.lock().unwrap_or_else(|e| e.into_inner())
+    }
+    fn tick(&self) -> SystemTime {
+        let mut ticks = self.ticks.lock().unwrap_or_else(|e| e.into_inner());
+        *ticks += 1;
+        SystemTime::UNIX_EPOCH + Duration::from_secs(*ticks)
+    }
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{} does not exist", path.display()),
+    )
+}
+
+impl Vfs for Memory {
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let files = self.files();
+        let file = files.get(path).ok_or_else(|| not_found(path))?;
+        let data = file.data.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(data.clone())
+    }
+    fn write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        let modified = self.tick();
+        let mut files = self.files();
+        let file = files.entry(path.to_path_buf()).or_insert_with(|| File {
+            data: Arc::default(),
+            modified,
+        });
+        file.modified = modified;
+        *file.data.lock().unwrap_or_else(|e| e.into_inner()) = data.to_vec();
+        Ok(())
+    }
+    fn append(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        let modified = self.tick();
+        let mut files = self.files();
+        let file = files.entry(path.to_path_buf()).or_insert_with(|| File {
+            data: Arc::default(),
+            modified,
+        });
+        file.modified = modified;
+        let mut contents = file.data.lock().unwrap_or_else(|e| e.into_inner());
+        contents.extend_from_slice(data);
+        Ok(())
+    }
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let mut files = self.files();
+        let file = files.remove(from).ok_or_else(|| not_found(from))?;
+        files.insert(to.to_path_buf(), file);
+        Ok(())
+    }
+    fn hard_link(&self, original: &Path, link: &Path) -> io::Result<()> {
+        let mut files = self.files();
+        if files.contains_key(link) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already exists", link.display()),
+            ));
+        }
+        let file = files
+            .get(original)
+            .ok_or_else(|| not_found(original))?
+            .clone();
+        files.insert(link.to_path_buf(), file);
+        Ok(())
+    }
+    fn remove_file(&self, path: &Path) -> io::Result<()> {
+        self.files()
+            .remove(path)
+            .map(drop)
+            .ok_or_else(|| not_found(path))
+    }
+    fn modified(&self, path: &Path) -> io::Result<SystemTime> {
+        let files = self.files();
+        Ok(files.get(path).ok_or_else(|| not_found(path))?.modified)
+    }
+    fn exists(&self, path: &Path) -> io::Result<bool> {
+        Ok(self.files().contains_key(path))
+    }
+    fn list(&self, dir: &Path) -> io::Result<Vec<PathBuf>> {
+        let files = self.files();
+        let paths = files.keys().filter(|path| path.parent() == Some(dir));
+        Ok(paths.cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::behaves_like_a_filesystem;
+
+    #[test]
+    fn behaves_like_the_disk() {
+        let memory = Memory::new();
+        behaves_like_a_filesystem(&memory, Path::new("/data"));
+        assert_eq!(memory.paths(), [PathBuf::from("/data/moved.csv")]);
+    }
+}